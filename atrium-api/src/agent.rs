@@ -7,15 +7,60 @@ pub mod store;
 use self::store::SessionStore;
 use crate::client::Service;
 use crate::did_doc::DidDocument;
-use crate::types::string::Did;
+use crate::types::string::{Did, Nsid};
 use crate::types::TryFromUnknown;
 use atrium_xrpc::error::Error;
-use atrium_xrpc::XrpcClient;
+use atrium_xrpc::{OutputDataOrBytes, XrpcClient, XrpcRequest};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
 use std::sync::Arc;
 
 /// Type alias for the [com::atproto::server::create_session::Output](crate::com::atproto::server::create_session::Output)
 pub type Session = crate::com::atproto::server::create_session::Output;
 
+/// A change to the session stored by [`AtpAgent`], passed to the callback
+/// registered with [`AtpAgent::on_session_change()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// A new session was created by [`AtpAgent::login()`].
+    Create,
+    /// A pre-existing session was resumed by [`AtpAgent::resume_session()`].
+    Resume,
+    /// The session tokens were refreshed, and the refresh token may have been rotated.
+    Update,
+    /// Refreshing the session failed, and the session was cleared.
+    Expired,
+}
+
+/// The remaining time before an access token expires, below which it is reported as
+/// [`SessionStatus::ExpiringSoon`].
+const EXPIRING_SOON_SECS: i64 = 5 * 60;
+
+/// The state of the access token of the current session, returned by [`AtpAgent::session_status()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatus {
+    /// The access token is valid.
+    Active,
+    /// The access token expires within 5 minutes.
+    ExpiringSoon,
+    /// The access token has expired, and the session needs to be refreshed.
+    Expired,
+}
+
+impl SessionStatus {
+    fn from_access_jwt(token: &str) -> Self {
+        // A token whose expiration cannot be read is left to the server to reject.
+        let Some(exp) = crate::jwt::decode_claims(token).ok().and_then(|claims| claims.exp) else {
+            return Self::Active;
+        };
+        match exp - chrono::Utc::now().timestamp() {
+            remaining if remaining <= 0 => Self::Expired,
+            remaining if remaining <= EXPIRING_SOON_SECS => Self::ExpiringSoon,
+            _ => Self::Active,
+        }
+    }
+}
+
 /// Supported proxy targets.
 #[cfg(feature = "bluesky")]
 pub type AtprotoServiceType = self::bluesky::AtprotoServiceType;
@@ -79,6 +124,7 @@ where
             )
             .await?;
         self.store.set_session(result.clone()).await;
+        self.store.notify_session_change(SessionEvent::Create, Some(&result));
         if let Some(did_doc) = result
             .did_doc
             .as_ref()
@@ -89,6 +135,9 @@ where
         Ok(result)
     }
     /// Resume a pre-existing session with this agent.
+    ///
+    /// If the access token of the session has expired, the session is refreshed with its
+    /// refresh token first. The session is cleared only if the refresh also fails.
     pub async fn resume_session(
         &self,
         session: Session,
@@ -103,7 +152,8 @@ where
                     session.email = output.data.email;
                     session.email_confirmed = output.data.email_confirmed;
                     session.handle = output.data.handle;
-                    self.store.set_session(session).await;
+                    self.store.set_session(session.clone()).await;
+                    self.store.notify_session_change(SessionEvent::Resume, Some(&session));
                 }
                 if let Some(did_doc) = output
                     .data
@@ -121,6 +171,17 @@ where
             }
         }
     }
+    /// Register a callback which is called whenever the agent changes the stored session,
+    /// e.g. to persist the rotated tokens after a refresh.
+    ///
+    /// The session is `None` for [`SessionEvent::Expired`].
+    /// Replaces any previously registered callback.
+    pub fn on_session_change(
+        &self,
+        callback: impl Fn(SessionEvent, Option<&Session>) + Send + Sync + 'static,
+    ) {
+        self.store.set_session_change_callback(Arc::new(callback));
+    }
     /// Set the current endpoint.
     pub fn configure_endpoint(&self, endpoint: String) {
         self.inner.configure_endpoint(endpoint);
@@ -143,10 +204,123 @@ where
     ) -> Service<inner::Client<S, T>> {
         Service::new(Arc::new(self.inner.clone_with_proxy(did, service_type)))
     }
+    /// Send an arbitrary XRPC request with the agent's session.
+    pub async fn send_xrpc<P, I, O, E>(
+        &self,
+        request: &XrpcRequest<P, I>,
+    ) -> atrium_xrpc::Result<OutputDataOrBytes<O>, E>
+    where
+        P: Serialize + Send + Sync,
+        I: Serialize + Send + Sync,
+        O: DeserializeOwned + Send + Sync,
+        E: DeserializeOwned + Send + Sync + Debug,
+    {
+        self.inner.send_xrpc(request).await
+    }
+    /// Send an arbitrary XRPC request with the agent's session,
+    /// with the atproto-proxy header for this request only.
+    ///
+    /// Unlike [`api_with_proxy()`](Self::api_with_proxy), this does not clone the client.
+    pub async fn send_xrpc_with_proxy<P, I, O, E>(
+        &self,
+        request: &XrpcRequest<P, I>,
+        did: Did,
+        service_type: impl AsRef<str>,
+    ) -> atrium_xrpc::Result<OutputDataOrBytes<O>, E>
+    where
+        P: Serialize + Send + Sync,
+        I: Serialize + Send + Sync,
+        O: DeserializeOwned + Send + Sync,
+        E: DeserializeOwned + Send + Sync + Debug,
+    {
+        self.inner.send_xrpc_with_proxy(request, did, service_type).await
+    }
+    /// Send an arbitrary XRPC request with the agent's session,
+    /// with `extensions` inserted into the HTTP request.
+    ///
+    /// The extensions are passed to the [`HttpClient`](atrium_xrpc::HttpClient) of the agent, e.g. an
+    /// [`UploadProgress`](atrium_xrpc::types::UploadProgress) to report the progress of the upload.
+    /// As with the other requests, the session is refreshed and the request is retried if the
+    /// access token has expired.
+    pub async fn send_xrpc_with_extensions<P, I, O, E>(
+        &self,
+        request: &XrpcRequest<P, I>,
+        extensions: http::Extensions,
+    ) -> atrium_xrpc::Result<OutputDataOrBytes<O>, E>
+    where
+        P: Serialize + Send + Sync,
+        I: Serialize + Send + Sync,
+        O: DeserializeOwned + Send + Sync,
+        E: DeserializeOwned + Send + Sync + Debug,
+    {
+        self.inner.send_xrpc_with_extensions(request, extensions).await
+    }
+    /// Get a service auth token to authenticate with the service `aud`,
+    /// bound to the lexicon method `lxm`.
+    ///
+    /// `exp` is the expiration time in Unix epoch seconds. If `None`, the server defaults to
+    /// 60 seconds in the future.
+    pub async fn service_auth(
+        &self,
+        aud: &Did,
+        lxm: &Nsid,
+        exp: Option<i64>,
+    ) -> Result<String, Error<crate::com::atproto::server::get_service_auth::Error>> {
+        let output = self
+            .api
+            .com
+            .atproto
+            .server
+            .get_service_auth(
+                crate::com::atproto::server::get_service_auth::ParametersData {
+                    aud: aud.clone(),
+                    exp,
+                    lxm: Some(lxm.clone()),
+                }
+                .into(),
+            )
+            .await?;
+        Ok(output.data.token)
+    }
+    /// Get a service auth token for the current endpoint (e.g. the PDS of the session),
+    /// bound to the lexicon method `lxm`.
+    ///
+    /// The audience is `did:web:<host>` of the endpoint. See [`service_auth()`](Self::service_auth).
+    pub async fn service_auth_for_endpoint(
+        &self,
+        lxm: &Nsid,
+        exp: Option<i64>,
+    ) -> Result<String, Error<crate::com::atproto::server::get_service_auth::Error>> {
+        let endpoint = self.store.get_endpoint();
+        let invalid = |reason: &str| Error::InvalidEndpoint(format!("{endpoint}: {reason}"));
+        let uri = endpoint.parse::<http::Uri>().map_err(|e| invalid(&e.to_string()))?;
+        let host = uri.host().ok_or_else(|| invalid("no host"))?;
+        // A port is percent-encoded in did:web
+        let id = match uri.port_u16() {
+            Some(port) => format!("{host}%3A{port}"),
+            None => host.to_string(),
+        };
+        let aud = format!("did:web:{id}").parse::<Did>().map_err(invalid)?;
+        self.service_auth(&aud, lxm, exp).await
+    }
     /// Get the current session.
     pub async fn get_session(&self) -> Option<Session> {
         self.store.get_session().await
     }
+    /// Get the status of the access token of the current session, without making a request.
+    ///
+    /// The `exp` claim of the access token is decoded locally, without verifying its signature.
+    /// Returns `None` if there is no session.
+    pub async fn session_status(&self) -> Option<SessionStatus> {
+        Some(SessionStatus::from_access_jwt(&self.store.get_session().await?.access_jwt))
+    }
+    /// Get the underlying XRPC client.
+    ///
+    /// Requests sent with it directly have none of the session tokens or headers of the agent,
+    /// e.g. for requests to other hosts.
+    pub fn xrpc_client(&self) -> Arc<T> {
+        self.inner.xrpc_client()
+    }
     /// Get the current endpoint.
     pub async fn get_endpoint(&self) -> String {
         self.store.get_endpoint()
@@ -169,6 +343,8 @@ mod tests {
     use crate::did_doc::{DidDocument, Service, VerificationMethod};
     use crate::types::TryIntoUnknown;
     use atrium_xrpc::HttpClient;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
     use http::{HeaderMap, HeaderName, HeaderValue, Request, Response};
     use std::collections::HashMap;
     use tokio::sync::RwLock;
@@ -186,8 +362,12 @@ mod tests {
         responses: MockResponses,
         counts: Arc<RwLock<HashMap<String, usize>>>,
         headers: Arc<RwLock<Vec<HeaderMap<HeaderValue>>>>,
+        extensions: Arc<RwLock<Vec<Option<MockExtension>>>>,
     }
 
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct MockExtension(&'static str);
+
     impl HttpClient for MockClient {
         async fn send_http(
             &self,
@@ -197,6 +377,7 @@ mod tests {
             tokio::time::sleep(std::time::Duration::from_micros(10)).await;
 
             self.headers.write().await.push(request.headers().clone());
+            self.extensions.write().await.push(request.extensions().get().cloned());
             let builder =
                 Response::builder().header(http::header::CONTENT_TYPE, "application/json");
             let token = request
@@ -255,6 +436,16 @@ mod tests {
                             },
                         )?);
                     }
+                    crate::com::atproto::server::get_service_auth::NSID => {
+                        if token == Some("access") {
+                            // Echo the parameters, to check them in tests
+                            body.extend(serde_json::to_vec(
+                                &crate::com::atproto::server::get_service_auth::OutputData {
+                                    token: request.uri().query().unwrap_or_default().into(),
+                                },
+                            )?);
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -401,6 +592,102 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn test_on_session_change() {
+        let session_data = session_data();
+        let client = MockClient {
+            responses: MockResponses {
+                create_session: Some(crate::com::atproto::server::create_session::OutputData {
+                    access_jwt: String::from("expired"),
+                    ..session_data.clone()
+                }),
+                get_session: Some(crate::com::atproto::server::get_session::OutputData {
+                    active: session_data.active,
+                    did: session_data.did.clone(),
+                    did_doc: session_data.did_doc.clone(),
+                    email: session_data.email.clone(),
+                    email_auth_factor: session_data.email_auth_factor,
+                    email_confirmed: session_data.email_confirmed,
+                    handle: session_data.handle.clone(),
+                    status: session_data.status.clone(),
+                }),
+            },
+            ..Default::default()
+        };
+        let agent = AtpAgent::new(client, MemorySessionStore::default());
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        {
+            let events = Arc::clone(&events);
+            agent.on_session_change(move |event, session| {
+                events
+                    .lock()
+                    .expect("failed to lock events")
+                    .push((event, session.map(|session| session.access_jwt.clone())));
+            });
+        }
+        agent.login("test", "pass").await.expect("login should be succeeded");
+        agent.api.com.atproto.server.get_session().await.expect("get session should be succeeded");
+        assert_eq!(
+            events.lock().expect("failed to lock events").clone(),
+            vec![
+                (SessionEvent::Create, Some(String::from("expired"))),
+                (SessionEvent::Update, Some(String::from("access"))),
+            ]
+        );
+        agent
+            .resume_session(session_data.clone().into())
+            .await
+            .expect("resume session should be succeeded");
+        assert_eq!(
+            events.lock().expect("failed to lock events").last(),
+            Some(&(SessionEvent::Resume, Some(String::from("access"))))
+        );
+        // failure with invalid refresh token
+        agent
+            .store
+            .set_session(
+                OutputData {
+                    access_jwt: String::from("expired"),
+                    refresh_jwt: String::from("invalid"),
+                    ..session_data
+                }
+                .into(),
+            )
+            .await;
+        agent.api.com.atproto.server.get_session().await.expect_err("get session should be failed");
+        assert_eq!(
+            events.lock().expect("failed to lock events").last(),
+            Some(&(SessionEvent::Expired, None))
+        );
+        assert_eq!(agent.get_session().await, None);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn test_on_session_change_reentrant() {
+        let client = MockClient {
+            responses: MockResponses { create_session: Some(session_data()), ..Default::default() },
+            ..Default::default()
+        };
+        let agent = Arc::new(AtpAgent::new(client, MemorySessionStore::default()));
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        {
+            let agent_ref = Arc::downgrade(&agent);
+            let events = Arc::clone(&events);
+            agent.on_session_change(move |event, _| {
+                events.lock().expect("failed to lock events").push(event);
+                // replacing the callback from the callback itself must not deadlock
+                if let Some(agent) = agent_ref.upgrade() {
+                    agent.on_session_change(|_, _| {});
+                }
+            });
+        }
+        agent.login("test", "pass").await.expect("login should be succeeded");
+        agent.login("test", "pass").await.expect("login should be succeeded");
+        assert_eq!(events.lock().expect("failed to lock events").clone(), [SessionEvent::Create]);
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     #[tokio::test]
     async fn test_xrpc_get_session_with_duplicated_refresh() {
@@ -531,6 +818,19 @@ mod tests {
             .await
             .expect("resume_session should be succeeded");
         assert_eq!(agent.get_session().await, Some(session_data.clone().into()));
+        // failure with an invalid refresh token
+        agent
+            .resume_session(
+                OutputData {
+                    access_jwt: "expired".into(),
+                    refresh_jwt: "invalid".into(),
+                    ..session_data.clone()
+                }
+                .into(),
+            )
+            .await
+            .expect_err("resume_session should be failed");
+        assert_eq!(agent.get_session().await, None);
     }
 
     #[tokio::test]
@@ -775,4 +1075,195 @@ mod tests {
             Some(String::from("did:plc:test1#atproto_labeler"))
         );
     }
+
+    #[cfg(feature = "bluesky")]
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn test_chat_proxy_header() {
+        use crate::agent::bluesky::BSKY_CHAT_DID;
+
+        let client = MockClient::default();
+        let headers = Arc::clone(&client.headers);
+        let agent = AtpAgent::new(client, MemorySessionStore::default());
+        // the mock has no response for chat endpoints, only the sent headers are checked
+        let _ = agent
+            .api_with_proxy(
+                BSKY_CHAT_DID.parse().expect("did should be valid"),
+                AtprotoServiceType::BskyChat,
+            )
+            .chat
+            .bsky
+            .convo
+            .list_convos(
+                crate::chat::bsky::convo::list_convos::ParametersData { cursor: None, limit: None }
+                    .into(),
+            )
+            .await;
+        assert_eq!(
+            headers.read().await.last(),
+            Some(&HeaderMap::from_iter([(
+                HeaderName::from_static("atproto-proxy"),
+                HeaderValue::from_static("did:web:api.bsky.chat#bsky_chat"),
+            ),]))
+        );
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn test_request_proxy_header() {
+        let client = MockClient::default();
+        let headers = Arc::clone(&client.headers);
+        let agent = AtpAgent::new(client, MemorySessionStore::default());
+        agent.configure_proxy_header(
+            "did:plc:test1".parse().expect("did should be valid"),
+            AtprotoServiceType::AtprotoLabeler,
+        );
+        let request = XrpcRequest::<(), ()> {
+            method: http::Method::GET,
+            nsid: crate::com::atproto::server::describe_server::NSID.into(),
+            parameters: None,
+            input: None,
+            encoding: None,
+        };
+
+        agent
+            .send_xrpc_with_proxy::<_, _, crate::com::atproto::server::describe_server::Output, ()>(
+                &request,
+                "did:plc:test2".parse().expect("did should be valid"),
+                AtprotoServiceType::AtprotoLabeler,
+            )
+            .await
+            .expect("describe_server should be succeeded");
+        assert_eq!(
+            headers.read().await.last(),
+            Some(&HeaderMap::from_iter([(
+                HeaderName::from_static("atproto-proxy"),
+                HeaderValue::from_static("did:plc:test2#atproto_labeler"),
+            ),]))
+        );
+
+        agent
+            .api
+            .com
+            .atproto
+            .server
+            .describe_server()
+            .await
+            .expect("describe_server should be succeeded");
+        assert_eq!(
+            headers.read().await.last(),
+            Some(&HeaderMap::from_iter([(
+                HeaderName::from_static("atproto-proxy"),
+                HeaderValue::from_static("did:plc:test1#atproto_labeler"),
+            ),]))
+        );
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn test_request_extensions() {
+        let mut session_data = session_data();
+        session_data.access_jwt = String::from("expired");
+        let client = MockClient {
+            responses: MockResponses {
+                get_session: Some(crate::com::atproto::server::get_session::OutputData {
+                    active: session_data.active,
+                    did: session_data.did.clone(),
+                    did_doc: session_data.did_doc.clone(),
+                    email: session_data.email.clone(),
+                    email_auth_factor: session_data.email_auth_factor,
+                    email_confirmed: session_data.email_confirmed,
+                    handle: session_data.handle.clone(),
+                    status: session_data.status.clone(),
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let extensions = Arc::clone(&client.extensions);
+        let agent = AtpAgent::new(client, MemorySessionStore::default());
+        agent.store.set_session(session_data.into()).await;
+        let request = XrpcRequest::<(), ()> {
+            method: http::Method::GET,
+            nsid: crate::com::atproto::server::get_session::NSID.into(),
+            parameters: None,
+            input: None,
+            encoding: None,
+        };
+        let mut request_extensions = http::Extensions::new();
+        request_extensions.insert(MockExtension("test"));
+
+        agent
+            .send_xrpc_with_extensions::<_, _, crate::com::atproto::server::get_session::Output, ()>(
+                &request,
+                request_extensions,
+            )
+            .await
+            .expect("get_session should be succeeded");
+        // The request is retried with the extensions after refreshing the session.
+        assert_eq!(
+            *extensions.read().await,
+            [Some(MockExtension("test")), None, Some(MockExtension("test"))]
+        );
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn test_session_status() {
+        fn jwt(exp: i64) -> String {
+            let claims = serde_json::json!({ "sub": "did:web:example.com", "exp": exp });
+            format!("header.{}.signature", URL_SAFE_NO_PAD.encode(claims.to_string()))
+        }
+
+        let agent = AtpAgent::new(MockClient::default(), MemorySessionStore::default());
+        assert_eq!(agent.session_status().await, None);
+
+        let now = chrono::Utc::now().timestamp();
+        for (access_jwt, expected) in [
+            (jwt(now + 3600), SessionStatus::Active),
+            (jwt(now + 60), SessionStatus::ExpiringSoon),
+            (jwt(now - 60), SessionStatus::Expired),
+            (String::from("access"), SessionStatus::Active),
+        ] {
+            agent.store.set_session(OutputData { access_jwt, ..session_data() }.into()).await;
+            assert_eq!(agent.session_status().await, Some(expected));
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn test_service_auth() {
+        let agent = AtpAgent::new(MockClient::default(), MemorySessionStore::default());
+        agent.store.set_session(session_data().into()).await;
+        let lxm = crate::com::atproto::repo::upload_blob::NSID.parse::<Nsid>().expect("valid");
+        assert_eq!(
+            agent
+                .service_auth(&"did:web:video.bsky.app".parse().expect("valid"), &lxm, Some(100))
+                .await
+                .expect("service_auth should be succeeded"),
+            "aud=did%3Aweb%3Avideo.bsky.app&exp=100&lxm=com.atproto.repo.uploadBlob"
+        );
+        assert_eq!(
+            agent
+                .service_auth_for_endpoint(&lxm, None)
+                .await
+                .expect("service_auth_for_endpoint should be succeeded"),
+            "aud=did%3Aweb%3Alocalhost%253A8080&lxm=com.atproto.repo.uploadBlob"
+        );
+        agent.configure_endpoint(String::from("https://pds.example.com"));
+        assert_eq!(
+            agent
+                .service_auth_for_endpoint(&lxm, None)
+                .await
+                .expect("service_auth_for_endpoint should be succeeded"),
+            "aud=did%3Aweb%3Apds.example.com&lxm=com.atproto.repo.uploadBlob"
+        );
+        for endpoint in ["/xrpc", "https://[::1]"] {
+            agent.configure_endpoint(String::from(endpoint));
+            assert!(matches!(
+                agent.service_auth_for_endpoint(&lxm, None).await,
+                Err(Error::InvalidEndpoint(_))
+            ));
+        }
+    }
 }
@@ -9,6 +9,7 @@ pub const BSKY_CHAT_DID: &str = "did:web:api.bsky.chat";
 pub enum AtprotoServiceType {
     AtprotoLabeler,
     BskyChat,
+    BskyFeedGenerator,
 }
 
 impl AsRef<str> for AtprotoServiceType {
@@ -16,6 +17,7 @@ impl AsRef<str> for AtprotoServiceType {
         match self {
             Self::AtprotoLabeler => "atproto_labeler",
             Self::BskyChat => "bsky_chat",
+            Self::BskyFeedGenerator => "bsky_fg",
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::{Session, SessionStore};
+use super::{Session, SessionEvent, SessionStore};
 use crate::did_doc::DidDocument;
 use crate::types::{string::Did, TryFromUnknown};
 use atrium_xrpc::{
@@ -6,7 +6,7 @@ use atrium_xrpc::{
     types::AuthorizationToken,
     HttpClient, OutputDataOrBytes, XrpcClient, XrpcRequest,
 };
-use http::{Method, Request, Response};
+use http::{Extensions, Method, Request, Response};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     fmt::Debug,
@@ -93,6 +93,85 @@ where
     }
 }
 
+/// A [`WrapperClient`] with the atproto-proxy header overridden, for a single request.
+struct ProxiedClient<'a, S, T> {
+    inner: &'a WrapperClient<S, T>,
+    proxy_header: String,
+}
+
+impl<S, T> HttpClient for ProxiedClient<'_, S, T>
+where
+    S: Send + Sync,
+    T: HttpClient + Send + Sync,
+{
+    async fn send_http(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        self.inner.send_http(request).await
+    }
+}
+
+impl<S, T> XrpcClient for ProxiedClient<'_, S, T>
+where
+    S: SessionStore + Send + Sync,
+    T: XrpcClient + Send + Sync,
+{
+    fn base_uri(&self) -> String {
+        self.inner.base_uri()
+    }
+    async fn authorization_token(&self, is_refresh: bool) -> Option<AuthorizationToken> {
+        self.inner.authorization_token(is_refresh).await
+    }
+    async fn atproto_proxy_header(&self) -> Option<String> {
+        Some(self.proxy_header.clone())
+    }
+    async fn atproto_accept_labelers_header(&self) -> Option<Vec<String>> {
+        self.inner.atproto_accept_labelers_header().await
+    }
+}
+
+/// A [`WrapperClient`] which inserts extensions into the HTTP request, for a single request.
+struct ExtendedClient<'a, S, T> {
+    inner: &'a WrapperClient<S, T>,
+    extensions: Extensions,
+}
+
+impl<S, T> HttpClient for ExtendedClient<'_, S, T>
+where
+    S: Send + Sync,
+    T: HttpClient + Send + Sync,
+{
+    async fn send_http(
+        &self,
+        mut request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        request.extensions_mut().extend(self.extensions.clone());
+        self.inner.send_http(request).await
+    }
+}
+
+impl<S, T> XrpcClient for ExtendedClient<'_, S, T>
+where
+    S: SessionStore + Send + Sync,
+    T: XrpcClient + Send + Sync,
+{
+    fn base_uri(&self) -> String {
+        self.inner.base_uri()
+    }
+    async fn authorization_token(&self, is_refresh: bool) -> Option<AuthorizationToken> {
+        self.inner.authorization_token(is_refresh).await
+    }
+    async fn atproto_proxy_header(&self) -> Option<String> {
+        self.inner.atproto_proxy_header().await
+    }
+    async fn atproto_accept_labelers_header(&self) -> Option<Vec<String>> {
+        self.inner.atproto_accept_labelers_header().await
+    }
+}
+
 pub struct Client<S, T> {
     store: Arc<Store<S>>,
     inner: WrapperClient<S, T>,
@@ -119,6 +198,9 @@ where
             notify: Arc::new(Notify::new()),
         }
     }
+    pub fn xrpc_client(&self) -> Arc<T> {
+        Arc::clone(&self.inner.inner)
+    }
     pub fn configure_endpoint(&self, endpoint: String) {
         *self.store.endpoint.write().expect("failed to write endpoint") = endpoint;
     }
@@ -139,6 +221,60 @@ where
     pub async fn get_proxy_header(&self) -> Option<String> {
         self.inner.atproto_proxy_header().await
     }
+    pub async fn send_xrpc_with_proxy<P, I, O, E>(
+        &self,
+        request: &XrpcRequest<P, I>,
+        did: Did,
+        service_type: impl AsRef<str>,
+    ) -> Result<OutputDataOrBytes<O>, E>
+    where
+        P: Serialize + Send + Sync,
+        I: Serialize + Send + Sync,
+        O: DeserializeOwned + Send + Sync,
+        E: DeserializeOwned + Send + Sync + Debug,
+    {
+        let client = ProxiedClient {
+            inner: &self.inner,
+            proxy_header: format!("{}#{}", did.as_ref(), service_type.as_ref()),
+        };
+        self.send_xrpc_with(&client, request).await
+    }
+    pub async fn send_xrpc_with_extensions<P, I, O, E>(
+        &self,
+        request: &XrpcRequest<P, I>,
+        extensions: Extensions,
+    ) -> Result<OutputDataOrBytes<O>, E>
+    where
+        P: Serialize + Send + Sync,
+        I: Serialize + Send + Sync,
+        O: DeserializeOwned + Send + Sync,
+        E: DeserializeOwned + Send + Sync + Debug,
+    {
+        let client = ExtendedClient { inner: &self.inner, extensions };
+        self.send_xrpc_with(&client, request).await
+    }
+    // Send a request with `client`, refreshing the session and retrying if the token has expired.
+    async fn send_xrpc_with<C, P, I, O, E>(
+        &self,
+        client: &C,
+        request: &XrpcRequest<P, I>,
+    ) -> Result<OutputDataOrBytes<O>, E>
+    where
+        C: XrpcClient + Send + Sync,
+        P: Serialize + Send + Sync,
+        I: Serialize + Send + Sync,
+        O: DeserializeOwned + Send + Sync,
+        E: DeserializeOwned + Send + Sync + Debug,
+    {
+        let result = client.send_xrpc(request).await;
+        // handle session-refreshes as needed
+        if Self::is_expired(&result) {
+            self.refresh_session().await;
+            client.send_xrpc(request).await
+        } else {
+            result
+        }
+    }
     // Internal helper to refresh sessions
     // - Wraps the actual implementation to ensure only one refresh is attempted at a time.
     async fn refresh_session(&self) {
@@ -163,7 +299,8 @@ where
                 session.did_doc = output.data.did_doc.clone();
                 session.handle = output.data.handle;
                 session.refresh_jwt = output.data.refresh_jwt;
-                self.store.set_session(session).await;
+                self.store.set_session(session.clone()).await;
+                self.store.notify_session_change(SessionEvent::Update, Some(&session));
             }
             if let Some(did_doc) = output
                 .data
@@ -175,6 +312,7 @@ where
             }
         } else {
             self.store.clear_session().await;
+            self.store.notify_session_change(SessionEvent::Expired, None);
         }
     }
     // same as `crate::client::com::atproto::server::Service::refresh_session()`
@@ -184,8 +322,7 @@ where
         crate::com::atproto::server::refresh_session::Output,
         crate::com::atproto::server::refresh_session::Error,
     > {
-        let response = self
-            .inner
+        self.inner
             .send_xrpc::<(), (), _, _>(&XrpcRequest {
                 method: Method::POST,
                 nsid: crate::com::atproto::server::refresh_session::NSID.into(),
@@ -193,11 +330,8 @@ where
                 input: None,
                 encoding: None,
             })
-            .await?;
-        match response {
-            OutputDataOrBytes::Data(data) => Ok(data),
-            _ => Err(Error::UnexpectedResponseType),
-        }
+            .await?
+            .into_data()
     }
     fn is_expired<O, E>(result: &Result<OutputDataOrBytes<O>, E>) -> bool
     where
@@ -262,25 +396,37 @@ where
         O: DeserializeOwned + Send + Sync,
         E: DeserializeOwned + Send + Sync + Debug,
     {
-        let result = self.inner.send_xrpc(request).await;
-        // handle session-refreshes as needed
-        if Self::is_expired(&result) {
-            self.refresh_session().await;
-            self.inner.send_xrpc(request).await
-        } else {
-            result
-        }
+        self.send_xrpc_with(&self.inner, request).await
     }
 }
 
+type SessionChangeCallback = Arc<dyn Fn(SessionEvent, Option<&Session>) + Send + Sync>;
+
 pub struct Store<S> {
     inner: S,
     endpoint: RwLock<String>,
+    on_session_change: RwLock<Option<SessionChangeCallback>>,
 }
 
 impl<S> Store<S> {
     pub fn new(inner: S, initial_endpoint: String) -> Self {
-        Self { inner, endpoint: RwLock::new(initial_endpoint) }
+        Self {
+            inner,
+            endpoint: RwLock::new(initial_endpoint),
+            on_session_change: RwLock::new(None),
+        }
+    }
+    pub fn set_session_change_callback(&self, callback: SessionChangeCallback) {
+        *self.on_session_change.write().expect("failed to write session change callback") =
+            Some(callback);
+    }
+    pub fn notify_session_change(&self, event: SessionEvent, session: Option<&Session>) {
+        // Release the lock before calling, so that the callback can replace itself.
+        let callback =
+            self.on_session_change.read().expect("failed to read session change callback").clone();
+        if let Some(callback) = callback {
+            callback(event, session);
+        }
     }
     pub fn get_endpoint(&self) -> String {
         self.endpoint.read().expect("failed to read endpoint").clone()
@@ -1,7 +1,16 @@
+#[cfg(feature = "encrypted-session-store")]
+mod encrypted;
+#[cfg(all(feature = "file-session-store", not(target_arch = "wasm32")))]
+mod file;
 mod memory;
 
 use std::future::Future;
 
+#[cfg(feature = "encrypted-session-store")]
+pub use self::encrypted::EncryptedSessionStore;
+#[cfg_attr(docsrs, doc(cfg(feature = "file-session-store")))]
+#[cfg(all(feature = "file-session-store", not(target_arch = "wasm32")))]
+pub use self::file::FileSessionStore;
 pub use self::memory::MemorySessionStore;
 pub(crate) use super::Session;
 
@@ -14,3 +23,31 @@ pub trait SessionStore {
     #[must_use]
     fn clear_session(&self) -> impl Future<Output = ()>;
 }
+
+#[cfg(all(
+    test,
+    any(
+        feature = "encrypted-session-store",
+        all(feature = "file-session-store", not(target_arch = "wasm32"))
+    )
+))]
+mod tests {
+    use super::Session;
+    use crate::com::atproto::server::create_session::OutputData;
+
+    pub(super) fn session() -> Session {
+        OutputData {
+            access_jwt: String::from("access"),
+            active: None,
+            did: "did:web:example.com".parse().expect("valid"),
+            did_doc: None,
+            email: None,
+            email_auth_factor: None,
+            email_confirmed: None,
+            handle: "example.com".parse().expect("valid"),
+            refresh_jwt: String::from("refresh"),
+            status: None,
+        }
+        .into()
+    }
+}
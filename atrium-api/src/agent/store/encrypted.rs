@@ -0,0 +1,120 @@
+use super::{Session, SessionStore};
+use atrium_common::store::Store;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use sha2::Sha256;
+
+const NONCE_LEN: usize = 24;
+/// Iterations of PBKDF2-HMAC-SHA256, as recommended by OWASP.
+const PBKDF2_ROUNDS: u32 = 600_000;
+
+/// A [`SessionStore`] that encrypts the whole session before passing it to the inner store.
+///
+/// The session is serialized to JSON and encrypted with XChaCha20-Poly1305, using a key derived
+/// from the passphrase and the salt with PBKDF2-HMAC-SHA256. The inner store only sees the nonce
+/// followed by the ciphertext, so none of the session (tokens, DID, handle, email, ...) is
+/// stored in plaintext.
+///
+/// The key is derived once, when the store is created. Key derivation is deliberately slow, so
+/// create the store outside of latency-sensitive code (e.g. with `spawn_blocking`).
+///
+/// If a stored session cannot be decrypted, for example because the passphrase is wrong,
+/// [`get_session()`](SessionStore::get_session) returns `None`. Errors of the inner store are
+/// ignored, since [`SessionStore`] cannot report them.
+pub struct EncryptedSessionStore<S> {
+    inner: S,
+    key: Key,
+}
+
+impl<S> EncryptedSessionStore<S> {
+    /// Create a new [`EncryptedSessionStore`] wrapping the given store, with 600,000
+    /// iterations of key derivation (as recommended by OWASP).
+    ///
+    /// The salt does not need to be secret, but should be unique to the application or the user.
+    pub fn new(inner: S, passphrase: impl AsRef<[u8]>, salt: impl AsRef<[u8]>) -> Self {
+        Self::with_rounds(inner, passphrase, salt, PBKDF2_ROUNDS)
+    }
+    /// Create a new [`EncryptedSessionStore`] wrapping the given store, with the given number of
+    /// iterations of key derivation.
+    pub fn with_rounds(
+        inner: S,
+        passphrase: impl AsRef<[u8]>,
+        salt: impl AsRef<[u8]>,
+        rounds: u32,
+    ) -> Self {
+        let key =
+            pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(passphrase.as_ref(), salt.as_ref(), rounds);
+        Self { inner, key: key.into() }
+    }
+    fn encrypt(&self, session: &Session) -> Option<Vec<u8>> {
+        let plaintext = serde_json::to_vec(session).ok()?;
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = XChaCha20Poly1305::new(&self.key).encrypt(&nonce, &plaintext[..]).ok()?;
+        Some([&nonce[..], &ciphertext].concat())
+    }
+    fn decrypt(&self, bytes: &[u8]) -> Option<Session> {
+        if bytes.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = XChaCha20Poly1305::new(&self.key)
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .ok()?;
+        serde_json::from_slice(&plaintext).ok()
+    }
+}
+
+impl<S> SessionStore for EncryptedSessionStore<S>
+where
+    S: Store<(), Vec<u8>> + Send + Sync,
+{
+    async fn get_session(&self) -> Option<Session> {
+        self.decrypt(&self.inner.get(&()).await.ok()??)
+    }
+    async fn set_session(&self, session: Session) {
+        if let Some(bytes) = self.encrypt(&session) {
+            let _ = self.inner.set((), bytes).await;
+        }
+    }
+    async fn clear_session(&self) {
+        let _ = self.inner.del(&()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::store::tests::session;
+    use atrium_common::store::memory::MemoryStore;
+
+    const ROUNDS: u32 = 1_000;
+
+    #[tokio::test]
+    async fn encrypt_session() {
+        let inner = MemoryStore::<(), Vec<u8>>::default();
+        let store = EncryptedSessionStore::with_rounds(inner.clone(), "passphrase", "salt", ROUNDS);
+        store.set_session(session()).await;
+        assert_eq!(store.get_session().await, Some(session()));
+        {
+            let stored = inner.get(&()).await.expect("get").expect("session should be stored");
+            let stored = String::from_utf8_lossy(&stored);
+            for plaintext in ["access", "refresh", "did:web:example.com", "example.com"] {
+                assert!(!stored.contains(plaintext), "{plaintext} is stored in plaintext");
+            }
+        }
+        // a new store with the same passphrase and salt can decrypt the session, but not with others
+        assert_eq!(
+            EncryptedSessionStore::with_rounds(inner.clone(), "passphrase", "salt", ROUNDS)
+                .get_session()
+                .await,
+            Some(session())
+        );
+        for (passphrase, salt) in [("wrong", "salt"), ("passphrase", "pepper")] {
+            let store = EncryptedSessionStore::with_rounds(inner.clone(), passphrase, salt, ROUNDS);
+            assert_eq!(store.get_session().await, None);
+        }
+        store.clear_session().await;
+        assert_eq!(inner.get(&()).await.expect("get"), None);
+        assert_eq!(store.get_session().await, None);
+    }
+}
@@ -0,0 +1,159 @@
+use super::{MemorySessionStore, Session, SessionStore};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+
+type WriteErrorCallback = Arc<dyn Fn(&std::io::Error) + Send + Sync>;
+
+/// A [`SessionStore`] that persists the session to a JSON file.
+///
+/// The session is loaded from the file when the store is created, and the file is rewritten
+/// whenever the session changes. The file is replaced atomically, by writing a temporary file
+/// next to it and renaming it. On unix, the file has `0600` permissions, because the session
+/// contains the refresh token.
+///
+/// [`SessionStore`] cannot report failures, so the session is kept in memory even if it could
+/// not be written. Use [`on_write_error()`](Self::on_write_error) to be notified of them.
+#[derive(Clone)]
+pub struct FileSessionStore {
+    path: PathBuf,
+    inner: MemorySessionStore,
+    on_write_error: Option<WriteErrorCallback>,
+}
+
+impl FileSessionStore {
+    /// Create a new [`FileSessionStore`] with the given path.
+    ///
+    /// If the file exists and contains a valid session, it is used as the initial session.
+    pub async fn new(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let inner = MemorySessionStore::default();
+        if let Some(session) = tokio::fs::read(&path)
+            .await
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Session>(&bytes).ok())
+        {
+            inner.set_session(session).await;
+        }
+        Self { path, inner, on_write_error: None }
+    }
+    /// Set a callback to be called when the session cannot be written to or removed from
+    /// the file.
+    pub fn on_write_error(
+        mut self,
+        callback: impl Fn(&std::io::Error) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_write_error = Some(Arc::new(callback));
+        self
+    }
+    async fn write(&self, session: &Session) -> std::io::Result<()> {
+        let mut file_name = self.path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".tmp");
+        let tmp = self.path.with_file_name(file_name);
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&tmp).await?;
+        // The mode is only applied when the file is created, e.g. not to a stale temporary file.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600)).await?;
+        }
+        file.write_all(&serde_json::to_vec_pretty(session)?).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&tmp, &self.path).await
+    }
+    fn report(&self, result: std::io::Result<()>) {
+        if let (Err(err), Some(callback)) = (result, &self.on_write_error) {
+            callback(&err);
+        }
+    }
+}
+
+impl SessionStore for FileSessionStore {
+    async fn get_session(&self) -> Option<Session> {
+        self.inner.get_session().await
+    }
+    async fn set_session(&self, session: Session) {
+        self.report(self.write(&session).await);
+        self.inner.set_session(session).await;
+    }
+    async fn clear_session(&self) {
+        match tokio::fs::remove_file(&self.path).await {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            result => self.report(result),
+        }
+        self.inner.clear_session().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::store::tests::session;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn persist() {
+        let path = std::env::temp_dir().join(format!("atrium-session-{}.json", std::process::id()));
+        {
+            let store = FileSessionStore::new(&path).await;
+            assert_eq!(store.get_session().await, None);
+            store.set_session(session()).await;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).expect("file exists").permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        {
+            let store = FileSessionStore::new(&path).await;
+            assert_eq!(store.get_session().await, Some(session()));
+            store.clear_session().await;
+        }
+        assert!(!path.exists());
+        assert_eq!(FileSessionStore::new(&path).await.get_session().await, None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn restrict_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path =
+            std::env::temp_dir().join(format!("atrium-session-mode-{}.json", std::process::id()));
+        std::fs::write(&path, b"{}").expect("failed to write file");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644))
+            .expect("failed to set permissions");
+        let store = FileSessionStore::new(&path).await;
+        assert_eq!(store.get_session().await, None);
+        store.set_session(session()).await;
+        let mode = std::fs::metadata(&path).expect("file exists").permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        store.clear_session().await;
+    }
+
+    #[tokio::test]
+    async fn write_error() {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let path = std::env::temp_dir()
+            .join(format!("atrium-session-missing-{}", std::process::id()))
+            .join("session.json");
+        let store = {
+            let errors = Arc::clone(&errors);
+            FileSessionStore::new(&path)
+                .await
+                .on_write_error(move |err| errors.lock().unwrap().push(err.kind()))
+        };
+        store.set_session(session()).await;
+        // The session is kept in memory, and the error is reported.
+        assert_eq!(store.get_session().await, Some(session()));
+        assert_eq!(*errors.lock().unwrap(), [std::io::ErrorKind::NotFound]);
+        // Removing a file that does not exist is not an error.
+        store.clear_session().await;
+        assert_eq!(errors.lock().unwrap().len(), 1);
+    }
+}
@@ -331,3 +331,123 @@ pub enum PreferencesItem {
     #[serde(rename = "app.bsky.actor.defs#labelersPref")]
     LabelersPref(Box<LabelersPref>),
 }
+impl From<AdultContentPref> for PreferencesItem {
+    fn from(value: AdultContentPref) -> Self {
+        PreferencesItem::AdultContentPref(Box::new(value))
+    }
+}
+impl From<AdultContentPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: AdultContentPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ContentLabelPref> for PreferencesItem {
+    fn from(value: ContentLabelPref) -> Self {
+        PreferencesItem::ContentLabelPref(Box::new(value))
+    }
+}
+impl From<ContentLabelPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: ContentLabelPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<SavedFeedsPref> for PreferencesItem {
+    fn from(value: SavedFeedsPref) -> Self {
+        PreferencesItem::SavedFeedsPref(Box::new(value))
+    }
+}
+impl From<SavedFeedsPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: SavedFeedsPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<SavedFeedsPrefV2> for PreferencesItem {
+    fn from(value: SavedFeedsPrefV2) -> Self {
+        PreferencesItem::SavedFeedsPrefV2(Box::new(value))
+    }
+}
+impl From<SavedFeedsPrefV2> for crate::types::Union<PreferencesItem> {
+    fn from(value: SavedFeedsPrefV2) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<PersonalDetailsPref> for PreferencesItem {
+    fn from(value: PersonalDetailsPref) -> Self {
+        PreferencesItem::PersonalDetailsPref(Box::new(value))
+    }
+}
+impl From<PersonalDetailsPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: PersonalDetailsPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<FeedViewPref> for PreferencesItem {
+    fn from(value: FeedViewPref) -> Self {
+        PreferencesItem::FeedViewPref(Box::new(value))
+    }
+}
+impl From<FeedViewPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: FeedViewPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ThreadViewPref> for PreferencesItem {
+    fn from(value: ThreadViewPref) -> Self {
+        PreferencesItem::ThreadViewPref(Box::new(value))
+    }
+}
+impl From<ThreadViewPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: ThreadViewPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<InterestsPref> for PreferencesItem {
+    fn from(value: InterestsPref) -> Self {
+        PreferencesItem::InterestsPref(Box::new(value))
+    }
+}
+impl From<InterestsPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: InterestsPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<MutedWordsPref> for PreferencesItem {
+    fn from(value: MutedWordsPref) -> Self {
+        PreferencesItem::MutedWordsPref(Box::new(value))
+    }
+}
+impl From<MutedWordsPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: MutedWordsPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<HiddenPostsPref> for PreferencesItem {
+    fn from(value: HiddenPostsPref) -> Self {
+        PreferencesItem::HiddenPostsPref(Box::new(value))
+    }
+}
+impl From<HiddenPostsPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: HiddenPostsPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<BskyAppStatePref> for PreferencesItem {
+    fn from(value: BskyAppStatePref) -> Self {
+        PreferencesItem::BskyAppStatePref(Box::new(value))
+    }
+}
+impl From<BskyAppStatePref> for crate::types::Union<PreferencesItem> {
+    fn from(value: BskyAppStatePref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<LabelersPref> for PreferencesItem {
+    fn from(value: LabelersPref) -> Self {
+        PreferencesItem::LabelersPref(Box::new(value))
+    }
+}
+impl From<LabelersPref> for crate::types::Union<PreferencesItem> {
+    fn from(value: LabelersPref) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
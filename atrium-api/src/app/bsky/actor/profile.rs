@@ -27,9 +27,32 @@ pub struct RecordData {
     pub pinned_post: core::option::Option<crate::com::atproto::repo::strong_ref::Main>,
 }
 pub type Record = crate::types::Object<RecordData>;
+impl RecordData {
+    ///Constraints on the `avatar` blob.
+    pub const AVATAR_CONSTRAINTS: crate::types::BlobConstraints = crate::types::BlobConstraints {
+        accept: &["image/png", "image/jpeg"],
+        max_size: Some(1000000),
+    };
+    ///Constraints on the `banner` blob.
+    pub const BANNER_CONSTRAINTS: crate::types::BlobConstraints = crate::types::BlobConstraints {
+        accept: &["image/png", "image/jpeg"],
+        max_size: Some(1000000),
+    };
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum RecordLabelsRefs {
     #[serde(rename = "com.atproto.label.defs#selfLabels")]
     ComAtprotoLabelDefsSelfLabels(Box<crate::com::atproto::label::defs::SelfLabels>),
 }
+impl From<crate::com::atproto::label::defs::SelfLabels> for RecordLabelsRefs {
+    fn from(value: crate::com::atproto::label::defs::SelfLabels) -> Self {
+        RecordLabelsRefs::ComAtprotoLabelDefsSelfLabels(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::label::defs::SelfLabels>
+for crate::types::Union<RecordLabelsRefs> {
+    fn from(value: crate::com::atproto::label::defs::SelfLabels) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -17,6 +17,13 @@ pub struct ExternalData {
     pub uri: String,
 }
 pub type External = crate::types::Object<ExternalData>;
+impl ExternalData {
+    ///Constraints on the `thumb` blob.
+    pub const THUMB_CONSTRAINTS: crate::types::BlobConstraints = crate::types::BlobConstraints {
+        accept: &["image/*"],
+        max_size: Some(1000000),
+    };
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ViewData {
@@ -17,6 +17,13 @@ pub struct ImageData {
     pub image: crate::types::BlobRef,
 }
 pub type Image = crate::types::Object<ImageData>;
+impl ImageData {
+    ///Constraints on the `image` blob.
+    pub const IMAGE_CONSTRAINTS: crate::types::BlobConstraints = crate::types::BlobConstraints {
+        accept: &["image/*"],
+        max_size: Some(1000000),
+    };
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ViewData {
@@ -74,6 +74,61 @@ pub enum ViewRecordEmbedsItem {
         Box<crate::app::bsky::embed::record_with_media::View>,
     ),
 }
+impl From<crate::app::bsky::embed::images::View> for ViewRecordEmbedsItem {
+    fn from(value: crate::app::bsky::embed::images::View) -> Self {
+        ViewRecordEmbedsItem::AppBskyEmbedImagesView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::images::View>
+for crate::types::Union<ViewRecordEmbedsItem> {
+    fn from(value: crate::app::bsky::embed::images::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::video::View> for ViewRecordEmbedsItem {
+    fn from(value: crate::app::bsky::embed::video::View) -> Self {
+        ViewRecordEmbedsItem::AppBskyEmbedVideoView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::video::View>
+for crate::types::Union<ViewRecordEmbedsItem> {
+    fn from(value: crate::app::bsky::embed::video::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::external::View> for ViewRecordEmbedsItem {
+    fn from(value: crate::app::bsky::embed::external::View) -> Self {
+        ViewRecordEmbedsItem::AppBskyEmbedExternalView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::external::View>
+for crate::types::Union<ViewRecordEmbedsItem> {
+    fn from(value: crate::app::bsky::embed::external::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::record::View> for ViewRecordEmbedsItem {
+    fn from(value: crate::app::bsky::embed::record::View) -> Self {
+        ViewRecordEmbedsItem::AppBskyEmbedRecordView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::record::View>
+for crate::types::Union<ViewRecordEmbedsItem> {
+    fn from(value: crate::app::bsky::embed::record::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::record_with_media::View> for ViewRecordEmbedsItem {
+    fn from(value: crate::app::bsky::embed::record_with_media::View) -> Self {
+        ViewRecordEmbedsItem::AppBskyEmbedRecordWithMediaView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::record_with_media::View>
+for crate::types::Union<ViewRecordEmbedsItem> {
+    fn from(value: crate::app::bsky::embed::record_with_media::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ViewRecordRefs {
@@ -96,3 +151,87 @@ pub enum ViewRecordRefs {
         Box<crate::app::bsky::graph::defs::StarterPackViewBasic>,
     ),
 }
+impl From<ViewRecord> for ViewRecordRefs {
+    fn from(value: ViewRecord) -> Self {
+        ViewRecordRefs::ViewRecord(Box::new(value))
+    }
+}
+impl From<ViewRecord> for crate::types::Union<ViewRecordRefs> {
+    fn from(value: ViewRecord) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ViewNotFound> for ViewRecordRefs {
+    fn from(value: ViewNotFound) -> Self {
+        ViewRecordRefs::ViewNotFound(Box::new(value))
+    }
+}
+impl From<ViewNotFound> for crate::types::Union<ViewRecordRefs> {
+    fn from(value: ViewNotFound) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ViewBlocked> for ViewRecordRefs {
+    fn from(value: ViewBlocked) -> Self {
+        ViewRecordRefs::ViewBlocked(Box::new(value))
+    }
+}
+impl From<ViewBlocked> for crate::types::Union<ViewRecordRefs> {
+    fn from(value: ViewBlocked) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ViewDetached> for ViewRecordRefs {
+    fn from(value: ViewDetached) -> Self {
+        ViewRecordRefs::ViewDetached(Box::new(value))
+    }
+}
+impl From<ViewDetached> for crate::types::Union<ViewRecordRefs> {
+    fn from(value: ViewDetached) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::feed::defs::GeneratorView> for ViewRecordRefs {
+    fn from(value: crate::app::bsky::feed::defs::GeneratorView) -> Self {
+        ViewRecordRefs::AppBskyFeedDefsGeneratorView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::feed::defs::GeneratorView>
+for crate::types::Union<ViewRecordRefs> {
+    fn from(value: crate::app::bsky::feed::defs::GeneratorView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::graph::defs::ListView> for ViewRecordRefs {
+    fn from(value: crate::app::bsky::graph::defs::ListView) -> Self {
+        ViewRecordRefs::AppBskyGraphDefsListView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::graph::defs::ListView>
+for crate::types::Union<ViewRecordRefs> {
+    fn from(value: crate::app::bsky::graph::defs::ListView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::labeler::defs::LabelerView> for ViewRecordRefs {
+    fn from(value: crate::app::bsky::labeler::defs::LabelerView) -> Self {
+        ViewRecordRefs::AppBskyLabelerDefsLabelerView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::labeler::defs::LabelerView>
+for crate::types::Union<ViewRecordRefs> {
+    fn from(value: crate::app::bsky::labeler::defs::LabelerView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::graph::defs::StarterPackViewBasic> for ViewRecordRefs {
+    fn from(value: crate::app::bsky::graph::defs::StarterPackViewBasic) -> Self {
+        ViewRecordRefs::AppBskyGraphDefsStarterPackViewBasic(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::graph::defs::StarterPackViewBasic>
+for crate::types::Union<ViewRecordRefs> {
+    fn from(value: crate::app::bsky::graph::defs::StarterPackViewBasic) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
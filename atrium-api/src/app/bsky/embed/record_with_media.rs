@@ -25,6 +25,37 @@ pub enum MainMediaRefs {
     #[serde(rename = "app.bsky.embed.external")]
     AppBskyEmbedExternalMain(Box<crate::app::bsky::embed::external::Main>),
 }
+impl From<crate::app::bsky::embed::images::Main> for MainMediaRefs {
+    fn from(value: crate::app::bsky::embed::images::Main) -> Self {
+        MainMediaRefs::AppBskyEmbedImagesMain(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::images::Main> for crate::types::Union<MainMediaRefs> {
+    fn from(value: crate::app::bsky::embed::images::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::video::Main> for MainMediaRefs {
+    fn from(value: crate::app::bsky::embed::video::Main) -> Self {
+        MainMediaRefs::AppBskyEmbedVideoMain(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::video::Main> for crate::types::Union<MainMediaRefs> {
+    fn from(value: crate::app::bsky::embed::video::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::external::Main> for MainMediaRefs {
+    fn from(value: crate::app::bsky::embed::external::Main) -> Self {
+        MainMediaRefs::AppBskyEmbedExternalMain(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::external::Main>
+for crate::types::Union<MainMediaRefs> {
+    fn from(value: crate::app::bsky::embed::external::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ViewMediaRefs {
@@ -35,3 +66,34 @@ pub enum ViewMediaRefs {
     #[serde(rename = "app.bsky.embed.external#view")]
     AppBskyEmbedExternalView(Box<crate::app::bsky::embed::external::View>),
 }
+impl From<crate::app::bsky::embed::images::View> for ViewMediaRefs {
+    fn from(value: crate::app::bsky::embed::images::View) -> Self {
+        ViewMediaRefs::AppBskyEmbedImagesView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::images::View> for crate::types::Union<ViewMediaRefs> {
+    fn from(value: crate::app::bsky::embed::images::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::video::View> for ViewMediaRefs {
+    fn from(value: crate::app::bsky::embed::video::View) -> Self {
+        ViewMediaRefs::AppBskyEmbedVideoView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::video::View> for crate::types::Union<ViewMediaRefs> {
+    fn from(value: crate::app::bsky::embed::video::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::external::View> for ViewMediaRefs {
+    fn from(value: crate::app::bsky::embed::external::View) -> Self {
+        ViewMediaRefs::AppBskyEmbedExternalView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::external::View>
+for crate::types::Union<ViewMediaRefs> {
+    fn from(value: crate::app::bsky::embed::external::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -14,6 +14,13 @@ pub struct MainData {
     pub video: crate::types::BlobRef,
 }
 pub type Main = crate::types::Object<MainData>;
+impl MainData {
+    ///Constraints on the `video` blob.
+    pub const VIDEO_CONSTRAINTS: crate::types::BlobConstraints = crate::types::BlobConstraints {
+        accept: &["video/mp4"],
+        max_size: Some(50000000),
+    };
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct CaptionData {
@@ -21,6 +28,13 @@ pub struct CaptionData {
     pub lang: crate::types::string::Language,
 }
 pub type Caption = crate::types::Object<CaptionData>;
+impl CaptionData {
+    ///Constraints on the `file` blob.
+    pub const FILE_CONSTRAINTS: crate::types::BlobConstraints = crate::types::BlobConstraints {
+        accept: &["text/vtt"],
+        max_size: Some(20000),
+    };
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ViewData {
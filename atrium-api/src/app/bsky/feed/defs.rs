@@ -227,6 +227,26 @@ pub enum FeedViewPostReasonRefs {
     #[serde(rename = "app.bsky.feed.defs#reasonPin")]
     ReasonPin(Box<ReasonPin>),
 }
+impl From<ReasonRepost> for FeedViewPostReasonRefs {
+    fn from(value: ReasonRepost) -> Self {
+        FeedViewPostReasonRefs::ReasonRepost(Box::new(value))
+    }
+}
+impl From<ReasonRepost> for crate::types::Union<FeedViewPostReasonRefs> {
+    fn from(value: ReasonRepost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ReasonPin> for FeedViewPostReasonRefs {
+    fn from(value: ReasonPin) -> Self {
+        FeedViewPostReasonRefs::ReasonPin(Box::new(value))
+    }
+}
+impl From<ReasonPin> for crate::types::Union<FeedViewPostReasonRefs> {
+    fn from(value: ReasonPin) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum PostViewEmbedRefs {
@@ -243,6 +263,61 @@ pub enum PostViewEmbedRefs {
         Box<crate::app::bsky::embed::record_with_media::View>,
     ),
 }
+impl From<crate::app::bsky::embed::images::View> for PostViewEmbedRefs {
+    fn from(value: crate::app::bsky::embed::images::View) -> Self {
+        PostViewEmbedRefs::AppBskyEmbedImagesView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::images::View>
+for crate::types::Union<PostViewEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::images::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::video::View> for PostViewEmbedRefs {
+    fn from(value: crate::app::bsky::embed::video::View) -> Self {
+        PostViewEmbedRefs::AppBskyEmbedVideoView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::video::View>
+for crate::types::Union<PostViewEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::video::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::external::View> for PostViewEmbedRefs {
+    fn from(value: crate::app::bsky::embed::external::View) -> Self {
+        PostViewEmbedRefs::AppBskyEmbedExternalView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::external::View>
+for crate::types::Union<PostViewEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::external::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::record::View> for PostViewEmbedRefs {
+    fn from(value: crate::app::bsky::embed::record::View) -> Self {
+        PostViewEmbedRefs::AppBskyEmbedRecordView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::record::View>
+for crate::types::Union<PostViewEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::record::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::record_with_media::View> for PostViewEmbedRefs {
+    fn from(value: crate::app::bsky::embed::record_with_media::View) -> Self {
+        PostViewEmbedRefs::AppBskyEmbedRecordWithMediaView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::record_with_media::View>
+for crate::types::Union<PostViewEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::record_with_media::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ReplyRefParentRefs {
@@ -253,6 +328,36 @@ pub enum ReplyRefParentRefs {
     #[serde(rename = "app.bsky.feed.defs#blockedPost")]
     BlockedPost(Box<BlockedPost>),
 }
+impl From<PostView> for ReplyRefParentRefs {
+    fn from(value: PostView) -> Self {
+        ReplyRefParentRefs::PostView(Box::new(value))
+    }
+}
+impl From<PostView> for crate::types::Union<ReplyRefParentRefs> {
+    fn from(value: PostView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<NotFoundPost> for ReplyRefParentRefs {
+    fn from(value: NotFoundPost) -> Self {
+        ReplyRefParentRefs::NotFoundPost(Box::new(value))
+    }
+}
+impl From<NotFoundPost> for crate::types::Union<ReplyRefParentRefs> {
+    fn from(value: NotFoundPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<BlockedPost> for ReplyRefParentRefs {
+    fn from(value: BlockedPost) -> Self {
+        ReplyRefParentRefs::BlockedPost(Box::new(value))
+    }
+}
+impl From<BlockedPost> for crate::types::Union<ReplyRefParentRefs> {
+    fn from(value: BlockedPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ReplyRefRootRefs {
@@ -263,6 +368,36 @@ pub enum ReplyRefRootRefs {
     #[serde(rename = "app.bsky.feed.defs#blockedPost")]
     BlockedPost(Box<BlockedPost>),
 }
+impl From<PostView> for ReplyRefRootRefs {
+    fn from(value: PostView) -> Self {
+        ReplyRefRootRefs::PostView(Box::new(value))
+    }
+}
+impl From<PostView> for crate::types::Union<ReplyRefRootRefs> {
+    fn from(value: PostView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<NotFoundPost> for ReplyRefRootRefs {
+    fn from(value: NotFoundPost) -> Self {
+        ReplyRefRootRefs::NotFoundPost(Box::new(value))
+    }
+}
+impl From<NotFoundPost> for crate::types::Union<ReplyRefRootRefs> {
+    fn from(value: NotFoundPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<BlockedPost> for ReplyRefRootRefs {
+    fn from(value: BlockedPost) -> Self {
+        ReplyRefRootRefs::BlockedPost(Box::new(value))
+    }
+}
+impl From<BlockedPost> for crate::types::Union<ReplyRefRootRefs> {
+    fn from(value: BlockedPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum SkeletonFeedPostReasonRefs {
@@ -271,6 +406,26 @@ pub enum SkeletonFeedPostReasonRefs {
     #[serde(rename = "app.bsky.feed.defs#skeletonReasonPin")]
     SkeletonReasonPin(Box<SkeletonReasonPin>),
 }
+impl From<SkeletonReasonRepost> for SkeletonFeedPostReasonRefs {
+    fn from(value: SkeletonReasonRepost) -> Self {
+        SkeletonFeedPostReasonRefs::SkeletonReasonRepost(Box::new(value))
+    }
+}
+impl From<SkeletonReasonRepost> for crate::types::Union<SkeletonFeedPostReasonRefs> {
+    fn from(value: SkeletonReasonRepost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<SkeletonReasonPin> for SkeletonFeedPostReasonRefs {
+    fn from(value: SkeletonReasonPin) -> Self {
+        SkeletonFeedPostReasonRefs::SkeletonReasonPin(Box::new(value))
+    }
+}
+impl From<SkeletonReasonPin> for crate::types::Union<SkeletonFeedPostReasonRefs> {
+    fn from(value: SkeletonReasonPin) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ThreadViewPostParentRefs {
@@ -281,6 +436,36 @@ pub enum ThreadViewPostParentRefs {
     #[serde(rename = "app.bsky.feed.defs#blockedPost")]
     BlockedPost(Box<BlockedPost>),
 }
+impl From<ThreadViewPost> for ThreadViewPostParentRefs {
+    fn from(value: ThreadViewPost) -> Self {
+        ThreadViewPostParentRefs::ThreadViewPost(Box::new(value))
+    }
+}
+impl From<ThreadViewPost> for crate::types::Union<ThreadViewPostParentRefs> {
+    fn from(value: ThreadViewPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<NotFoundPost> for ThreadViewPostParentRefs {
+    fn from(value: NotFoundPost) -> Self {
+        ThreadViewPostParentRefs::NotFoundPost(Box::new(value))
+    }
+}
+impl From<NotFoundPost> for crate::types::Union<ThreadViewPostParentRefs> {
+    fn from(value: NotFoundPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<BlockedPost> for ThreadViewPostParentRefs {
+    fn from(value: BlockedPost) -> Self {
+        ThreadViewPostParentRefs::BlockedPost(Box::new(value))
+    }
+}
+impl From<BlockedPost> for crate::types::Union<ThreadViewPostParentRefs> {
+    fn from(value: BlockedPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ThreadViewPostRepliesItem {
@@ -291,3 +476,33 @@ pub enum ThreadViewPostRepliesItem {
     #[serde(rename = "app.bsky.feed.defs#blockedPost")]
     BlockedPost(Box<BlockedPost>),
 }
+impl From<ThreadViewPost> for ThreadViewPostRepliesItem {
+    fn from(value: ThreadViewPost) -> Self {
+        ThreadViewPostRepliesItem::ThreadViewPost(Box::new(value))
+    }
+}
+impl From<ThreadViewPost> for crate::types::Union<ThreadViewPostRepliesItem> {
+    fn from(value: ThreadViewPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<NotFoundPost> for ThreadViewPostRepliesItem {
+    fn from(value: NotFoundPost) -> Self {
+        ThreadViewPostRepliesItem::NotFoundPost(Box::new(value))
+    }
+}
+impl From<NotFoundPost> for crate::types::Union<ThreadViewPostRepliesItem> {
+    fn from(value: NotFoundPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<BlockedPost> for ThreadViewPostRepliesItem {
+    fn from(value: BlockedPost) -> Self {
+        ThreadViewPostRepliesItem::BlockedPost(Box::new(value))
+    }
+}
+impl From<BlockedPost> for crate::types::Union<ThreadViewPostRepliesItem> {
+    fn from(value: BlockedPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
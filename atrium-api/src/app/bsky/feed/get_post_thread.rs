@@ -50,3 +50,36 @@ pub enum OutputThreadRefs {
     #[serde(rename = "app.bsky.feed.defs#blockedPost")]
     AppBskyFeedDefsBlockedPost(Box<crate::app::bsky::feed::defs::BlockedPost>),
 }
+impl From<crate::app::bsky::feed::defs::ThreadViewPost> for OutputThreadRefs {
+    fn from(value: crate::app::bsky::feed::defs::ThreadViewPost) -> Self {
+        OutputThreadRefs::AppBskyFeedDefsThreadViewPost(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::feed::defs::ThreadViewPost>
+for crate::types::Union<OutputThreadRefs> {
+    fn from(value: crate::app::bsky::feed::defs::ThreadViewPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::feed::defs::NotFoundPost> for OutputThreadRefs {
+    fn from(value: crate::app::bsky::feed::defs::NotFoundPost) -> Self {
+        OutputThreadRefs::AppBskyFeedDefsNotFoundPost(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::feed::defs::NotFoundPost>
+for crate::types::Union<OutputThreadRefs> {
+    fn from(value: crate::app::bsky::feed::defs::NotFoundPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::feed::defs::BlockedPost> for OutputThreadRefs {
+    fn from(value: crate::app::bsky::feed::defs::BlockedPost) -> Self {
+        OutputThreadRefs::AppBskyFeedDefsBlockedPost(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::feed::defs::BlockedPost>
+for crate::types::Union<OutputThreadRefs> {
+    fn from(value: crate::app::bsky::feed::defs::BlockedPost) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -69,9 +69,75 @@ pub enum RecordEmbedRefs {
         Box<crate::app::bsky::embed::record_with_media::Main>,
     ),
 }
+impl From<crate::app::bsky::embed::images::Main> for RecordEmbedRefs {
+    fn from(value: crate::app::bsky::embed::images::Main) -> Self {
+        RecordEmbedRefs::AppBskyEmbedImagesMain(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::images::Main>
+for crate::types::Union<RecordEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::images::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::video::Main> for RecordEmbedRefs {
+    fn from(value: crate::app::bsky::embed::video::Main) -> Self {
+        RecordEmbedRefs::AppBskyEmbedVideoMain(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::video::Main>
+for crate::types::Union<RecordEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::video::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::external::Main> for RecordEmbedRefs {
+    fn from(value: crate::app::bsky::embed::external::Main) -> Self {
+        RecordEmbedRefs::AppBskyEmbedExternalMain(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::external::Main>
+for crate::types::Union<RecordEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::external::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::record::Main> for RecordEmbedRefs {
+    fn from(value: crate::app::bsky::embed::record::Main) -> Self {
+        RecordEmbedRefs::AppBskyEmbedRecordMain(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::record::Main>
+for crate::types::Union<RecordEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::record::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::embed::record_with_media::Main> for RecordEmbedRefs {
+    fn from(value: crate::app::bsky::embed::record_with_media::Main) -> Self {
+        RecordEmbedRefs::AppBskyEmbedRecordWithMediaMain(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::record_with_media::Main>
+for crate::types::Union<RecordEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::record_with_media::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum RecordLabelsRefs {
     #[serde(rename = "com.atproto.label.defs#selfLabels")]
     ComAtprotoLabelDefsSelfLabels(Box<crate::com::atproto::label::defs::SelfLabels>),
 }
+impl From<crate::com::atproto::label::defs::SelfLabels> for RecordLabelsRefs {
+    fn from(value: crate::com::atproto::label::defs::SelfLabels) -> Self {
+        RecordLabelsRefs::ComAtprotoLabelDefsSelfLabels(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::label::defs::SelfLabels>
+for crate::types::Union<RecordLabelsRefs> {
+    fn from(value: crate::com::atproto::label::defs::SelfLabels) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
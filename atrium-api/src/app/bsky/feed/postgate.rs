@@ -26,3 +26,13 @@ pub enum RecordEmbeddingRulesItem {
     #[serde(rename = "app.bsky.feed.postgate#disableRule")]
     DisableRule(Box<DisableRule>),
 }
+impl From<DisableRule> for RecordEmbeddingRulesItem {
+    fn from(value: DisableRule) -> Self {
+        RecordEmbeddingRulesItem::DisableRule(Box::new(value))
+    }
+}
+impl From<DisableRule> for crate::types::Union<RecordEmbeddingRulesItem> {
+    fn from(value: DisableRule) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
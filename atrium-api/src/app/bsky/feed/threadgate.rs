@@ -40,3 +40,33 @@ pub enum RecordAllowItem {
     #[serde(rename = "app.bsky.feed.threadgate#listRule")]
     ListRule(Box<ListRule>),
 }
+impl From<MentionRule> for RecordAllowItem {
+    fn from(value: MentionRule) -> Self {
+        RecordAllowItem::MentionRule(Box::new(value))
+    }
+}
+impl From<MentionRule> for crate::types::Union<RecordAllowItem> {
+    fn from(value: MentionRule) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<FollowingRule> for RecordAllowItem {
+    fn from(value: FollowingRule) -> Self {
+        RecordAllowItem::FollowingRule(Box::new(value))
+    }
+}
+impl From<FollowingRule> for crate::types::Union<RecordAllowItem> {
+    fn from(value: FollowingRule) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ListRule> for RecordAllowItem {
+    fn from(value: ListRule) -> Self {
+        RecordAllowItem::ListRule(Box::new(value))
+    }
+}
+impl From<ListRule> for crate::types::Union<RecordAllowItem> {
+    fn from(value: ListRule) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -46,3 +46,25 @@ pub enum OutputRelationshipsItem {
     #[serde(rename = "app.bsky.graph.defs#notFoundActor")]
     AppBskyGraphDefsNotFoundActor(Box<crate::app::bsky::graph::defs::NotFoundActor>),
 }
+impl From<crate::app::bsky::graph::defs::Relationship> for OutputRelationshipsItem {
+    fn from(value: crate::app::bsky::graph::defs::Relationship) -> Self {
+        OutputRelationshipsItem::AppBskyGraphDefsRelationship(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::graph::defs::Relationship>
+for crate::types::Union<OutputRelationshipsItem> {
+    fn from(value: crate::app::bsky::graph::defs::Relationship) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::graph::defs::NotFoundActor> for OutputRelationshipsItem {
+    fn from(value: crate::app::bsky::graph::defs::NotFoundActor) -> Self {
+        OutputRelationshipsItem::AppBskyGraphDefsNotFoundActor(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::graph::defs::NotFoundActor>
+for crate::types::Union<OutputRelationshipsItem> {
+    fn from(value: crate::app::bsky::graph::defs::NotFoundActor) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
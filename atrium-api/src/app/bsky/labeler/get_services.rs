@@ -33,3 +33,25 @@ pub enum OutputViewsItem {
         Box<crate::app::bsky::labeler::defs::LabelerViewDetailed>,
     ),
 }
+impl From<crate::app::bsky::labeler::defs::LabelerView> for OutputViewsItem {
+    fn from(value: crate::app::bsky::labeler::defs::LabelerView) -> Self {
+        OutputViewsItem::AppBskyLabelerDefsLabelerView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::labeler::defs::LabelerView>
+for crate::types::Union<OutputViewsItem> {
+    fn from(value: crate::app::bsky::labeler::defs::LabelerView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::app::bsky::labeler::defs::LabelerViewDetailed> for OutputViewsItem {
+    fn from(value: crate::app::bsky::labeler::defs::LabelerViewDetailed) -> Self {
+        OutputViewsItem::AppBskyLabelerDefsLabelerViewDetailed(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::labeler::defs::LabelerViewDetailed>
+for crate::types::Union<OutputViewsItem> {
+    fn from(value: crate::app::bsky::labeler::defs::LabelerViewDetailed) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -15,3 +15,14 @@ pub enum RecordLabelsRefs {
     #[serde(rename = "com.atproto.label.defs#selfLabels")]
     ComAtprotoLabelDefsSelfLabels(Box<crate::com::atproto::label::defs::SelfLabels>),
 }
+impl From<crate::com::atproto::label::defs::SelfLabels> for RecordLabelsRefs {
+    fn from(value: crate::com::atproto::label::defs::SelfLabels) -> Self {
+        RecordLabelsRefs::ComAtprotoLabelDefsSelfLabels(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::label::defs::SelfLabels>
+for crate::types::Union<RecordLabelsRefs> {
+    fn from(value: crate::com::atproto::label::defs::SelfLabels) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
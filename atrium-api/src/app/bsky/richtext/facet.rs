@@ -47,3 +47,33 @@ pub enum MainFeaturesItem {
     #[serde(rename = "app.bsky.richtext.facet#tag")]
     Tag(Box<Tag>),
 }
+impl From<Mention> for MainFeaturesItem {
+    fn from(value: Mention) -> Self {
+        MainFeaturesItem::Mention(Box::new(value))
+    }
+}
+impl From<Mention> for crate::types::Union<MainFeaturesItem> {
+    fn from(value: Mention) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Link> for MainFeaturesItem {
+    fn from(value: Link) -> Self {
+        MainFeaturesItem::Link(Box::new(value))
+    }
+}
+impl From<Link> for crate::types::Union<MainFeaturesItem> {
+    fn from(value: Link) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Tag> for MainFeaturesItem {
+    fn from(value: Tag) -> Self {
+        MainFeaturesItem::Tag(Box::new(value))
+    }
+}
+impl From<Tag> for crate::types::Union<MainFeaturesItem> {
+    fn from(value: Tag) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -103,6 +103,26 @@ pub enum ConvoViewLastMessageRefs {
     #[serde(rename = "chat.bsky.convo.defs#deletedMessageView")]
     DeletedMessageView(Box<DeletedMessageView>),
 }
+impl From<MessageView> for ConvoViewLastMessageRefs {
+    fn from(value: MessageView) -> Self {
+        ConvoViewLastMessageRefs::MessageView(Box::new(value))
+    }
+}
+impl From<MessageView> for crate::types::Union<ConvoViewLastMessageRefs> {
+    fn from(value: MessageView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<DeletedMessageView> for ConvoViewLastMessageRefs {
+    fn from(value: DeletedMessageView) -> Self {
+        ConvoViewLastMessageRefs::DeletedMessageView(Box::new(value))
+    }
+}
+impl From<DeletedMessageView> for crate::types::Union<ConvoViewLastMessageRefs> {
+    fn from(value: DeletedMessageView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum LogCreateMessageMessageRefs {
@@ -111,6 +131,26 @@ pub enum LogCreateMessageMessageRefs {
     #[serde(rename = "chat.bsky.convo.defs#deletedMessageView")]
     DeletedMessageView(Box<DeletedMessageView>),
 }
+impl From<MessageView> for LogCreateMessageMessageRefs {
+    fn from(value: MessageView) -> Self {
+        LogCreateMessageMessageRefs::MessageView(Box::new(value))
+    }
+}
+impl From<MessageView> for crate::types::Union<LogCreateMessageMessageRefs> {
+    fn from(value: MessageView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<DeletedMessageView> for LogCreateMessageMessageRefs {
+    fn from(value: DeletedMessageView) -> Self {
+        LogCreateMessageMessageRefs::DeletedMessageView(Box::new(value))
+    }
+}
+impl From<DeletedMessageView> for crate::types::Union<LogCreateMessageMessageRefs> {
+    fn from(value: DeletedMessageView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum LogDeleteMessageMessageRefs {
@@ -119,15 +159,57 @@ pub enum LogDeleteMessageMessageRefs {
     #[serde(rename = "chat.bsky.convo.defs#deletedMessageView")]
     DeletedMessageView(Box<DeletedMessageView>),
 }
+impl From<MessageView> for LogDeleteMessageMessageRefs {
+    fn from(value: MessageView) -> Self {
+        LogDeleteMessageMessageRefs::MessageView(Box::new(value))
+    }
+}
+impl From<MessageView> for crate::types::Union<LogDeleteMessageMessageRefs> {
+    fn from(value: MessageView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<DeletedMessageView> for LogDeleteMessageMessageRefs {
+    fn from(value: DeletedMessageView) -> Self {
+        LogDeleteMessageMessageRefs::DeletedMessageView(Box::new(value))
+    }
+}
+impl From<DeletedMessageView> for crate::types::Union<LogDeleteMessageMessageRefs> {
+    fn from(value: DeletedMessageView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum MessageInputEmbedRefs {
     #[serde(rename = "app.bsky.embed.record")]
     AppBskyEmbedRecordMain(Box<crate::app::bsky::embed::record::Main>),
 }
+impl From<crate::app::bsky::embed::record::Main> for MessageInputEmbedRefs {
+    fn from(value: crate::app::bsky::embed::record::Main) -> Self {
+        MessageInputEmbedRefs::AppBskyEmbedRecordMain(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::record::Main>
+for crate::types::Union<MessageInputEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::record::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum MessageViewEmbedRefs {
     #[serde(rename = "app.bsky.embed.record#view")]
     AppBskyEmbedRecordView(Box<crate::app::bsky::embed::record::View>),
 }
+impl From<crate::app::bsky::embed::record::View> for MessageViewEmbedRefs {
+    fn from(value: crate::app::bsky::embed::record::View) -> Self {
+        MessageViewEmbedRefs::AppBskyEmbedRecordView(Box::new(value))
+    }
+}
+impl From<crate::app::bsky::embed::record::View>
+for crate::types::Union<MessageViewEmbedRefs> {
+    fn from(value: crate::app::bsky::embed::record::View) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
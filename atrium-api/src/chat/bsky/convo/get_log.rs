@@ -40,3 +40,47 @@ pub enum OutputLogsItem {
         Box<crate::chat::bsky::convo::defs::LogDeleteMessage>,
     ),
 }
+impl From<crate::chat::bsky::convo::defs::LogBeginConvo> for OutputLogsItem {
+    fn from(value: crate::chat::bsky::convo::defs::LogBeginConvo) -> Self {
+        OutputLogsItem::ChatBskyConvoDefsLogBeginConvo(Box::new(value))
+    }
+}
+impl From<crate::chat::bsky::convo::defs::LogBeginConvo>
+for crate::types::Union<OutputLogsItem> {
+    fn from(value: crate::chat::bsky::convo::defs::LogBeginConvo) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::chat::bsky::convo::defs::LogLeaveConvo> for OutputLogsItem {
+    fn from(value: crate::chat::bsky::convo::defs::LogLeaveConvo) -> Self {
+        OutputLogsItem::ChatBskyConvoDefsLogLeaveConvo(Box::new(value))
+    }
+}
+impl From<crate::chat::bsky::convo::defs::LogLeaveConvo>
+for crate::types::Union<OutputLogsItem> {
+    fn from(value: crate::chat::bsky::convo::defs::LogLeaveConvo) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::chat::bsky::convo::defs::LogCreateMessage> for OutputLogsItem {
+    fn from(value: crate::chat::bsky::convo::defs::LogCreateMessage) -> Self {
+        OutputLogsItem::ChatBskyConvoDefsLogCreateMessage(Box::new(value))
+    }
+}
+impl From<crate::chat::bsky::convo::defs::LogCreateMessage>
+for crate::types::Union<OutputLogsItem> {
+    fn from(value: crate::chat::bsky::convo::defs::LogCreateMessage) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::chat::bsky::convo::defs::LogDeleteMessage> for OutputLogsItem {
+    fn from(value: crate::chat::bsky::convo::defs::LogDeleteMessage) -> Self {
+        OutputLogsItem::ChatBskyConvoDefsLogDeleteMessage(Box::new(value))
+    }
+}
+impl From<crate::chat::bsky::convo::defs::LogDeleteMessage>
+for crate::types::Union<OutputLogsItem> {
+    fn from(value: crate::chat::bsky::convo::defs::LogDeleteMessage) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
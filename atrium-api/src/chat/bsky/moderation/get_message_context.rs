@@ -38,3 +38,25 @@ pub enum OutputMessagesItem {
         Box<crate::chat::bsky::convo::defs::DeletedMessageView>,
     ),
 }
+impl From<crate::chat::bsky::convo::defs::MessageView> for OutputMessagesItem {
+    fn from(value: crate::chat::bsky::convo::defs::MessageView) -> Self {
+        OutputMessagesItem::ChatBskyConvoDefsMessageView(Box::new(value))
+    }
+}
+impl From<crate::chat::bsky::convo::defs::MessageView>
+for crate::types::Union<OutputMessagesItem> {
+    fn from(value: crate::chat::bsky::convo::defs::MessageView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::chat::bsky::convo::defs::DeletedMessageView> for OutputMessagesItem {
+    fn from(value: crate::chat::bsky::convo::defs::DeletedMessageView) -> Self {
+        OutputMessagesItem::ChatBskyConvoDefsDeletedMessageView(Box::new(value))
+    }
+}
+impl From<crate::chat::bsky::convo::defs::DeletedMessageView>
+for crate::types::Union<OutputMessagesItem> {
+    fn from(value: crate::chat::bsky::convo::defs::DeletedMessageView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -40,3 +40,36 @@ pub enum OutputSubjectRefs {
     #[serde(rename = "com.atproto.admin.defs#repoBlobRef")]
     ComAtprotoAdminDefsRepoBlobRef(Box<crate::com::atproto::admin::defs::RepoBlobRef>),
 }
+impl From<crate::com::atproto::admin::defs::RepoRef> for OutputSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        OutputSubjectRefs::ComAtprotoAdminDefsRepoRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoRef>
+for crate::types::Union<OutputSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main> for OutputSubjectRefs {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        OutputSubjectRefs::ComAtprotoRepoStrongRefMain(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main>
+for crate::types::Union<OutputSubjectRefs> {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoBlobRef> for OutputSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoBlobRef) -> Self {
+        OutputSubjectRefs::ComAtprotoAdminDefsRepoBlobRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoBlobRef>
+for crate::types::Union<OutputSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoBlobRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -37,6 +37,39 @@ pub enum InputSubjectRefs {
     #[serde(rename = "com.atproto.admin.defs#repoBlobRef")]
     ComAtprotoAdminDefsRepoBlobRef(Box<crate::com::atproto::admin::defs::RepoBlobRef>),
 }
+impl From<crate::com::atproto::admin::defs::RepoRef> for InputSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        InputSubjectRefs::ComAtprotoAdminDefsRepoRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoRef>
+for crate::types::Union<InputSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main> for InputSubjectRefs {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        InputSubjectRefs::ComAtprotoRepoStrongRefMain(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main>
+for crate::types::Union<InputSubjectRefs> {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoBlobRef> for InputSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoBlobRef) -> Self {
+        InputSubjectRefs::ComAtprotoAdminDefsRepoBlobRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoBlobRef>
+for crate::types::Union<InputSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoBlobRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum OutputSubjectRefs {
@@ -47,3 +80,36 @@ pub enum OutputSubjectRefs {
     #[serde(rename = "com.atproto.admin.defs#repoBlobRef")]
     ComAtprotoAdminDefsRepoBlobRef(Box<crate::com::atproto::admin::defs::RepoBlobRef>),
 }
+impl From<crate::com::atproto::admin::defs::RepoRef> for OutputSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        OutputSubjectRefs::ComAtprotoAdminDefsRepoRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoRef>
+for crate::types::Union<OutputSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main> for OutputSubjectRefs {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        OutputSubjectRefs::ComAtprotoRepoStrongRefMain(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main>
+for crate::types::Union<OutputSubjectRefs> {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoBlobRef> for OutputSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoBlobRef) -> Self {
+        OutputSubjectRefs::ComAtprotoAdminDefsRepoBlobRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoBlobRef>
+for crate::types::Union<OutputSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoBlobRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -50,3 +50,23 @@ pub enum Message {
     #[serde(rename = "com.atproto.label.subscribeLabels#info")]
     Info(Box<Info>),
 }
+impl From<Labels> for Message {
+    fn from(value: Labels) -> Self {
+        Message::Labels(Box::new(value))
+    }
+}
+impl From<Labels> for crate::types::Union<Message> {
+    fn from(value: Labels) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Info> for Message {
+    fn from(value: Info) -> Self {
+        Message::Info(Box::new(value))
+    }
+}
+impl From<Info> for crate::types::Union<Message> {
+    fn from(value: Info) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -40,6 +40,28 @@ pub enum InputSubjectRefs {
     #[serde(rename = "com.atproto.repo.strongRef")]
     ComAtprotoRepoStrongRefMain(Box<crate::com::atproto::repo::strong_ref::Main>),
 }
+impl From<crate::com::atproto::admin::defs::RepoRef> for InputSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        InputSubjectRefs::ComAtprotoAdminDefsRepoRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoRef>
+for crate::types::Union<InputSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main> for InputSubjectRefs {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        InputSubjectRefs::ComAtprotoRepoStrongRefMain(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main>
+for crate::types::Union<InputSubjectRefs> {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum OutputSubjectRefs {
@@ -48,3 +70,25 @@ pub enum OutputSubjectRefs {
     #[serde(rename = "com.atproto.repo.strongRef")]
     ComAtprotoRepoStrongRefMain(Box<crate::com::atproto::repo::strong_ref::Main>),
 }
+impl From<crate::com::atproto::admin::defs::RepoRef> for OutputSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        OutputSubjectRefs::ComAtprotoAdminDefsRepoRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoRef>
+for crate::types::Union<OutputSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main> for OutputSubjectRefs {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        OutputSubjectRefs::ComAtprotoRepoStrongRefMain(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main>
+for crate::types::Union<OutputSubjectRefs> {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
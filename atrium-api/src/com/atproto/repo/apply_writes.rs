@@ -102,6 +102,36 @@ pub enum InputWritesItem {
     #[serde(rename = "com.atproto.repo.applyWrites#delete")]
     Delete(Box<Delete>),
 }
+impl From<Create> for InputWritesItem {
+    fn from(value: Create) -> Self {
+        InputWritesItem::Create(Box::new(value))
+    }
+}
+impl From<Create> for crate::types::Union<InputWritesItem> {
+    fn from(value: Create) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Update> for InputWritesItem {
+    fn from(value: Update) -> Self {
+        InputWritesItem::Update(Box::new(value))
+    }
+}
+impl From<Update> for crate::types::Union<InputWritesItem> {
+    fn from(value: Update) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Delete> for InputWritesItem {
+    fn from(value: Delete) -> Self {
+        InputWritesItem::Delete(Box::new(value))
+    }
+}
+impl From<Delete> for crate::types::Union<InputWritesItem> {
+    fn from(value: Delete) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum OutputResultsItem {
@@ -112,3 +142,33 @@ pub enum OutputResultsItem {
     #[serde(rename = "com.atproto.repo.applyWrites#deleteResult")]
     DeleteResult(Box<DeleteResult>),
 }
+impl From<CreateResult> for OutputResultsItem {
+    fn from(value: CreateResult) -> Self {
+        OutputResultsItem::CreateResult(Box::new(value))
+    }
+}
+impl From<CreateResult> for crate::types::Union<OutputResultsItem> {
+    fn from(value: CreateResult) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<UpdateResult> for OutputResultsItem {
+    fn from(value: UpdateResult) -> Self {
+        OutputResultsItem::UpdateResult(Box::new(value))
+    }
+}
+impl From<UpdateResult> for crate::types::Union<OutputResultsItem> {
+    fn from(value: UpdateResult) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<DeleteResult> for OutputResultsItem {
+    fn from(value: DeleteResult) -> Self {
+        OutputResultsItem::DeleteResult(Box::new(value))
+    }
+}
+impl From<DeleteResult> for crate::types::Union<OutputResultsItem> {
+    fn from(value: DeleteResult) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -159,3 +159,73 @@ pub enum Message {
     #[serde(rename = "com.atproto.sync.subscribeRepos#info")]
     Info(Box<Info>),
 }
+impl From<Commit> for Message {
+    fn from(value: Commit) -> Self {
+        Message::Commit(Box::new(value))
+    }
+}
+impl From<Commit> for crate::types::Union<Message> {
+    fn from(value: Commit) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Identity> for Message {
+    fn from(value: Identity) -> Self {
+        Message::Identity(Box::new(value))
+    }
+}
+impl From<Identity> for crate::types::Union<Message> {
+    fn from(value: Identity) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Account> for Message {
+    fn from(value: Account) -> Self {
+        Message::Account(Box::new(value))
+    }
+}
+impl From<Account> for crate::types::Union<Message> {
+    fn from(value: Account) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Handle> for Message {
+    fn from(value: Handle) -> Self {
+        Message::Handle(Box::new(value))
+    }
+}
+impl From<Handle> for crate::types::Union<Message> {
+    fn from(value: Handle) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Migrate> for Message {
+    fn from(value: Migrate) -> Self {
+        Message::Migrate(Box::new(value))
+    }
+}
+impl From<Migrate> for crate::types::Union<Message> {
+    fn from(value: Migrate) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Tombstone> for Message {
+    fn from(value: Tombstone) -> Self {
+        Message::Tombstone(Box::new(value))
+    }
+}
+impl From<Tombstone> for crate::types::Union<Message> {
+    fn from(value: Tombstone) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<Info> for Message {
+    fn from(value: Info) -> Self {
+        Message::Info(Box::new(value))
+    }
+}
+impl From<Info> for crate::types::Union<Message> {
+    fn from(value: Info) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -6,6 +6,18 @@ use thiserror::Error;
 pub enum Error {
     #[error(transparent)]
     IpldCoreSerde(#[from] ipld_core::serde::SerdeError),
+    #[error(transparent)]
+    DagCborEncode(#[from] serde_ipld_dagcbor::EncodeError<std::collections::TryReserveError>),
+    #[error(transparent)]
+    DagCborDecode(#[from] serde_ipld_dagcbor::DecodeError<std::convert::Infallible>),
+    #[cfg(feature = "agent")]
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[cfg(feature = "agent")]
+    #[error("invalid JWT: {0}")]
+    InvalidJwt(&'static str),
     #[error("not allowed in ATProtocol")]
     NotAllowed,
 }
@@ -0,0 +1,92 @@
+//! Decoding the claims of JSON Web Tokens, such as session and service auth tokens.
+//!
+//! The signature is **not** verified. Use the claims only for inspecting tokens issued to
+//! this client, e.g. to check their expiration, not for authenticating others.
+use crate::error::{Error, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Common claims of the JWTs used in atproto.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub struct Claims {
+    /// The issuer, e.g. the DID of the account for service auth tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    /// The subject, e.g. the DID of the account for session tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    /// The audience: the DID of the service the token is intended for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    /// The expiration time, in seconds since the Unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    /// The time at which the token was issued, in seconds since the Unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    /// The unique identifier of the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    /// The scope, e.g. `com.atproto.access` or `com.atproto.refresh`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// The lexicon method (NSID) a service auth token is bound to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lxm: Option<String>,
+    /// Other claims.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Decode the payload of a JWT in compact serialization as [`Claims`], without verifying it.
+pub fn decode_claims(token: &str) -> Result<Claims> {
+    decode_payload(token)
+}
+
+/// Decode the payload of a JWT in compact serialization as any type, without verifying it.
+pub fn decode_payload<T: DeserializeOwned>(token: &str) -> Result<T> {
+    let mut parts = token.split('.');
+    let (Some(_), Some(payload), Some(_), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(Error::InvalidJwt("expected 3 parts separated by '.'"));
+    };
+    Ok(serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        // a service auth token, with an invalid signature
+        let payload = URL_SAFE_NO_PAD.encode(
+            r#"{"iat":1700000000,"iss":"did:plc:alice","aud":"did:web:video.bsky.app","exp":1700000060,"lxm":"com.atproto.repo.uploadBlob","jti":"abc"}"#,
+        );
+        let claims = decode_claims(&format!("eyJhbGciOiJFUzI1NksifQ.{payload}.c2ln"))
+            .expect("decoding should succeed");
+        assert_eq!(
+            claims,
+            Claims {
+                iss: Some(String::from("did:plc:alice")),
+                aud: Some(String::from("did:web:video.bsky.app")),
+                exp: Some(1700000060),
+                iat: Some(1700000000),
+                jti: Some(String::from("abc")),
+                lxm: Some(String::from("com.atproto.repo.uploadBlob")),
+                ..Default::default()
+            }
+        );
+
+        assert!(matches!(decode_claims("access"), Err(Error::InvalidJwt(_))));
+        assert!(matches!(decode_claims("a.b*.c"), Err(Error::Base64(_))));
+        assert!(matches!(
+            decode_claims(&format!("a.{}.c", URL_SAFE_NO_PAD.encode("[]"))),
+            Err(Error::SerdeJson(_))
+        ));
+    }
+}
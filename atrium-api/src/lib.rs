@@ -1,15 +1,22 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
+#[cfg_attr(docsrs, doc(cfg(feature = "client")))]
+#[cfg(feature = "client")]
 pub use atrium_xrpc as xrpc;
 #[cfg_attr(docsrs, doc(cfg(feature = "agent")))]
 #[cfg(feature = "agent")]
 pub mod agent;
 pub mod app;
 pub mod chat;
+#[cfg_attr(docsrs, doc(cfg(feature = "client")))]
+#[cfg(feature = "client")]
 pub mod client;
 pub mod com;
 pub mod did_doc;
 pub mod error;
+#[cfg_attr(docsrs, doc(cfg(feature = "agent")))]
+#[cfg(feature = "agent")]
+pub mod jwt;
 pub mod record;
 pub mod tools;
 pub mod types;
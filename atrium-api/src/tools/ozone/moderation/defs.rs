@@ -442,6 +442,26 @@ pub enum BlobViewDetailsRefs {
     #[serde(rename = "tools.ozone.moderation.defs#videoDetails")]
     VideoDetails(Box<VideoDetails>),
 }
+impl From<ImageDetails> for BlobViewDetailsRefs {
+    fn from(value: ImageDetails) -> Self {
+        BlobViewDetailsRefs::ImageDetails(Box::new(value))
+    }
+}
+impl From<ImageDetails> for crate::types::Union<BlobViewDetailsRefs> {
+    fn from(value: ImageDetails) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<VideoDetails> for BlobViewDetailsRefs {
+    fn from(value: VideoDetails) -> Self {
+        BlobViewDetailsRefs::VideoDetails(Box::new(value))
+    }
+}
+impl From<VideoDetails> for crate::types::Union<BlobViewDetailsRefs> {
+    fn from(value: VideoDetails) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ModEventViewDetailEventRefs {
@@ -482,6 +502,186 @@ pub enum ModEventViewDetailEventRefs {
     #[serde(rename = "tools.ozone.moderation.defs#recordEvent")]
     RecordEvent(Box<RecordEvent>),
 }
+impl From<ModEventTakedown> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventTakedown) -> Self {
+        ModEventViewDetailEventRefs::ModEventTakedown(Box::new(value))
+    }
+}
+impl From<ModEventTakedown> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventTakedown) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventReverseTakedown> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventReverseTakedown) -> Self {
+        ModEventViewDetailEventRefs::ModEventReverseTakedown(Box::new(value))
+    }
+}
+impl From<ModEventReverseTakedown> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventReverseTakedown) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventComment> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventComment) -> Self {
+        ModEventViewDetailEventRefs::ModEventComment(Box::new(value))
+    }
+}
+impl From<ModEventComment> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventComment) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventReport> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventReport) -> Self {
+        ModEventViewDetailEventRefs::ModEventReport(Box::new(value))
+    }
+}
+impl From<ModEventReport> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventReport) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventLabel> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventLabel) -> Self {
+        ModEventViewDetailEventRefs::ModEventLabel(Box::new(value))
+    }
+}
+impl From<ModEventLabel> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventLabel) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventAcknowledge> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventAcknowledge) -> Self {
+        ModEventViewDetailEventRefs::ModEventAcknowledge(Box::new(value))
+    }
+}
+impl From<ModEventAcknowledge> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventAcknowledge) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventEscalate> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventEscalate) -> Self {
+        ModEventViewDetailEventRefs::ModEventEscalate(Box::new(value))
+    }
+}
+impl From<ModEventEscalate> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventEscalate) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventMute> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventMute) -> Self {
+        ModEventViewDetailEventRefs::ModEventMute(Box::new(value))
+    }
+}
+impl From<ModEventMute> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventMute) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventUnmute> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventUnmute) -> Self {
+        ModEventViewDetailEventRefs::ModEventUnmute(Box::new(value))
+    }
+}
+impl From<ModEventUnmute> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventUnmute) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventMuteReporter> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventMuteReporter) -> Self {
+        ModEventViewDetailEventRefs::ModEventMuteReporter(Box::new(value))
+    }
+}
+impl From<ModEventMuteReporter> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventMuteReporter) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventUnmuteReporter> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventUnmuteReporter) -> Self {
+        ModEventViewDetailEventRefs::ModEventUnmuteReporter(Box::new(value))
+    }
+}
+impl From<ModEventUnmuteReporter> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventUnmuteReporter) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventEmail> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventEmail) -> Self {
+        ModEventViewDetailEventRefs::ModEventEmail(Box::new(value))
+    }
+}
+impl From<ModEventEmail> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventEmail) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventResolveAppeal> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventResolveAppeal) -> Self {
+        ModEventViewDetailEventRefs::ModEventResolveAppeal(Box::new(value))
+    }
+}
+impl From<ModEventResolveAppeal> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventResolveAppeal) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventDivert> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventDivert) -> Self {
+        ModEventViewDetailEventRefs::ModEventDivert(Box::new(value))
+    }
+}
+impl From<ModEventDivert> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventDivert) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventTag> for ModEventViewDetailEventRefs {
+    fn from(value: ModEventTag) -> Self {
+        ModEventViewDetailEventRefs::ModEventTag(Box::new(value))
+    }
+}
+impl From<ModEventTag> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: ModEventTag) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<AccountEvent> for ModEventViewDetailEventRefs {
+    fn from(value: AccountEvent) -> Self {
+        ModEventViewDetailEventRefs::AccountEvent(Box::new(value))
+    }
+}
+impl From<AccountEvent> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: AccountEvent) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<IdentityEvent> for ModEventViewDetailEventRefs {
+    fn from(value: IdentityEvent) -> Self {
+        ModEventViewDetailEventRefs::IdentityEvent(Box::new(value))
+    }
+}
+impl From<IdentityEvent> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: IdentityEvent) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<RecordEvent> for ModEventViewDetailEventRefs {
+    fn from(value: RecordEvent) -> Self {
+        ModEventViewDetailEventRefs::RecordEvent(Box::new(value))
+    }
+}
+impl From<RecordEvent> for crate::types::Union<ModEventViewDetailEventRefs> {
+    fn from(value: RecordEvent) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ModEventViewDetailSubjectRefs {
@@ -494,6 +694,46 @@ pub enum ModEventViewDetailSubjectRefs {
     #[serde(rename = "tools.ozone.moderation.defs#recordViewNotFound")]
     RecordViewNotFound(Box<RecordViewNotFound>),
 }
+impl From<RepoView> for ModEventViewDetailSubjectRefs {
+    fn from(value: RepoView) -> Self {
+        ModEventViewDetailSubjectRefs::RepoView(Box::new(value))
+    }
+}
+impl From<RepoView> for crate::types::Union<ModEventViewDetailSubjectRefs> {
+    fn from(value: RepoView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<RepoViewNotFound> for ModEventViewDetailSubjectRefs {
+    fn from(value: RepoViewNotFound) -> Self {
+        ModEventViewDetailSubjectRefs::RepoViewNotFound(Box::new(value))
+    }
+}
+impl From<RepoViewNotFound> for crate::types::Union<ModEventViewDetailSubjectRefs> {
+    fn from(value: RepoViewNotFound) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<RecordView> for ModEventViewDetailSubjectRefs {
+    fn from(value: RecordView) -> Self {
+        ModEventViewDetailSubjectRefs::RecordView(Box::new(value))
+    }
+}
+impl From<RecordView> for crate::types::Union<ModEventViewDetailSubjectRefs> {
+    fn from(value: RecordView) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<RecordViewNotFound> for ModEventViewDetailSubjectRefs {
+    fn from(value: RecordViewNotFound) -> Self {
+        ModEventViewDetailSubjectRefs::RecordViewNotFound(Box::new(value))
+    }
+}
+impl From<RecordViewNotFound> for crate::types::Union<ModEventViewDetailSubjectRefs> {
+    fn from(value: RecordViewNotFound) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ModEventViewEventRefs {
@@ -534,6 +774,186 @@ pub enum ModEventViewEventRefs {
     #[serde(rename = "tools.ozone.moderation.defs#recordEvent")]
     RecordEvent(Box<RecordEvent>),
 }
+impl From<ModEventTakedown> for ModEventViewEventRefs {
+    fn from(value: ModEventTakedown) -> Self {
+        ModEventViewEventRefs::ModEventTakedown(Box::new(value))
+    }
+}
+impl From<ModEventTakedown> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventTakedown) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventReverseTakedown> for ModEventViewEventRefs {
+    fn from(value: ModEventReverseTakedown) -> Self {
+        ModEventViewEventRefs::ModEventReverseTakedown(Box::new(value))
+    }
+}
+impl From<ModEventReverseTakedown> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventReverseTakedown) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventComment> for ModEventViewEventRefs {
+    fn from(value: ModEventComment) -> Self {
+        ModEventViewEventRefs::ModEventComment(Box::new(value))
+    }
+}
+impl From<ModEventComment> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventComment) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventReport> for ModEventViewEventRefs {
+    fn from(value: ModEventReport) -> Self {
+        ModEventViewEventRefs::ModEventReport(Box::new(value))
+    }
+}
+impl From<ModEventReport> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventReport) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventLabel> for ModEventViewEventRefs {
+    fn from(value: ModEventLabel) -> Self {
+        ModEventViewEventRefs::ModEventLabel(Box::new(value))
+    }
+}
+impl From<ModEventLabel> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventLabel) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventAcknowledge> for ModEventViewEventRefs {
+    fn from(value: ModEventAcknowledge) -> Self {
+        ModEventViewEventRefs::ModEventAcknowledge(Box::new(value))
+    }
+}
+impl From<ModEventAcknowledge> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventAcknowledge) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventEscalate> for ModEventViewEventRefs {
+    fn from(value: ModEventEscalate) -> Self {
+        ModEventViewEventRefs::ModEventEscalate(Box::new(value))
+    }
+}
+impl From<ModEventEscalate> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventEscalate) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventMute> for ModEventViewEventRefs {
+    fn from(value: ModEventMute) -> Self {
+        ModEventViewEventRefs::ModEventMute(Box::new(value))
+    }
+}
+impl From<ModEventMute> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventMute) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventUnmute> for ModEventViewEventRefs {
+    fn from(value: ModEventUnmute) -> Self {
+        ModEventViewEventRefs::ModEventUnmute(Box::new(value))
+    }
+}
+impl From<ModEventUnmute> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventUnmute) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventMuteReporter> for ModEventViewEventRefs {
+    fn from(value: ModEventMuteReporter) -> Self {
+        ModEventViewEventRefs::ModEventMuteReporter(Box::new(value))
+    }
+}
+impl From<ModEventMuteReporter> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventMuteReporter) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventUnmuteReporter> for ModEventViewEventRefs {
+    fn from(value: ModEventUnmuteReporter) -> Self {
+        ModEventViewEventRefs::ModEventUnmuteReporter(Box::new(value))
+    }
+}
+impl From<ModEventUnmuteReporter> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventUnmuteReporter) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventEmail> for ModEventViewEventRefs {
+    fn from(value: ModEventEmail) -> Self {
+        ModEventViewEventRefs::ModEventEmail(Box::new(value))
+    }
+}
+impl From<ModEventEmail> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventEmail) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventResolveAppeal> for ModEventViewEventRefs {
+    fn from(value: ModEventResolveAppeal) -> Self {
+        ModEventViewEventRefs::ModEventResolveAppeal(Box::new(value))
+    }
+}
+impl From<ModEventResolveAppeal> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventResolveAppeal) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventDivert> for ModEventViewEventRefs {
+    fn from(value: ModEventDivert) -> Self {
+        ModEventViewEventRefs::ModEventDivert(Box::new(value))
+    }
+}
+impl From<ModEventDivert> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventDivert) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<ModEventTag> for ModEventViewEventRefs {
+    fn from(value: ModEventTag) -> Self {
+        ModEventViewEventRefs::ModEventTag(Box::new(value))
+    }
+}
+impl From<ModEventTag> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: ModEventTag) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<AccountEvent> for ModEventViewEventRefs {
+    fn from(value: AccountEvent) -> Self {
+        ModEventViewEventRefs::AccountEvent(Box::new(value))
+    }
+}
+impl From<AccountEvent> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: AccountEvent) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<IdentityEvent> for ModEventViewEventRefs {
+    fn from(value: IdentityEvent) -> Self {
+        ModEventViewEventRefs::IdentityEvent(Box::new(value))
+    }
+}
+impl From<IdentityEvent> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: IdentityEvent) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<RecordEvent> for ModEventViewEventRefs {
+    fn from(value: RecordEvent) -> Self {
+        ModEventViewEventRefs::RecordEvent(Box::new(value))
+    }
+}
+impl From<RecordEvent> for crate::types::Union<ModEventViewEventRefs> {
+    fn from(value: RecordEvent) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum ModEventViewSubjectRefs {
@@ -544,6 +964,39 @@ pub enum ModEventViewSubjectRefs {
     #[serde(rename = "chat.bsky.convo.defs#messageRef")]
     ChatBskyConvoDefsMessageRef(Box<crate::chat::bsky::convo::defs::MessageRef>),
 }
+impl From<crate::com::atproto::admin::defs::RepoRef> for ModEventViewSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        ModEventViewSubjectRefs::ComAtprotoAdminDefsRepoRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoRef>
+for crate::types::Union<ModEventViewSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main> for ModEventViewSubjectRefs {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        ModEventViewSubjectRefs::ComAtprotoRepoStrongRefMain(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main>
+for crate::types::Union<ModEventViewSubjectRefs> {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::chat::bsky::convo::defs::MessageRef> for ModEventViewSubjectRefs {
+    fn from(value: crate::chat::bsky::convo::defs::MessageRef) -> Self {
+        ModEventViewSubjectRefs::ChatBskyConvoDefsMessageRef(Box::new(value))
+    }
+}
+impl From<crate::chat::bsky::convo::defs::MessageRef>
+for crate::types::Union<ModEventViewSubjectRefs> {
+    fn from(value: crate::chat::bsky::convo::defs::MessageRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum SubjectStatusViewHostingRefs {
@@ -552,6 +1005,26 @@ pub enum SubjectStatusViewHostingRefs {
     #[serde(rename = "tools.ozone.moderation.defs#recordHosting")]
     RecordHosting(Box<RecordHosting>),
 }
+impl From<AccountHosting> for SubjectStatusViewHostingRefs {
+    fn from(value: AccountHosting) -> Self {
+        SubjectStatusViewHostingRefs::AccountHosting(Box::new(value))
+    }
+}
+impl From<AccountHosting> for crate::types::Union<SubjectStatusViewHostingRefs> {
+    fn from(value: AccountHosting) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<RecordHosting> for SubjectStatusViewHostingRefs {
+    fn from(value: RecordHosting) -> Self {
+        SubjectStatusViewHostingRefs::RecordHosting(Box::new(value))
+    }
+}
+impl From<RecordHosting> for crate::types::Union<SubjectStatusViewHostingRefs> {
+    fn from(value: RecordHosting) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum SubjectStatusViewSubjectRefs {
@@ -560,3 +1033,25 @@ pub enum SubjectStatusViewSubjectRefs {
     #[serde(rename = "com.atproto.repo.strongRef")]
     ComAtprotoRepoStrongRefMain(Box<crate::com::atproto::repo::strong_ref::Main>),
 }
+impl From<crate::com::atproto::admin::defs::RepoRef> for SubjectStatusViewSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        SubjectStatusViewSubjectRefs::ComAtprotoAdminDefsRepoRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoRef>
+for crate::types::Union<SubjectStatusViewSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main> for SubjectStatusViewSubjectRefs {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        SubjectStatusViewSubjectRefs::ComAtprotoRepoStrongRefMain(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main>
+for crate::types::Union<SubjectStatusViewSubjectRefs> {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -102,6 +102,210 @@ pub enum InputEventRefs {
         Box<crate::tools::ozone::moderation::defs::RecordEvent>,
     ),
 }
+impl From<crate::tools::ozone::moderation::defs::ModEventTakedown> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventTakedown) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventTakedown(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventTakedown>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventTakedown) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventAcknowledge>
+for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventAcknowledge) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventAcknowledge(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventAcknowledge>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventAcknowledge) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventEscalate> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventEscalate) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventEscalate(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventEscalate>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventEscalate) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventComment> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventComment) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventComment(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventComment>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventComment) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventLabel> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventLabel) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventLabel(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventLabel>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventLabel) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventReport> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventReport) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventReport(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventReport>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventReport) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventMute> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventMute) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventMute(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventMute>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventMute) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventUnmute> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventUnmute) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventUnmute(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventUnmute>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventUnmute) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventMuteReporter>
+for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventMuteReporter) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventMuteReporter(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventMuteReporter>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventMuteReporter) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventUnmuteReporter>
+for InputEventRefs {
+    fn from(
+        value: crate::tools::ozone::moderation::defs::ModEventUnmuteReporter,
+    ) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventUnmuteReporter(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventUnmuteReporter>
+for crate::types::Union<InputEventRefs> {
+    fn from(
+        value: crate::tools::ozone::moderation::defs::ModEventUnmuteReporter,
+    ) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventReverseTakedown>
+for InputEventRefs {
+    fn from(
+        value: crate::tools::ozone::moderation::defs::ModEventReverseTakedown,
+    ) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventReverseTakedown(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventReverseTakedown>
+for crate::types::Union<InputEventRefs> {
+    fn from(
+        value: crate::tools::ozone::moderation::defs::ModEventReverseTakedown,
+    ) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventResolveAppeal>
+for InputEventRefs {
+    fn from(
+        value: crate::tools::ozone::moderation::defs::ModEventResolveAppeal,
+    ) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventResolveAppeal(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventResolveAppeal>
+for crate::types::Union<InputEventRefs> {
+    fn from(
+        value: crate::tools::ozone::moderation::defs::ModEventResolveAppeal,
+    ) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventEmail> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventEmail) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventEmail(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventEmail>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventEmail) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventTag> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventTag) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsModEventTag(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::ModEventTag>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::ModEventTag) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::AccountEvent> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::AccountEvent) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsAccountEvent(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::AccountEvent>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::AccountEvent) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::IdentityEvent> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::IdentityEvent) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsIdentityEvent(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::IdentityEvent>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::IdentityEvent) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::RecordEvent> for InputEventRefs {
+    fn from(value: crate::tools::ozone::moderation::defs::RecordEvent) -> Self {
+        InputEventRefs::ToolsOzoneModerationDefsRecordEvent(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::RecordEvent>
+for crate::types::Union<InputEventRefs> {
+    fn from(value: crate::tools::ozone::moderation::defs::RecordEvent) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(tag = "$type")]
 pub enum InputSubjectRefs {
@@ -110,3 +314,25 @@ pub enum InputSubjectRefs {
     #[serde(rename = "com.atproto.repo.strongRef")]
     ComAtprotoRepoStrongRefMain(Box<crate::com::atproto::repo::strong_ref::Main>),
 }
+impl From<crate::com::atproto::admin::defs::RepoRef> for InputSubjectRefs {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        InputSubjectRefs::ComAtprotoAdminDefsRepoRef(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::admin::defs::RepoRef>
+for crate::types::Union<InputSubjectRefs> {
+    fn from(value: crate::com::atproto::admin::defs::RepoRef) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main> for InputSubjectRefs {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        InputSubjectRefs::ComAtprotoRepoStrongRefMain(Box::new(value))
+    }
+}
+impl From<crate::com::atproto::repo::strong_ref::Main>
+for crate::types::Union<InputSubjectRefs> {
+    fn from(value: crate::com::atproto::repo::strong_ref::Main) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
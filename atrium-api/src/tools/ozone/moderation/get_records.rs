@@ -33,3 +33,27 @@ pub enum OutputRecordsItem {
         Box<crate::tools::ozone::moderation::defs::RecordViewNotFound>,
     ),
 }
+impl From<crate::tools::ozone::moderation::defs::RecordViewDetail>
+for OutputRecordsItem {
+    fn from(value: crate::tools::ozone::moderation::defs::RecordViewDetail) -> Self {
+        OutputRecordsItem::ToolsOzoneModerationDefsRecordViewDetail(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::RecordViewDetail>
+for crate::types::Union<OutputRecordsItem> {
+    fn from(value: crate::tools::ozone::moderation::defs::RecordViewDetail) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::RecordViewNotFound>
+for OutputRecordsItem {
+    fn from(value: crate::tools::ozone::moderation::defs::RecordViewNotFound) -> Self {
+        OutputRecordsItem::ToolsOzoneModerationDefsRecordViewNotFound(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::RecordViewNotFound>
+for crate::types::Union<OutputRecordsItem> {
+    fn from(value: crate::tools::ozone::moderation::defs::RecordViewNotFound) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -33,3 +33,25 @@ pub enum OutputReposItem {
         Box<crate::tools::ozone::moderation::defs::RepoViewNotFound>,
     ),
 }
+impl From<crate::tools::ozone::moderation::defs::RepoViewDetail> for OutputReposItem {
+    fn from(value: crate::tools::ozone::moderation::defs::RepoViewDetail) -> Self {
+        OutputReposItem::ToolsOzoneModerationDefsRepoViewDetail(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::RepoViewDetail>
+for crate::types::Union<OutputReposItem> {
+    fn from(value: crate::tools::ozone::moderation::defs::RepoViewDetail) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::RepoViewNotFound> for OutputReposItem {
+    fn from(value: crate::tools::ozone::moderation::defs::RepoViewNotFound) -> Self {
+        OutputReposItem::ToolsOzoneModerationDefsRepoViewNotFound(Box::new(value))
+    }
+}
+impl From<crate::tools::ozone::moderation::defs::RepoViewNotFound>
+for crate::types::Union<OutputReposItem> {
+    fn from(value: crate::tools::ozone::moderation::defs::RepoViewNotFound) -> Self {
+        crate::types::Union::Refs(value.into())
+    }
+}
@@ -11,13 +11,15 @@ use std::fmt;
 use std::ops::{Deref, DerefMut};
 
 mod cid_link;
-pub use cid_link::CidLink;
+pub use cid_link::{CidLink, CidLinkError};
+
+pub mod dag_cbor;
 
 mod integer;
 pub use integer::*;
 
 pub mod string;
-use string::RecordKey;
+use string::{Did, RecordKey};
 
 /// Trait for a collection of records that can be stored in a repository.
 ///
@@ -55,6 +57,29 @@ pub trait Collection: fmt::Debug {
     fn repo_path(rkey: &RecordKey) -> String {
         format!("{}/{}", Self::NSID, rkey.as_str())
     }
+
+    /// Returns the [AT URI] for a record in this collection owned by the given DID.
+    ///
+    /// [AT URI]: https://atproto.com/specs/at-uri-scheme
+    fn record_uri(did: &Did, rkey: &RecordKey) -> String {
+        format!("at://{}/{}", did.as_str(), Self::repo_path(rkey))
+    }
+
+    /// Splits a repo path of the form `<collection>/<record-key>` into its collection and
+    /// record key parts.
+    ///
+    /// Returns `None` if the path does not have exactly two non-empty segments.
+    /// This does not check that the collection matches [`Self::NSID`].
+    fn split_path(path: &str) -> Option<(&str, &str)> {
+        match path.split_once('/') {
+            Some((collection, rkey))
+                if !collection.is_empty() && !rkey.is_empty() && !rkey.contains('/') =>
+            {
+                Some((collection, rkey))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Definitions for Blob types.
@@ -91,8 +116,52 @@ pub struct Blob {
     pub size: usize, // TODO
 }
 
+/// Constraints on a blob, declared by the `accept` and `maxSize` fields of a Lexicon blob type.
+/// <https://atproto.com/specs/lexicon#blob>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobConstraints {
+    /// Accepted MIME types, which may be glob patterns such as `image/*`.
+    /// Any type is accepted if empty.
+    pub accept: &'static [&'static str],
+    /// Maximum size in bytes.
+    pub max_size: Option<usize>,
+}
+
+impl BlobConstraints {
+    /// Returns whether the given MIME type is accepted.
+    pub fn accepts(&self, mime_type: &str) -> bool {
+        accepts_mime_type(self.accept, mime_type)
+    }
+    /// Returns whether the given MIME type matches an `accept` pattern, such as `image/*` or `*/*`.
+    pub fn matches(pattern: &str, mime_type: &str) -> bool {
+        match pattern.strip_suffix('*') {
+            Some("*/") => true,
+            Some(prefix) => mime_type.starts_with(prefix),
+            None => pattern == mime_type,
+        }
+    }
+    /// Returns whether a blob with the given MIME type and size satisfies the constraints.
+    pub fn allows(&self, mime_type: &str, size: usize) -> bool {
+        self.accepts(mime_type) && self.max_size.map_or(true, |max_size| size <= max_size)
+    }
+}
+
+/// Returns whether the given MIME type is accepted by a list of `accept` patterns, as in
+/// [`BlobConstraints::accepts()`]. Any type is accepted if the list is empty.
+///
+/// Unlike [`BlobConstraints`], the patterns need not be `'static`, e.g. if they are read from a
+/// configuration file.
+pub fn accepts_mime_type(accept: &[impl AsRef<str>], mime_type: &str) -> bool {
+    accept.is_empty()
+        || accept.iter().any(|pattern| BlobConstraints::matches(pattern.as_ref(), mime_type))
+}
+
 /// A generic object type.
+///
+/// Fields not defined in `T` are kept in `extra_data`. With the `deny-unknown-fields` feature
+/// enabled, deserialization fails instead if there are any such fields (other than `$type`).
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "deny-unknown-fields", serde(try_from = "StrictObject<T>"))]
 pub struct Object<T> {
     #[serde(flatten)]
     pub data: T,
@@ -100,6 +169,42 @@ pub struct Object<T> {
     pub extra_data: Ipld,
 }
 
+#[cfg(feature = "deny-unknown-fields")]
+#[derive(Deserialize)]
+struct StrictObject<T> {
+    #[serde(flatten)]
+    data: T,
+    #[serde(flatten)]
+    extra_data: Ipld,
+}
+
+#[cfg(feature = "deny-unknown-fields")]
+impl<T> TryFrom<StrictObject<T>> for Object<T> {
+    type Error = String;
+
+    fn try_from(value: StrictObject<T>) -> Result<Self, Self::Error> {
+        if let Ipld::Map(map) = &value.extra_data {
+            let unknown =
+                map.keys().filter(|k| *k != "$type").map(|k| format!("`{k}`")).collect::<Vec<_>>();
+            if !unknown.is_empty() {
+                return Err(format!("unknown field(s): {}", unknown.join(", ")));
+            }
+        }
+        Ok(Self { data: value.data, extra_data: value.extra_data })
+    }
+}
+
+impl<T> Object<T> {
+    /// Consumes the object, returning its data without the `extra_data`.
+    ///
+    /// The fields of the data can also be accessed directly through [`Deref`], so that
+    /// e.g. `output.job_status.state` can be written instead of
+    /// `output.data.job_status.data.state`.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
 impl<T> From<T> for Object<T> {
     fn from(data: T) -> Self {
         Self { data, extra_data: Ipld::Map(std::collections::BTreeMap::new()) }
@@ -120,7 +225,23 @@ impl<T> DerefMut for Object<T> {
     }
 }
 
+impl<T> AsRef<T> for Object<T> {
+    fn as_ref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<T> AsMut<T> for Object<T> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+}
+
 /// An "open" union type.
+///
+/// A variant whose `$type` is not one of the known refs is deserialized as [`Union::Unknown`]
+/// instead of failing, and is serialized back unchanged, so that data using newer lexicons
+/// is not lost.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum Union<T> {
@@ -298,6 +419,47 @@ where
     }
 }
 
+/// Trait for types that can be deserialized from an [`Ipld`] value.
+///
+/// Unlike [`TryFromUnknown`], this does not go through JSON, so bytes and CID links are
+/// preserved. This is suitable for records decoded from DAG-CBOR blocks.
+pub trait TryFromIpld: Sized {
+    type Error;
+
+    fn try_from_ipld(value: Ipld) -> Result<Self, Self::Error>;
+}
+
+impl<T> TryFromIpld for T
+where
+    T: de::DeserializeOwned,
+{
+    type Error = Error;
+
+    fn try_from_ipld(value: Ipld) -> Result<Self, Self::Error> {
+        // Deserializing directly from `Ipld` has the same problem as described in
+        // `TryFromUnknown`, so encode it to DAG-CBOR and deserialize from the bytes instead.
+        Ok(serde_ipld_dagcbor::from_slice(&serde_ipld_dagcbor::to_vec(&value)?)?)
+    }
+}
+
+/// Trait for types that can be serialized into an [`Ipld`] value.
+pub trait TryIntoIpld {
+    type Error;
+
+    fn try_into_ipld(self) -> Result<Ipld, Self::Error>;
+}
+
+impl<T> TryIntoIpld for T
+where
+    T: Serialize,
+{
+    type Error = Error;
+
+    fn try_into_ipld(self) -> Result<Ipld, Self::Error> {
+        Ok(to_ipld(self)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -392,6 +554,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn blob_constraints() {
+        let images = BlobConstraints { accept: &["image/*"], max_size: Some(1_000_000) };
+        assert!(images.allows("image/png", 1_000_000));
+        assert!(!images.allows("image/png", 1_000_001));
+        assert!(!images.allows("video/mp4", 1024));
+        let avatar = BlobConstraints { accept: &["image/png", "image/jpeg"], max_size: None };
+        assert!(avatar.allows("image/jpeg", usize::MAX));
+        assert!(!avatar.accepts("image/webp"));
+        let any = BlobConstraints { accept: &[], max_size: None };
+        assert!(any.accepts("application/octet-stream"));
+        assert!(BlobConstraints { accept: &["*/*"], max_size: None }.accepts("text/plain"));
+    }
+
+    #[test]
+    fn collection_helpers() {
+        #[derive(Debug)]
+        struct Example;
+
+        impl Collection for Example {
+            const NSID: &'static str = "com.example.record";
+            type Record = ();
+        }
+
+        let did = "did:web:example.com".parse().expect("invalid did");
+        let rkey = "3jwdwj2ctlk26".parse().expect("invalid rkey");
+        assert_eq!(
+            Example::record_uri(&did, &rkey),
+            "at://did:web:example.com/com.example.record/3jwdwj2ctlk26"
+        );
+        assert_eq!(
+            Example::split_path("com.example.record/3jwdwj2ctlk26"),
+            Some(("com.example.record", "3jwdwj2ctlk26"))
+        );
+        assert_eq!(
+            Example::split_path(&Example::repo_path(&rkey)),
+            Some((Example::NSID, "3jwdwj2ctlk26"))
+        );
+        for invalid in ["", "com.example.record", "com.example.record/", "/3jwdwj2ctlk26", "a/b/c"]
+        {
+            assert_eq!(Example::split_path(invalid), None, "`{invalid}` should not be split");
+        }
+    }
+
     #[test]
     fn data_model() {
         assert!(DataModel::try_from(Ipld::Null).is_ok());
@@ -467,6 +673,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn union_unknown_roundtrip() {
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+        #[serde(tag = "$type")]
+        enum FooRefs {
+            #[serde(rename = "example.com#bar")]
+            Bar(Box<Bar>),
+        }
+
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+        struct Bar {
+            bar: String,
+        }
+
+        type Foo = Union<FooRefs>;
+
+        let json = r#"{"$type":"example.com#foo","foo":{"bar":[1,"baz",null]}}"#;
+        let foo = serde_json::from_str::<Foo>(json).expect("failed to deserialize foo");
+        assert!(matches!(foo, Union::Unknown(_)));
+        assert_eq!(serde_json::to_string(&foo).expect("failed to serialize foo"), json);
+
+        let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy")
+            .expect("failed to create cid");
+        let cbor = serde_ipld_dagcbor::to_vec(&Ipld::Map(BTreeMap::from_iter([
+            (String::from("$type"), Ipld::String(String::from("example.com#foo"))),
+            (String::from("link"), Ipld::Link(cid)),
+            (String::from("bytes"), Ipld::Bytes(vec![1, 2, 3])),
+        ])))
+        .expect("failed to encode");
+        let foo = serde_ipld_dagcbor::from_slice::<Foo>(&cbor).expect("failed to deserialize foo");
+        assert!(matches!(foo, Union::Unknown(_)));
+        assert_eq!(serde_ipld_dagcbor::to_vec(&foo).expect("failed to serialize foo"), cbor);
+    }
+
+    #[test]
+    fn object_unknown_fields() {
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+        struct FooData {
+            foo: String,
+        }
+
+        type Foo = Object<FooData>;
+
+        let result = serde_json::from_str::<Foo>(r#"{"$type":"example.com#foo","foo":"foo"}"#);
+        assert!(result.is_ok(), "`$type` should always be allowed");
+
+        let result = serde_json::from_str::<Foo>(r#"{"foo":"foo","bar":42}"#);
+        if cfg!(feature = "deny-unknown-fields") {
+            assert!(result.is_err(), "unknown field should be rejected");
+        } else {
+            let foo = result.expect("failed to deserialize foo");
+            assert_eq!(
+                foo.extra_data,
+                Ipld::Map(BTreeMap::from_iter([(String::from("bar"), Ipld::Integer(42))]))
+            );
+        }
+    }
+
+    #[test]
+    fn object_data() {
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+        struct FooData {
+            foo: String,
+        }
+
+        let mut foo = serde_json::from_str::<Object<FooData>>(r#"{"foo":"foo"}"#)
+            .expect("failed to deserialize foo");
+        assert_eq!(foo.foo, "foo");
+        foo.as_mut().foo = String::from("bar");
+        assert_eq!(foo.as_ref(), &FooData { foo: String::from("bar") });
+        assert_eq!(foo.into_data(), FooData { foo: String::from("bar") });
+    }
+
     #[test]
     fn unknown_serialize() {
         #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -638,4 +917,32 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn ipld_roundtrip() {
+        #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+        struct Foo {
+            blob: BlobRef,
+            #[serde(with = "serde_bytes")]
+            bytes: Vec<u8>,
+        }
+
+        let cid_link =
+            CidLink::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy")
+                .expect("failed to create cid-link");
+        let foo = Foo {
+            blob: BlobRef::Typed(TypedBlobRef::Blob(Blob {
+                r#ref: cid_link.clone(),
+                mime_type: "text/plain".into(),
+                size: 42,
+            })),
+            bytes: vec![0x01, 0x02],
+        };
+        let ipld = foo.clone().try_into_ipld().expect("failed to convert to ipld");
+        let Ipld::Map(map) = &ipld else { panic!("expected map, got {ipld:?}") };
+        assert_eq!(map.get("bytes"), Some(&Ipld::Bytes(vec![0x01, 0x02])));
+        let Some(Ipld::Map(blob)) = map.get("blob") else { panic!("expected blob map") };
+        assert_eq!(blob.get("ref"), Some(&Ipld::Link(cid_link.0)));
+        assert_eq!(Foo::try_from_ipld(ipld).expect("failed to convert from ipld"), foo);
+    }
 }
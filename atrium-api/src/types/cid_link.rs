@@ -1,11 +1,52 @@
-use ipld_core::cid::{Cid, Error};
+use ipld_core::cid::{Cid, Error, Version};
 use ipld_core::ipld::Ipld;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The multicodec code of DAG-CBOR.
+const DAG_CBOR: u64 = 0x71;
+/// The multicodec code of raw binary.
+const RAW: u64 = 0x55;
+/// The multihash code of SHA-256.
+const SHA2_256: u64 = 0x12;
 
 /// Representation of an IPLD Link.
+///
+/// Every conversion into a link ([`CidLink::new()`], [`FromStr`], [`TryFrom<&str>`] and
+/// deserialization) checks that the CID is in a
+/// [blessed format](https://atproto.com/specs/data-model#link-and-cid-formats).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CidLink(pub Cid);
 
+impl CidLink {
+    /// Creates a link, checking that the CID is a CIDv1 with the `dag-cbor` or `raw` codec
+    /// and a `sha-256` hash, as required by the AT Protocol.
+    pub fn new(cid: Cid) -> Result<Self, CidLinkError> {
+        let link = Self(cid);
+        if link.is_blessed() {
+            Ok(link)
+        } else {
+            Err(CidLinkError::NotBlessed(cid))
+        }
+    }
+    /// Returns whether the CID is in a format blessed by the AT Protocol.
+    pub fn is_blessed(&self) -> bool {
+        self.0.version() == Version::V1
+            && [DAG_CBOR, RAW].contains(&self.0.codec())
+            && self.0.hash().code() == SHA2_256
+            && self.0.hash().size() == 32
+    }
+}
+
+/// The reason why a CID cannot be a [`CidLink`].
+#[derive(Debug, thiserror::Error)]
+pub enum CidLinkError {
+    #[error(transparent)]
+    Cid(#[from] Error),
+    #[error("cid `{0}` is not in a blessed format")]
+    NotBlessed(Cid),
+}
+
 #[derive(Serialize, Deserialize)]
 struct Link {
     #[serde(rename = "$link")]
@@ -27,14 +68,12 @@ impl<'de> Deserialize<'de> for CidLink {
         let ipld = Ipld::deserialize(deserializer)?;
         match &ipld {
             Ipld::Link(cid) => {
-                return Ok(Self(*cid));
+                return Self::new(*cid).map_err(serde::de::Error::custom);
             }
             Ipld::Map(map) => {
                 if map.len() == 1 {
                     if let Some(Ipld::String(link)) = map.get("$link") {
-                        return Ok(Self(
-                            Cid::try_from(link.as_str()).map_err(serde::de::Error::custom)?,
-                        ));
+                        return link.parse().map_err(serde::de::Error::custom);
                     }
                 }
             }
@@ -44,11 +83,25 @@ impl<'de> Deserialize<'de> for CidLink {
     }
 }
 
+impl FromStr for CidLink {
+    type Err = CidLinkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(Cid::try_from(s)?)
+    }
+}
+
+impl From<CidLink> for Cid {
+    fn from(link: CidLink) -> Self {
+        link.0
+    }
+}
+
 impl TryFrom<&str> for CidLink {
-    type Error = Error;
+    type Error = CidLinkError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        Ok(Self(Cid::try_from(s)?))
+        s.parse()
     }
 }
 
@@ -155,4 +208,37 @@ mod tests {
             from_slice::<CidLink>(&serialized).expect("failed to deserialize cid-link");
         assert_eq!(deserialized, cid_link);
     }
+
+    #[test]
+    fn cid_link_blessed() {
+        for s in [
+            "bafyreidfayvfuwqa7qlnopdjiqrxzs6blmoeu4rujcjtnci5beludirz2a",
+            "bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy",
+        ] {
+            let cid_link = s.parse::<CidLink>().expect("blessed cid should be accepted");
+            assert!(cid_link.is_blessed());
+            assert_eq!(Cid::from(cid_link).to_string(), s);
+        }
+        for s in [
+            // CIDv0
+            "QmY7Yh4UquoXHLPFo2XbhXkhBvFoPwmQUSa92pxnxjQuPU",
+            // dag-pb codec
+            "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            // blake2b-256 hash
+            "bafyr4iadagdwqn3ofbqfoo4xjb2gg6t2x4i2n5hvbvbkcwbbztxdf5a6na",
+        ] {
+            assert!(Cid::try_from(s).is_ok(), "{s} should be a valid cid");
+            assert!(s.parse::<CidLink>().is_err(), "{s} should not be blessed");
+            assert!(CidLink::try_from(s).is_err(), "{s} should not be blessed");
+            let json = format!(r#"{{"$link":"{s}"}}"#);
+            assert!(from_str::<CidLink>(&json).is_err(), "{s} should not be deserialized");
+            let dagcbor = to_vec(&Cid::try_from(s).unwrap()).unwrap();
+            assert!(from_slice::<CidLink>(&dagcbor).is_err(), "{s} should not be deserialized");
+        }
+        assert!(matches!(
+            "QmY7Yh4UquoXHLPFo2XbhXkhBvFoPwmQUSa92pxnxjQuPU".parse::<CidLink>(),
+            Err(CidLinkError::NotBlessed(_))
+        ));
+        assert!(matches!("invalid".parse::<CidLink>(), Err(CidLinkError::Cid(_))));
+    }
 }
@@ -0,0 +1,62 @@
+//! Canonical DAG-CBOR encoding of records.
+//! <https://atproto.com/specs/data-model#data-representation>
+use crate::error::Result;
+use ipld_core::ipld::Ipld;
+
+/// Encode a value in the canonical DAG-CBOR form: map keys sorted by length and then bytewise,
+/// integers and lengths in their shortest form, and floats as 64-bit.
+///
+/// The CID of a record must be computed from these bytes.
+pub fn canonicalize(ipld: &Ipld) -> Result<Vec<u8>> {
+    Ok(serde_ipld_dagcbor::to_vec(ipld)?)
+}
+
+/// Check whether the bytes are a single DAG-CBOR value in the canonical form.
+///
+/// A record that is not canonically encoded (e.g. with unsorted map keys, or integers that are
+/// not in their shortest form) has a different CID when re-encoded, so it should be treated as
+/// malformed. Bytes that cannot be decoded as DAG-CBOR are not canonical either.
+pub fn is_canonical_dagcbor(bytes: &[u8]) -> bool {
+    serde_ipld_dagcbor::from_slice::<Ipld>(bytes)
+        .ok()
+        .and_then(|ipld| canonicalize(&ipld).ok())
+        .is_some_and(|canonical| canonical == bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn canonicalize_map() {
+        let ipld = Ipld::Map(BTreeMap::from_iter([
+            (String::from("bb"), Ipld::Integer(1)),
+            (String::from("a"), Ipld::Integer(2)),
+            (String::from("c"), Ipld::Integer(300)),
+        ]));
+        let bytes = canonicalize(&ipld).expect("failed to canonicalize");
+        // shorter keys first, then bytewise
+        assert_eq!(
+            bytes,
+            [0xa3, 0x61, b'a', 0x02, 0x61, b'c', 0x19, 0x01, 0x2c, 0x62, b'b', b'b', 0x01]
+        );
+        assert!(is_canonical_dagcbor(&bytes));
+    }
+
+    #[test]
+    fn non_canonical() {
+        // {"bb": 1, "a": 2}: keys not sorted
+        assert!(!is_canonical_dagcbor(&[0xa2, 0x62, b'b', b'b', 0x01, 0x61, b'a', 0x02]));
+        // {"a": 2}: integer not in the shortest form
+        assert!(!is_canonical_dagcbor(&[0xa1, 0x61, b'a', 0x18, 0x02]));
+        // {"a": 1.5}: 32-bit float
+        assert!(!is_canonical_dagcbor(&[0xa1, 0x61, b'a', 0xfa, 0x3f, 0xc0, 0x00, 0x00]));
+        // {"a": [2]}: indefinite-length array
+        assert!(!is_canonical_dagcbor(&[0xa1, 0x61, b'a', 0x9f, 0x02, 0xff]));
+        // {"a": 2} followed by trailing bytes
+        assert!(!is_canonical_dagcbor(&[0xa1, 0x61, b'a', 0x02, 0x00]));
+        assert!(!is_canonical_dagcbor(&[]));
+        assert!(is_canonical_dagcbor(&[0xa1, 0x61, b'a', 0x02]));
+    }
+}
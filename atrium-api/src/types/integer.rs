@@ -4,8 +4,62 @@ use std::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8};
 
 use serde::{de::Error, Deserialize};
 
+/// Create a limited integer from a constant, failing to compile if it is out of range.
+///
+/// The type is inferred from the context, so this can be used wherever one of the limited
+/// integer types such as [`LimitedNonZeroU8`] or [`BoundedU16`] is expected.
+///
+/// ```
+/// use atrium_api::bounded;
+/// use atrium_api::types::LimitedNonZeroU8;
+///
+/// let limit: LimitedNonZeroU8<100> = bounded!(25);
+/// assert_eq!(u8::from(limit), 25);
+/// ```
+///
+/// ```compile_fail
+/// use atrium_api::bounded;
+/// use atrium_api::types::LimitedNonZeroU8;
+///
+/// let limit: LimitedNonZeroU8<100> = bounded!(101);
+/// ```
+#[macro_export]
+macro_rules! bounded {
+    ($value:expr) => {
+        <_ as $crate::types::ConstBounded>::new_const::<{ $value }>()
+    };
+}
+
+/// Construction of the limited integer types from constants, checked at compile time.
+///
+/// This is usually used through the [`bounded!`](crate::bounded) macro.
+pub trait ConstBounded: Sized {
+    /// Create a value from the constant `VALUE`.
+    ///
+    /// Fails to compile if `VALUE` is out of the range of `Self`.
+    fn new_const<const VALUE: u64>() -> Self;
+}
+
 macro_rules! uint {
-    ($primitive:ident, $nz:ident, $lim:ident, $lim_nz:ident, $bounded:ident) => {
+    ($primitive:ident, $nz:ident, $lim:ident, $lim_nz:ident, $bounded:ident, $check:ident) => {
+        struct $check<const MIN: $primitive, const MAX: $primitive, const VALUE: u64>;
+
+        impl<const MIN: $primitive, const MAX: $primitive, const VALUE: u64>
+            $check<MIN, MAX, VALUE>
+        {
+            // Evaluated when `new_const` is instantiated, so that an out-of-range value is
+            // reported as a compile error.
+            const VALUE: $primitive = {
+                assert!(VALUE >= MIN as u64, "value is less than the minimum");
+                assert!(VALUE <= MAX as u64, "value is greater than the maximum");
+                VALUE as $primitive
+            };
+            const NON_ZERO: $nz = match $nz::new(Self::VALUE) {
+                Some(value) => value,
+                None => panic!("value is zero"),
+            };
+        }
+
         /// An unsigned integer with a maximum value of `MAX`.
         #[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, Hash)]
         #[repr(transparent)]
@@ -46,6 +100,12 @@ macro_rules! uint {
             }
         }
 
+        impl<const MAX: $primitive> ConstBounded for $lim<MAX> {
+            fn new_const<const VALUE: u64>() -> Self {
+                Self($check::<0, MAX, VALUE>::VALUE)
+            }
+        }
+
         impl<const MAX: $primitive> From<$lim<MAX>> for $primitive {
             fn from(value: $lim<MAX>) -> Self {
                 value.0
@@ -96,6 +156,12 @@ macro_rules! uint {
             }
         }
 
+        impl<const MAX: $primitive> ConstBounded for $lim_nz<MAX> {
+            fn new_const<const VALUE: u64>() -> Self {
+                Self($check::<1, MAX, VALUE>::NON_ZERO)
+            }
+        }
+
         impl<const MAX: $primitive> From<$lim_nz<MAX>> for $nz {
             fn from(value: $lim_nz<MAX>) -> Self {
                 value.0
@@ -158,6 +224,12 @@ macro_rules! uint {
             }
         }
 
+        impl<const MIN: $primitive, const MAX: $primitive> ConstBounded for $bounded<MIN, MAX> {
+            fn new_const<const VALUE: u64>() -> Self {
+                Self($check::<MIN, MAX, VALUE>::NON_ZERO)
+            }
+        }
+
         impl<const MIN: $primitive, const MAX: $primitive> From<$bounded<MIN, MAX>> for $nz {
             fn from(value: $bounded<MIN, MAX>) -> Self {
                 value.0
@@ -172,10 +244,10 @@ macro_rules! uint {
     };
 }
 
-uint!(u8, NonZeroU8, LimitedU8, LimitedNonZeroU8, BoundedU8);
-uint!(u16, NonZeroU16, LimitedU16, LimitedNonZeroU16, BoundedU16);
-uint!(u32, NonZeroU32, LimitedU32, LimitedNonZeroU32, BoundedU32);
-uint!(u64, NonZeroU64, LimitedU64, LimitedNonZeroU64, BoundedU64);
+uint!(u8, NonZeroU8, LimitedU8, LimitedNonZeroU8, BoundedU8, CheckU8);
+uint!(u16, NonZeroU16, LimitedU16, LimitedNonZeroU16, BoundedU16, CheckU16);
+uint!(u32, NonZeroU32, LimitedU32, LimitedNonZeroU32, BoundedU32, CheckU32);
+uint!(u64, NonZeroU64, LimitedU64, LimitedNonZeroU64, BoundedU64, CheckU64);
 
 #[cfg(test)]
 mod tests {
@@ -190,4 +262,13 @@ mod tests {
         assert_eq!(Ok(BoundedU8::<7, 10>::MIN), 7.try_into());
         assert_eq!(Ok(BoundedU8::<7, 10>::MAX), 10.try_into());
     }
+
+    #[test]
+    fn new_const() {
+        assert_eq!(Ok(LimitedU8::<10>::new_const::<0>()), 0.try_into());
+        assert_eq!(Ok(LimitedNonZeroU16::<1000>::new_const::<1000>()), 1000.try_into());
+        assert_eq!(Ok(BoundedU32::<7, 10>::new_const::<7>()), 7.try_into());
+        let limit: Option<LimitedNonZeroU8<100>> = Some(crate::bounded!(25));
+        assert_eq!(limit, 25.try_into().ok());
+    }
 }
@@ -7,14 +7,20 @@ use ipld_core::cid;
 use langtag::{LanguageTag, LanguageTagBuf};
 use regex::Regex;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
-use std::{cmp, ops::Deref, str::FromStr, sync::OnceLock};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{cmp, fmt, ops::Deref, str::FromStr, sync::OnceLock};
 
 /// Common trait implementations for Lexicon string formats that are newtype wrappers
 /// around `String`.
 macro_rules! string_newtype {
     ($name:ident) => {
+        string_newtype!($name, &'static str);
+    };
+    ($name:ident, $err:ty) => {
         impl FromStr for $name {
-            type Err = &'static str;
+            type Err = $err;
 
             fn from_str(s: &str) -> Result<Self, Self::Err> {
                 Self::new(s.into())
@@ -80,7 +86,7 @@ impl FromStr for AtIdentifier {
         if let Ok(did) = s.parse() {
             Ok(AtIdentifier::Did(did))
         } else {
-            s.parse().map(AtIdentifier::Handle)
+            s.parse().map(AtIdentifier::Handle).map_err(|_| "Invalid AT identifier")
         }
     }
 }
@@ -103,6 +109,28 @@ impl AsRef<str> for AtIdentifier {
     }
 }
 
+impl fmt::Display for AtIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+#[cfg(feature = "resolver")]
+impl AtIdentifier {
+    /// Resolves this identifier to a DID.
+    ///
+    /// A DID is returned as is, and a handle is resolved with the given handle resolver.
+    pub async fn resolve_did<R>(&self, resolver: &R) -> Result<Did, R::Error>
+    where
+        R: atrium_common::resolver::Resolver<Input = Handle, Output = Did>,
+    {
+        match self {
+            AtIdentifier::Did(did) => Ok(did.clone()),
+            AtIdentifier::Handle(handle) => resolver.resolve(handle).await,
+        }
+    }
+}
+
 /// A [CID in string format].
 ///
 /// [CID in string format]: https://atproto.com/specs/data-model#link-and-cid-formats
@@ -213,10 +241,12 @@ impl FromStr for Datetime {
         // datetimes to the subset that is also valid under ISO 8601. Apply a regex that
         // validates enough of the relevant ISO 8601 format that the RFC 3339 parser can
         // do the rest.
+        // The negative zero offset `-00:00` is valid in RFC 3339 but not in ISO 8601.
         static RE_ISO_8601: OnceLock<Regex> = OnceLock::new();
         if RE_ISO_8601
-            .get_or_init(|| Regex::new(r"^[0-9]{4}-[0-9]{2}-[0-9]{2}T[0-9]{2}:[0-9]{2}:[0-9]{2}(\.[0-9]+)?(Z|(\+[0-9]{2}|\-[0-9][1-9]):[0-9]{2})$").unwrap())
+            .get_or_init(|| Regex::new(r"^[0-9]{4}-[0-9]{2}-[0-9]{2}T[0-9]{2}:[0-9]{2}:[0-9]{2}(\.[0-9]+)?(Z|[+-][0-9]{2}:[0-9]{2})$").unwrap())
             .is_match(s)
+            && !s.ends_with("-00:00")
         {
             let dt = chrono::DateTime::parse_from_rfc3339(s)?;
             Ok(Self {
@@ -299,31 +329,77 @@ impl Did {
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Hash)]
 #[serde(transparent)]
 pub struct Handle(String);
-string_newtype!(Handle);
+string_newtype!(Handle, HandleError);
 
 impl Handle {
-    #[allow(clippy::borrow_interior_mutable_const, clippy::declare_interior_mutable_const)]
     /// Parses a `Handle` from the given string.
-    pub fn new(handle: String) -> Result<Self, &'static str> {
-        static RE_HANDLE: OnceLock<Regex> = OnceLock::new();
+    pub fn new(handle: String) -> Result<Self, HandleError> {
+        Self::validate(&handle)?;
+        Ok(Self(handle))
+    }
 
-        // https://atproto.com/specs/handle#handle-identifier-syntax
-        if handle.len() > 253 {
-            Err("Handle too long")
-        } else if !RE_HANDLE
-            .get_or_init(|| Regex::new(r"^([a-zA-Z0-9]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?\.)+[a-zA-Z]([a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?$").unwrap())
-            .is_match(&handle)
-        {
-            Err("Invalid handle")
-        } else {
-            Ok(Self(handle))
-        }
+    /// Creates a `Handle` from the given string without validating it.
+    ///
+    /// This is intended for trusted inputs, such as handles that have already been validated
+    /// elsewhere. Passing an invalid handle will not cause undefined behavior, but may cause
+    /// errors when the handle is used.
+    pub fn new_unchecked(handle: String) -> Self {
+        Self(handle)
     }
 
     /// Returns the handle as a string slice.
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    // https://atproto.com/specs/handle#handle-identifier-syntax
+    fn validate(handle: &str) -> Result<(), HandleError> {
+        if handle.len() > 253 {
+            return Err(HandleError::TooLong);
+        }
+        let segments = handle.split('.').collect::<Vec<_>>();
+        if segments.len() < 2 {
+            return Err(HandleError::TooFewSegments);
+        }
+        for segment in &segments {
+            if segment.is_empty() {
+                return Err(HandleError::EmptySegment);
+            }
+            if segment.len() > 63 {
+                return Err(HandleError::SegmentTooLong);
+            }
+            if !segment.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+                return Err(HandleError::InvalidCharacter);
+            }
+            if segment.starts_with('-') || segment.ends_with('-') {
+                return Err(HandleError::InvalidHyphen);
+            }
+        }
+        // This also rules out IP addresses.
+        if !segments[segments.len() - 1].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return Err(HandleError::InvalidTld);
+        }
+        Ok(())
+    }
+}
+
+/// The reason why a string is not a valid [`Handle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum HandleError {
+    #[error("handle is longer than 253 characters")]
+    TooLong,
+    #[error("handle must have at least two segments")]
+    TooFewSegments,
+    #[error("handle has an empty segment")]
+    EmptySegment,
+    #[error("handle segment is longer than 63 characters")]
+    SegmentTooLong,
+    #[error("handle contains a character other than ASCII letters, digits and hyphens")]
+    InvalidCharacter,
+    #[error("handle segment starts or ends with a hyphen")]
+    InvalidHyphen,
+    #[error("last segment of handle does not start with an ASCII letter")]
+    InvalidTld,
 }
 
 /// A [Namespaced Identifier].
@@ -436,12 +512,78 @@ impl Tid {
         }
     }
 
+    /// Returns a new TID for the current time.
+    ///
+    /// The clock identifier is chosen randomly once per process, and TIDs returned
+    /// within the same process are strictly increasing, even if the system clock goes backwards.
+    pub fn now() -> Self {
+        static LAST_TIMESTAMP: AtomicU64 = AtomicU64::new(0);
+        static CLOCK_ID: OnceLock<u64> = OnceLock::new();
+
+        let now = u64::try_from(chrono::Utc::now().timestamp_micros()).unwrap_or_default();
+        let mut last = LAST_TIMESTAMP.load(Ordering::Relaxed);
+        let timestamp = loop {
+            let timestamp = cmp::max(now, last + 1);
+            match LAST_TIMESTAMP.compare_exchange_weak(
+                last,
+                timestamp,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break timestamp,
+                Err(current) => last = current,
+            }
+        };
+        let clock_id = *CLOCK_ID.get_or_init(|| {
+            let mut bytes = [0; 8];
+            match getrandom::getrandom(&mut bytes) {
+                Ok(()) => u64::from_le_bytes(bytes),
+                // The seed of `RandomState` is not random on every platform (e.g. wasm32), but it
+                // is the best effort without an entropy source.
+                Err(_) => RandomState::new().build_hasher().finish(),
+            }
+        });
+        Self::from_parts(timestamp, clock_id)
+    }
+
+    /// Constructs a TID from a timestamp in microseconds since the UNIX epoch and a clock identifier.
+    ///
+    /// Only the lower 53 bits of the timestamp and the lower 10 bits of the clock identifier are used.
+    pub fn from_parts(timestamp: u64, clock_id: u64) -> Self {
+        const S32_CHAR: &[u8; 32] = b"234567abcdefghijklmnopqrstuvwxyz";
+
+        let value = ((timestamp & ((1 << 53) - 1)) << 10) | (clock_id & ((1 << 10) - 1));
+        Self(
+            (0..13)
+                .rev()
+                .map(|i| char::from(S32_CHAR[((value >> (i * 5)) & 0x1f) as usize]))
+                .collect(),
+        )
+    }
+
+    /// Returns the timestamp of the TID in microseconds since the UNIX epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.0.bytes().fold(0, |value, c| {
+            let digit = match c {
+                b'2'..=b'7' => c - b'2',
+                _ => c - b'a' + 6,
+            };
+            (value << 5) | u64::from(digit)
+        }) >> 10
+    }
+
     /// Returns the TID as a string slice.
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
 }
 
+impl From<Tid> for RecordKey {
+    fn from(tid: Tid) -> Self {
+        Self(tid.0)
+    }
+}
+
 /// A record key (`rkey`) used to name and reference an individual record within the same
 /// collection of an atproto repository.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Hash)]
@@ -472,6 +614,177 @@ impl RecordKey {
     }
 }
 
+/// An [AT URI].
+///
+/// Consists of an authority (a [`Did`] or a [`Handle`]), and an optional collection [`Nsid`],
+/// [`RecordKey`] and fragment.
+///
+/// [AT URI]: https://atproto.com/specs/at-uri-scheme
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AtUri {
+    authority: AtIdentifier,
+    collection: Option<Nsid>,
+    rkey: Option<RecordKey>,
+    fragment: Option<String>,
+}
+
+impl AtUri {
+    /// Parses an `AtUri` from the given string.
+    pub fn new(uri: String) -> Result<Self, &'static str> {
+        // https://atproto.com/specs/at-uri-scheme#restricted-at-uri-syntax
+        if uri.len() > 8 * 1024 {
+            return Err("AT URI too long");
+        }
+        let rest = uri.strip_prefix("at://").ok_or("AT URI must start with \"at://\"")?;
+        let (path, fragment) = match rest.split_once('#') {
+            Some((_, "")) => return Err("Empty AT URI fragment"),
+            Some((path, fragment)) => (path, Some(fragment.to_string())),
+            None => (rest, None),
+        };
+        let mut segments = path.split('/');
+        let authority = segments
+            .next()
+            .filter(|s| !s.is_empty())
+            .ok_or("Missing AT URI authority")?
+            .parse::<AtIdentifier>()
+            .map_err(|_| "Invalid AT URI authority")?;
+        let collection = segments
+            .next()
+            .map(|s| s.parse::<Nsid>().map_err(|_| "Invalid AT URI collection"))
+            .transpose()?;
+        let rkey = segments
+            .next()
+            .map(|s| s.parse::<RecordKey>().map_err(|_| "Invalid AT URI record key"))
+            .transpose()?;
+        if segments.next().is_some() {
+            return Err("Too many AT URI path segments");
+        }
+        Ok(Self { authority, collection, rkey, fragment })
+    }
+
+    /// Returns a new [`AtUriBuilder`] with the given authority.
+    pub fn builder(authority: impl Into<AtIdentifier>) -> AtUriBuilder {
+        AtUriBuilder::new(authority)
+    }
+
+    /// Returns the authority (a DID or a handle) of the AT URI.
+    pub fn authority(&self) -> &AtIdentifier {
+        &self.authority
+    }
+
+    /// Returns the collection NSID of the AT URI, if any.
+    pub fn collection(&self) -> Option<&Nsid> {
+        self.collection.as_ref()
+    }
+
+    /// Returns the record key of the AT URI, if any.
+    pub fn rkey(&self) -> Option<&RecordKey> {
+        self.rkey.as_ref()
+    }
+
+    /// Returns the fragment of the AT URI (without the leading `#`), if any.
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+}
+
+impl FromStr for AtUri {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s.into())
+    }
+}
+
+impl fmt::Display for AtUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at://{}", self.authority.as_ref())?;
+        if let Some(collection) = &self.collection {
+            write!(f, "/{}", collection.as_str())?;
+            if let Some(rkey) = &self.rkey {
+                write!(f, "/{}", rkey.as_str())?;
+            }
+        }
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{fragment}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'de> Deserialize<'de> for AtUri {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Deserialize::deserialize(deserializer)?;
+        Self::new(value).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for AtUri {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl From<AtUri> for String {
+    fn from(value: AtUri) -> Self {
+        value.to_string()
+    }
+}
+
+/// A builder for [`AtUri`].
+#[derive(Clone, Debug)]
+pub struct AtUriBuilder {
+    authority: AtIdentifier,
+    collection: Option<Nsid>,
+    rkey: Option<RecordKey>,
+    fragment: Option<String>,
+}
+
+impl AtUriBuilder {
+    /// Create a new builder with the given authority.
+    pub fn new(authority: impl Into<AtIdentifier>) -> Self {
+        Self { authority: authority.into(), collection: None, rkey: None, fragment: None }
+    }
+    /// Set the collection NSID.
+    pub fn collection(mut self, collection: Nsid) -> Self {
+        self.collection = Some(collection);
+        self
+    }
+    /// Set the record key.
+    pub fn rkey(mut self, rkey: RecordKey) -> Self {
+        self.rkey = Some(rkey);
+        self
+    }
+    /// Set the fragment (without the leading `#`).
+    pub fn fragment(mut self, fragment: impl Into<String>) -> Self {
+        self.fragment = Some(fragment.into());
+        self
+    }
+    /// Build the [`AtUri`].
+    ///
+    /// Returns an error if a record key is set without a collection, or if the fragment is empty.
+    pub fn build(self) -> Result<AtUri, &'static str> {
+        if self.rkey.is_some() && self.collection.is_none() {
+            return Err("AT URI record key requires a collection");
+        }
+        if self.fragment.as_deref() == Some("") {
+            return Err("Empty AT URI fragment");
+        }
+        Ok(AtUri {
+            authority: self.authority,
+            collection: self.collection,
+            rkey: self.rkey,
+            fragment: self.fragment,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::{from_str, to_string};
@@ -493,6 +806,8 @@ mod tests {
             "1985-04-12T23:20:50.0Z",
             "1985-04-12T23:20:50.123+00:00",
             "1985-04-12T23:20:50.123-07:00",
+            "1985-04-12T23:20:50.123-10:00",
+            "1985-04-12T23:20:50.123+05:30",
         ] {
             let json_valid = format!("\"{}\"", valid);
             let res = from_str::<Datetime>(&json_valid);
@@ -544,6 +859,30 @@ mod tests {
         assert_eq!(from_str::<Datetime>(&encoded).unwrap(), dt);
     }
 
+    #[test]
+    fn datetime_preserves_original_form() {
+        // `Z` and `+00:00` are the same instant, but each keeps its own serialized form.
+        let z = "1985-04-12T23:20:50.123Z".parse::<Datetime>().unwrap();
+        let utc = "1985-04-12T23:20:50.123+00:00".parse::<Datetime>().unwrap();
+        assert_eq!(z, utc);
+        assert_eq!(z.as_str(), "1985-04-12T23:20:50.123Z");
+        assert_eq!(utc.as_str(), "1985-04-12T23:20:50.123+00:00");
+        // Fractional seconds are not normalized, even beyond nanosecond precision.
+        for s in [
+            "1985-04-12T23:20:50.1Z",
+            "1985-04-12T23:20:50.100Z",
+            "1985-04-12T23:20:50.12345678912345Z",
+        ] {
+            let dt = s.parse::<Datetime>().unwrap();
+            assert_eq!(dt.as_str(), s);
+            assert_eq!(to_string(&dt).unwrap(), format!("\"{s}\""));
+        }
+        assert_eq!(
+            "1985-04-12T23:20:50.1Z".parse::<Datetime>().unwrap(),
+            "1985-04-12T23:20:50.100Z".parse::<Datetime>().unwrap()
+        );
+    }
+
     #[test]
     fn valid_did() {
         // From https://atproto.com/specs/did#examples
@@ -648,6 +987,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn handle_error() {
+        for (invalid, error) in [
+            ("a".repeat(250) + ".test", HandleError::TooLong),
+            (String::from("org"), HandleError::TooFewSegments),
+            (String::from(".name.org"), HandleError::EmptySegment),
+            (String::from("name.org."), HandleError::EmptySegment),
+            ("a".repeat(64) + ".test", HandleError::SegmentTooLong),
+            (String::from("jo@hn.test"), HandleError::InvalidCharacter),
+            (String::from("-john.test"), HandleError::InvalidHyphen),
+            (String::from("john.0"), HandleError::InvalidTld),
+            (String::from("127.0.0.1"), HandleError::InvalidTld),
+            (String::from("https://bsky.social"), HandleError::InvalidCharacter),
+        ] {
+            assert_eq!(Handle::new(invalid), Err(error));
+        }
+        assert_eq!(Handle::new_unchecked(String::from("org")).as_str(), "org");
+    }
+
+    #[test]
+    fn at_identifier() {
+        let did = "did:plc:ewvi7nxzyoun6zhxrhs64oiz".parse::<AtIdentifier>().unwrap();
+        assert!(matches!(did, AtIdentifier::Did(_)));
+        assert_eq!(did.to_string(), "did:plc:ewvi7nxzyoun6zhxrhs64oiz");
+        let handle = "alice.test".parse::<AtIdentifier>().unwrap();
+        assert!(matches!(handle, AtIdentifier::Handle(_)));
+        assert_eq!(handle.to_string(), "alice.test");
+        assert!("https://bsky.social".parse::<AtIdentifier>().is_err());
+    }
+
+    #[cfg(feature = "resolver")]
+    #[tokio::test]
+    async fn at_identifier_resolve_did() {
+        struct MockResolver;
+
+        impl atrium_common::resolver::Resolver for MockResolver {
+            type Input = Handle;
+            type Output = Did;
+            type Error = &'static str;
+
+            async fn resolve(&self, handle: &Handle) -> Result<Did, Self::Error> {
+                match handle.as_str() {
+                    "alice.test" => Did::new("did:plc:alice".into()),
+                    _ => Err("unknown handle"),
+                }
+            }
+        }
+
+        let did = Did::new("did:plc:bob".into()).unwrap();
+        assert_eq!(AtIdentifier::Did(did.clone()).resolve_did(&MockResolver).await, Ok(did));
+        assert_eq!(
+            "alice.test".parse::<AtIdentifier>().unwrap().resolve_did(&MockResolver).await,
+            Ok(Did::new("did:plc:alice".into()).unwrap())
+        );
+        assert_eq!(
+            "bob.test".parse::<AtIdentifier>().unwrap().resolve_did(&MockResolver).await,
+            Err("unknown handle")
+        );
+    }
+
     #[test]
     fn valid_nsid() {
         // From https://atproto.com/specs/nsid#examples
@@ -800,6 +1199,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tid_now() {
+        let tids = (0..1000).map(|_| Tid::now()).collect::<Vec<_>>();
+        for pair in tids.windows(2) {
+            assert!(pair[0].as_str() < pair[1].as_str(), "{pair:?} are not sorted");
+            assert!(pair[0].timestamp() < pair[1].timestamp());
+        }
+        for tid in tids {
+            assert!(Tid::new(tid.as_str().into()).is_ok(), "generated TID {tid:?} is invalid");
+            assert!(
+                RecordKey::new(tid.as_str().into()).is_ok(),
+                "generated TID {tid:?} is invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn tid_from_parts() {
+        let tid = Tid::from_parts(1_700_000_000_000_000, 42);
+        assert_eq!(tid.as_str(), "3ke6kg3wk223e");
+        assert_eq!(tid.timestamp(), 1_700_000_000_000_000);
+        assert_eq!(Tid::from_parts(0, 0).as_str(), "2222222222222");
+        assert!(Tid::new(Tid::from_parts(u64::MAX, u64::MAX).as_str().into()).is_ok());
+    }
+
     #[test]
     fn valid_rkey() {
         // From https://atproto.com/specs/record-key#examples
@@ -844,4 +1268,86 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn valid_at_uri() {
+        // From https://atproto.com/specs/at-uri-scheme#examples
+        for (valid, authority, collection, rkey, fragment) in [
+            ("at://foo.com", "foo.com", None, None, None),
+            (
+                "at://did:plc:z72i7hdynmk6r22z27h6tvur",
+                "did:plc:z72i7hdynmk6r22z27h6tvur",
+                None,
+                None,
+                None,
+            ),
+            (
+                "at://did:web:example.com/app.bsky.feed.post",
+                "did:web:example.com",
+                Some("app.bsky.feed.post"),
+                None,
+                None,
+            ),
+            (
+                "at://did:plc:z72i7hdynmk6r22z27h6tvur/app.bsky.feed.post/3jwdwj2ctlk26",
+                "did:plc:z72i7hdynmk6r22z27h6tvur",
+                Some("app.bsky.feed.post"),
+                Some("3jwdwj2ctlk26"),
+                None,
+            ),
+            (
+                "at://bob.test/app.bsky.actor.profile/self#/displayName",
+                "bob.test",
+                Some("app.bsky.actor.profile"),
+                Some("self"),
+                Some("/displayName"),
+            ),
+        ] {
+            let uri = from_str::<AtUri>(&format!("\"{}\"", valid))
+                .unwrap_or_else(|e| panic!("valid AT URI `{valid}` parsed as invalid: {e}"));
+            assert_eq!(uri.authority().as_ref(), authority);
+            assert_eq!(uri.collection().map(Nsid::as_str), collection);
+            assert_eq!(uri.rkey().map(RecordKey::as_str), rkey);
+            assert_eq!(uri.fragment(), fragment);
+            assert_eq!(uri.to_string(), valid);
+            assert_eq!(to_string(&uri).unwrap(), format!("\"{}\"", valid));
+        }
+    }
+
+    #[test]
+    fn invalid_at_uri() {
+        for invalid in [
+            "foo.com",
+            "https://foo.com",
+            "at://",
+            "at://foo",
+            "at://foo.com/",
+            "at://foo.com/app.bsky.feed.post/",
+            "at://foo.com/app.bsky.feed.post/3jwdwj2ctlk26/extra",
+            "at://foo.com/app/3jwdwj2ctlk26",
+            "at://foo.com/app.bsky.feed.post/any space",
+            "at://foo.com#",
+        ] {
+            assert!(
+                from_str::<AtUri>(&format!("\"{}\"", invalid)).is_err(),
+                "invalid AT URI `{}` parsed as valid",
+                invalid,
+            );
+        }
+    }
+
+    #[test]
+    fn build_at_uri() {
+        let did = Did::new("did:web:example.com".into()).unwrap();
+        let uri = AtUri::builder(did.clone())
+            .collection(Nsid::new("app.bsky.feed.post".into()).unwrap())
+            .rkey(RecordKey::new("3jwdwj2ctlk26".into()).unwrap())
+            .build()
+            .expect("failed to build AT URI");
+        assert_eq!(uri.to_string(), "at://did:web:example.com/app.bsky.feed.post/3jwdwj2ctlk26");
+        assert!(AtUri::builder(did)
+            .rkey(RecordKey::new("3jwdwj2ctlk26".into()).unwrap())
+            .build()
+            .is_err());
+    }
 }
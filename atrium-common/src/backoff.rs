@@ -0,0 +1,131 @@
+//! Exponential backoff with jitter, for retrying requests and reconnecting streams.
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::time::Duration;
+
+/// An exponential backoff policy with jitter.
+///
+/// The `n`-th delay (starting from `0`) is `min * factor^n`, capped at `max`,
+/// then reduced by a random fraction of at most `jitter`.
+/// So every delay is within `[base * (1 - jitter), base]`, and never exceeds `max`.
+///
+/// # Example
+///
+/// ```
+/// use atrium_common::backoff::Backoff;
+/// use std::time::Duration;
+///
+/// let mut backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(10)).jitter(0.0);
+/// assert_eq!(backoff.next_delay(), Duration::from_millis(100));
+/// assert_eq!(backoff.next_delay(), Duration::from_millis(200));
+/// backoff.reset();
+/// assert_eq!(backoff.next_delay(), Duration::from_millis(100));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Backoff<R = SmallRng> {
+    min: Duration,
+    max: Duration,
+    factor: f64,
+    jitter: f64,
+    attempt: u32,
+    rng: R,
+}
+
+impl Backoff {
+    /// Create a new policy with a factor of `2` and a jitter of `0.5`.
+    pub fn new(min: Duration, max: Duration) -> Self {
+        Self { min, max, factor: 2.0, jitter: 0.5, attempt: 0, rng: SmallRng::from_entropy() }
+    }
+}
+
+impl<R> Backoff<R>
+where
+    R: RngCore,
+{
+    /// Set the multiplier applied for each attempt.
+    pub fn factor(mut self, factor: f64) -> Self {
+        self.factor = factor.max(1.0);
+        self
+    }
+    /// Set the maximum fraction of the delay which is randomly subtracted, clamped to `[0, 1]`.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+    /// Use the given random number generator for jitter, e.g. a seeded one in tests.
+    pub fn with_rng<R0>(self, rng: R0) -> Backoff<R0>
+    where
+        R0: RngCore,
+    {
+        Backoff {
+            min: self.min,
+            max: self.max,
+            factor: self.factor,
+            jitter: self.jitter,
+            attempt: self.attempt,
+            rng,
+        }
+    }
+    /// Returns the number of delays returned since the last reset.
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+    /// Returns the delay before the next attempt.
+    pub fn next_delay(&mut self) -> Duration {
+        let base =
+            self.min.as_secs_f64() * self.factor.powi(self.attempt.min(i32::MAX as u32) as i32);
+        let base = base.min(self.max.as_secs_f64());
+        self.attempt = self.attempt.saturating_add(1);
+        let jitter = if self.jitter > 0.0 { self.rng.gen_range(0.0..=self.jitter) } else { 0.0 };
+        Duration::from_secs_f64(base * (1.0 - jitter)).min(self.max)
+    }
+    /// Start over from the minimum delay, e.g. after a successful attempt.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backoff() -> Backoff<SmallRng> {
+        Backoff::new(Duration::from_millis(100), Duration::from_secs(5))
+            .with_rng(SmallRng::seed_from_u64(42))
+    }
+
+    #[test]
+    fn delays_without_jitter() {
+        let mut backoff = backoff().jitter(0.0).factor(3.0);
+        let delays = (0..6).map(|_| backoff.next_delay()).collect::<Vec<_>>();
+        assert_eq!(delays, [100, 300, 900, 2700, 5000, 5000].map(Duration::from_millis).to_vec());
+        assert_eq!(backoff.attempt(), 6);
+        backoff.reset();
+        assert_eq!(backoff.attempt(), 0);
+        assert_eq!(backoff.next_delay(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn delays_within_bounds() {
+        let mut backoff = backoff().jitter(0.25);
+        for attempt in 0..100 {
+            let base = (0.1 * 2f64.powi(attempt)).min(5.0);
+            let delay = backoff.next_delay();
+            assert!(delay <= Duration::from_secs(5), "{delay:?} exceeds max");
+            assert!(
+                delay >= Duration::from_secs_f64(base * 0.75),
+                "{delay:?} is below the jitter bound of attempt {attempt}"
+            );
+            assert!(delay <= Duration::from_secs_f64(base), "{delay:?} is above attempt {attempt}");
+        }
+    }
+
+    #[test]
+    fn deterministic_with_seeded_rng() {
+        let mut a = backoff();
+        let mut b = backoff();
+        for _ in 0..10 {
+            assert_eq!(a.next_delay(), b.next_delay());
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod backoff;
 pub mod resolver;
 pub mod store;
 pub mod types;
@@ -1,11 +1,17 @@
 mod common_resolver;
+#[cfg(feature = "plc-audit-log")]
+mod plc_audit_log;
 mod plc_resolver;
+mod static_resolver;
 mod web_resolver;
 
 use crate::Error;
 
 pub use self::common_resolver::{CommonDidResolver, CommonDidResolverConfig};
+#[cfg(feature = "plc-audit-log")]
+pub use self::plc_audit_log::{PlcAuditLogEntry, PlcOperation, PlcService};
 pub use self::plc_resolver::DEFAULT_PLC_DIRECTORY_URL;
+pub use self::static_resolver::StaticDidResolver;
 use atrium_api::did_doc::DidDocument;
 use atrium_api::types::string::Did;
 use atrium_common::resolver::Resolver;
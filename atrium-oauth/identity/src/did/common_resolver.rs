@@ -3,6 +3,8 @@ use atrium_api::types::string::Did;
 use atrium_common::resolver::Resolver;
 use atrium_xrpc::HttpClient;
 
+#[cfg(feature = "plc-audit-log")]
+use super::plc_audit_log::PlcAuditLogEntry;
 use super::plc_resolver::{PlcDidResolver, PlcDidResolverConfig};
 use super::web_resolver::{WebDidResolver, WebDidResolverConfig};
 use super::DidResolver;
@@ -34,6 +36,25 @@ impl<T> CommonDidResolver<T> {
     }
 }
 
+#[cfg(feature = "plc-audit-log")]
+impl<T> CommonDidResolver<T>
+where
+    T: HttpClient + Send + Sync + 'static,
+{
+    /// Fetch the audit log of a `did:plc` from the PLC directory, and verify its operation chain.
+    ///
+    /// Returns the entries of the verified chain, without nullified ones. If the chain is broken,
+    /// [`Error::PlcAuditLog`] tells which operation is invalid.
+    ///
+    /// This requires the `plc-audit-log` feature.
+    pub async fn audit_log(&self, did: &Did) -> Result<Vec<PlcAuditLogEntry>> {
+        match did.strip_prefix("did:").and_then(|s| s.split_once(':').map(|(method, _)| method)) {
+            Some("plc") => self.plc_resolver.audit_log(did).await,
+            _ => Err(Error::UnsupportedDidMethod(did.clone())),
+        }
+    }
+}
+
 impl<T> Resolver for CommonDidResolver<T>
 where
     PlcDidResolver<T>: DidResolver + Send + Sync + 'static,
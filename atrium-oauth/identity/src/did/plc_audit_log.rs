@@ -0,0 +1,305 @@
+use crate::error::{Error, Result};
+use atrium_api::types::string::{Datetime, Did};
+use atrium_crypto::multibase::Base;
+use atrium_crypto::verify::verify_signature;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ipld_core::cid::multihash::Multihash;
+use ipld_core::cid::Cid;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Multicodec code of DAG-CBOR.
+const DAG_CBOR: u64 = 0x71;
+/// Multicodec code of SHA2-256.
+const SHA2_256: u64 = 0x12;
+/// Length of the base32-encoded hash in a `did:plc` identifier.
+const DID_PLC_HASH_LEN: usize = 24;
+
+/// An entry of the audit log of a `did:plc`, as returned by `/<did>/log/audit` of the PLC directory.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlcAuditLogEntry {
+    pub did: Did,
+    pub operation: PlcOperation,
+    pub cid: String,
+    pub nullified: bool,
+    pub created_at: Datetime,
+}
+
+/// A signed operation of a `did:plc`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum PlcOperation {
+    #[serde(rename = "plc_operation", rename_all = "camelCase")]
+    Operation {
+        rotation_keys: Vec<String>,
+        verification_methods: BTreeMap<String, String>,
+        also_known_as: Vec<String>,
+        services: BTreeMap<String, PlcService>,
+        prev: Option<String>,
+        sig: String,
+    },
+    #[serde(rename = "plc_tombstone")]
+    Tombstone { prev: String, sig: String },
+    /// The legacy format of genesis operations.
+    #[serde(rename = "create", rename_all = "camelCase")]
+    Create {
+        signing_key: String,
+        recovery_key: String,
+        handle: String,
+        service: String,
+        prev: Option<String>,
+        sig: String,
+    },
+}
+
+impl PlcOperation {
+    /// The CID of the previous operation, or `None` for a genesis operation.
+    pub fn prev(&self) -> Option<&str> {
+        match self {
+            Self::Operation { prev, .. } | Self::Create { prev, .. } => prev.as_deref(),
+            Self::Tombstone { prev, .. } => Some(prev),
+        }
+    }
+    /// The `did:key`s allowed to sign the next operation.
+    pub fn rotation_keys(&self) -> Vec<String> {
+        match self {
+            Self::Operation { rotation_keys, .. } => rotation_keys.clone(),
+            Self::Tombstone { .. } => Vec::new(),
+            Self::Create { signing_key, recovery_key, .. } => {
+                vec![recovery_key.clone(), signing_key.clone()]
+            }
+        }
+    }
+    fn sig(&self) -> &str {
+        match self {
+            Self::Operation { sig, .. }
+            | Self::Tombstone { sig, .. }
+            | Self::Create { sig, .. } => sig,
+        }
+    }
+    fn unsigned_bytes(&self) -> core::result::Result<Vec<u8>, String> {
+        let mut value = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        if let Some(object) = value.as_object_mut() {
+            object.remove("sig");
+        }
+        serde_ipld_dagcbor::to_vec(&value).map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PlcService {
+    pub r#type: String,
+    pub endpoint: String,
+}
+
+/// Verify the operation chain of an audit log, and return the entries of the chain.
+///
+/// Nullified entries are not part of the chain, and are skipped. The genesis operation must be
+/// signed by one of its own rotation keys and hash to the DID, and every following operation must
+/// reference the previous one and be signed by one of its rotation keys.
+pub(crate) fn verify_audit_log(
+    did: &Did,
+    entries: Vec<PlcAuditLogEntry>,
+) -> Result<Vec<PlcAuditLogEntry>> {
+    let mut chain = Vec::<PlcAuditLogEntry>::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        if entry.nullified {
+            continue;
+        }
+        let invalid = |reason: String| Error::PlcAuditLog { index, cid: entry.cid.clone(), reason };
+        if entry.did != *did {
+            return Err(invalid(format!("operation of another did `{}`", entry.did.as_str())));
+        }
+        let signed =
+            serde_ipld_dagcbor::to_vec(&entry.operation).map_err(|e| invalid(e.to_string()))?;
+        let hash = Sha256::digest(&signed);
+        let cid = Multihash::<64>::wrap(SHA2_256, &hash)
+            .map(|multihash| Cid::new_v1(DAG_CBOR, multihash).to_string())
+            .map_err(|e| invalid(e.to_string()))?;
+        if cid != entry.cid {
+            return Err(invalid(format!("cid of the operation is `{cid}`")));
+        }
+        if entry.operation.prev() != chain.last().map(|prev| prev.cid.as_str()) {
+            return Err(invalid(String::from("prev does not reference the previous operation")));
+        }
+        let rotation_keys = match chain.last() {
+            Some(prev) => prev.operation.rotation_keys(),
+            None => {
+                let encoded = Base::Base32Lower.encode(hash);
+                if did.as_str() != format!("did:plc:{}", &encoded[..DID_PLC_HASH_LEN]) {
+                    return Err(invalid(String::from("genesis operation does not match the did")));
+                }
+                entry.operation.rotation_keys()
+            }
+        };
+        let unsigned = entry.operation.unsigned_bytes().map_err(invalid)?;
+        let sig =
+            URL_SAFE_NO_PAD.decode(entry.operation.sig()).map_err(|e| invalid(e.to_string()))?;
+        if !rotation_keys.iter().any(|key| verify_signature(key, &unsigned, &sig).is_ok()) {
+            return Err(invalid(String::from("not signed by any of the rotation keys")));
+        }
+        chain.push(entry);
+    }
+    if chain.is_empty() {
+        return Err(Error::NotFound);
+    }
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atrium_crypto::keypair::{Did as _, Secp256k1Keypair};
+
+    fn sign(keypair: &Secp256k1Keypair, mut operation: PlcOperation) -> PlcOperation {
+        let sig = URL_SAFE_NO_PAD.encode(
+            keypair
+                .sign(&operation.unsigned_bytes().expect("operation should be encoded"))
+                .expect("signing should succeed"),
+        );
+        match &mut operation {
+            PlcOperation::Operation { sig: s, .. }
+            | PlcOperation::Tombstone { sig: s, .. }
+            | PlcOperation::Create { sig: s, .. } => *s = sig,
+        }
+        operation
+    }
+
+    fn cid(operation: &PlcOperation) -> String {
+        let bytes = serde_ipld_dagcbor::to_vec(operation).expect("operation should be encoded");
+        Cid::new_v1(
+            DAG_CBOR,
+            Multihash::<64>::wrap(SHA2_256, &Sha256::digest(bytes))
+                .expect("hash should be wrapped"),
+        )
+        .to_string()
+    }
+
+    fn update(
+        rotation_keys: &[&Secp256k1Keypair],
+        handle: &str,
+        prev: Option<String>,
+    ) -> PlcOperation {
+        PlcOperation::Operation {
+            rotation_keys: rotation_keys.iter().map(|keypair| keypair.did()).collect(),
+            verification_methods: BTreeMap::from([(
+                String::from("atproto"),
+                rotation_keys[0].did(),
+            )]),
+            also_known_as: vec![format!("at://{handle}")],
+            services: BTreeMap::from([(
+                String::from("atproto_pds"),
+                PlcService {
+                    r#type: String::from("AtprotoPersonalDataServer"),
+                    endpoint: String::from("https://pds.test"),
+                },
+            )]),
+            prev,
+            sig: String::new(),
+        }
+    }
+
+    /// Build a valid audit log of three operations, the last one signed by a rotated key.
+    fn audit_log() -> (Did, Vec<PlcAuditLogEntry>) {
+        let mut rng = rand::thread_rng();
+        let (first, second) =
+            (Secp256k1Keypair::create(&mut rng), Secp256k1Keypair::create(&mut rng));
+        let genesis = sign(&first, update(&[&first], "alice.test", None));
+        let hash = Sha256::digest(serde_ipld_dagcbor::to_vec(&genesis).expect("encode"));
+        let did = format!("did:plc:{}", &Base::Base32Lower.encode(hash)[..DID_PLC_HASH_LEN])
+            .parse::<Did>()
+            .expect("did should be valid");
+        let rotate = sign(&first, update(&[&second], "alice.test", Some(cid(&genesis))));
+        let rename = sign(&second, update(&[&second], "bob.test", Some(cid(&rotate))));
+        let entries = [genesis, rotate, rename]
+            .into_iter()
+            .map(|operation| PlcAuditLogEntry {
+                did: did.clone(),
+                cid: cid(&operation),
+                operation,
+                nullified: false,
+                created_at: Datetime::now(),
+            })
+            .collect();
+        (did, entries)
+    }
+
+    fn reason(result: Result<Vec<PlcAuditLogEntry>>) -> (usize, String) {
+        match result {
+            Err(Error::PlcAuditLog { index, reason, .. }) => (index, reason),
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn verify() {
+        let (did, entries) = audit_log();
+        assert_eq!(verify_audit_log(&did, entries.clone()).expect("should be verified"), entries);
+    }
+
+    #[test]
+    fn verify_nullified() {
+        let (did, mut entries) = audit_log();
+        // a forked operation signed by an unknown key, which has been nullified by a recovery
+        let keypair = Secp256k1Keypair::create(&mut rand::thread_rng());
+        let forked =
+            sign(&keypair, update(&[&keypair], "mallory.test", Some(entries[1].cid.clone())));
+        entries.insert(
+            2,
+            PlcAuditLogEntry {
+                did: did.clone(),
+                cid: cid(&forked),
+                operation: forked,
+                nullified: true,
+                created_at: Datetime::now(),
+            },
+        );
+        let chain = verify_audit_log(&did, entries.clone()).expect("should be verified");
+        assert_eq!(chain, [entries[0].clone(), entries[1].clone(), entries[3].clone()]);
+        entries[2].nullified = false;
+        assert_eq!(reason(verify_audit_log(&did, entries)).0, 2);
+    }
+
+    #[test]
+    fn verify_broken() {
+        let (did, entries) = audit_log();
+        // operation of another did
+        let other = "did:plc:aaaaaaaaaaaaaaaaaaaaaaaa".parse::<Did>().expect("did should be valid");
+        assert_eq!(
+            reason(verify_audit_log(&other, entries.clone())),
+            (0, format!("operation of another did `{}`", did.as_str()))
+        );
+        // genesis operation does not hash to the did
+        let mut forged = entries.clone();
+        forged.iter_mut().for_each(|entry| entry.did = other.clone());
+        assert_eq!(
+            reason(verify_audit_log(&other, forged)),
+            (0, String::from("genesis operation does not match the did"))
+        );
+        // tampered operation
+        let mut tampered = entries.clone();
+        if let PlcOperation::Operation { also_known_as, .. } = &mut tampered[2].operation {
+            also_known_as[0] = String::from("at://mallory.test");
+        }
+        assert!(reason(verify_audit_log(&did, tampered.clone()))
+            .1
+            .starts_with("cid of the operation"));
+        tampered[2].cid = cid(&tampered[2].operation);
+        assert_eq!(
+            reason(verify_audit_log(&did, tampered)),
+            (2, String::from("not signed by any of the rotation keys"))
+        );
+        // missing link
+        let mut missing = entries.clone();
+        missing.remove(1);
+        assert_eq!(
+            reason(verify_audit_log(&did, missing)),
+            (1, String::from("prev does not reference the previous operation"))
+        );
+        // empty log
+        assert!(matches!(verify_audit_log(&did, Vec::new()), Err(Error::NotFound)));
+    }
+}
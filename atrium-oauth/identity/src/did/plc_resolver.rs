@@ -1,3 +1,5 @@
+#[cfg(feature = "plc-audit-log")]
+use super::plc_audit_log::{verify_audit_log, PlcAuditLogEntry};
 use super::DidResolver;
 use crate::error::{Error, Result};
 use atrium_api::did_doc::DidDocument;
@@ -27,6 +29,28 @@ impl<T> PlcDidResolver<T> {
     }
 }
 
+#[cfg(feature = "plc-audit-log")]
+impl<T> PlcDidResolver<T>
+where
+    T: HttpClient + Send + Sync + 'static,
+{
+    pub async fn audit_log(&self, did: &Did) -> Result<Vec<PlcAuditLogEntry>> {
+        let uri = Builder::from(self.plc_directory_url.parse::<Uri>()?)
+            .path_and_query(format!("/{}/log/audit", did.as_str()))
+            .build()?;
+        let res = self
+            .http_client
+            .send_http(Request::builder().uri(uri).body(Vec::new())?)
+            .await
+            .map_err(Error::HttpClient)?;
+        if res.status().is_success() {
+            verify_audit_log(did, serde_json::from_slice(res.body())?)
+        } else {
+            Err(Error::HttpStatus(res.status()))
+        }
+    }
+}
+
 impl<T> Resolver for PlcDidResolver<T>
 where
     T: HttpClient + Send + Sync + 'static,
@@ -0,0 +1,61 @@
+use super::DidResolver;
+use crate::error::{Error, Result};
+use atrium_api::did_doc::DidDocument;
+use atrium_api::types::string::Did;
+use atrium_common::resolver::Resolver;
+use std::collections::HashMap;
+
+/// A [`DidResolver`] that resolves DIDs from a fixed set of documents, without any network access.
+///
+/// Resolving a DID that is not registered fails with [`Error::NotFound`].
+///
+/// # Example
+///
+/// ```
+/// use atrium_api::did_doc::DidDocument;
+/// use atrium_common::resolver::Resolver;
+/// use atrium_identity::did::StaticDidResolver;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let did = "did:web:example.com".parse()?;
+/// let document = DidDocument {
+///     context: None,
+///     id: String::from("did:web:example.com"),
+///     also_known_as: None,
+///     verification_method: None,
+///     service: None,
+/// };
+/// let resolver = StaticDidResolver::new().with_document(did, document.clone());
+/// assert_eq!(resolver.resolve(&"did:web:example.com".parse()?).await?, document);
+/// assert!(resolver.resolve(&"did:web:example.org".parse()?).await.is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StaticDidResolver {
+    documents: HashMap<Did, DidDocument>,
+}
+
+impl StaticDidResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Register the document for the given DID, replacing any existing one.
+    pub fn with_document(mut self, did: Did, document: DidDocument) -> Self {
+        self.documents.insert(did, document);
+        self
+    }
+}
+
+impl Resolver for StaticDidResolver {
+    type Input = Did;
+    type Output = DidDocument;
+    type Error = Error;
+
+    async fn resolve(&self, did: &Self::Input) -> Result<Self::Output> {
+        self.documents.get(did).cloned().ok_or(Error::NotFound)
+    }
+}
+
+impl DidResolver for StaticDidResolver {}
@@ -8,6 +8,8 @@ use atrium_xrpc::HttpClient;
 use std::sync::Arc;
 
 const DID_WEB_PREFIX: &str = "did:web:";
+const WELL_KNOWN_PATH: &str = "/.well-known";
+const DOCUMENT_PATH: &str = "/did.json";
 
 #[derive(Clone, Debug)]
 pub struct WebDidResolverConfig<T> {
@@ -33,13 +35,7 @@ where
     type Error = Error;
 
     async fn resolve(&self, did: &Self::Input) -> Result<Self::Output> {
-        let document_url = format!(
-            "https://{}/.well-known/did.json",
-            did.as_str()
-                .strip_prefix(DID_WEB_PREFIX)
-                .ok_or_else(|| Error::Did(did.as_str().to_string()))?
-        )
-        .parse::<Uri>()?;
+        let document_url = document_url(did)?.parse::<Uri>()?;
         let res = self
             .http_client
             .send_http(
@@ -59,3 +55,93 @@ where
 }
 
 impl<T> DidResolver for WebDidResolver<T> where T: HttpClient + Send + Sync + 'static {}
+
+/// Transform a `did:web` into the URL of its DID document, as defined in the
+/// [did:web spec](https://w3c-ccg.github.io/did-method-web/#read-resolve).
+///
+/// The first segment is the host, whose port is percent-encoded (e.g. `example.com%3A3000`).
+/// Without any other segments the document is at `/.well-known/did.json`, otherwise the segments
+/// (which are kept percent-encoded) are the path to `did.json`. As in the reference
+/// implementation, `localhost` is fetched over HTTP.
+fn document_url(did: &Did) -> Result<String> {
+    let invalid = || Error::Did(did.as_str().to_string());
+    let mut segments = did.as_str().strip_prefix(DID_WEB_PREFIX).ok_or_else(invalid)?.split(':');
+    let host = segments.next().and_then(percent_decode).ok_or_else(invalid)?;
+    let path = segments.flat_map(|segment| ["/", segment]).collect::<String>();
+    if host.is_empty() || host.contains('/') || path.contains("//") {
+        return Err(invalid());
+    }
+    let scheme = match host.split_once(':').map_or(host.as_str(), |(hostname, _)| hostname) {
+        "localhost" => "http",
+        _ => "https",
+    };
+    let path = if path.is_empty() { WELL_KNOWN_PATH } else { &path };
+    Ok(format!("{scheme}://{host}{path}{DOCUMENT_PATH}"))
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(did: &str) -> Result<String> {
+        document_url(&did.parse().expect("did should be valid"))
+    }
+
+    #[test]
+    fn document_url_host() {
+        assert_eq!(
+            url("did:web:example.com").expect("url should be valid"),
+            "https://example.com/.well-known/did.json"
+        );
+        assert_eq!(
+            url("did:web:example.com%3A3000").expect("url should be valid"),
+            "https://example.com:3000/.well-known/did.json"
+        );
+        assert_eq!(
+            url("did:web:localhost%3a2583").expect("url should be valid"),
+            "http://localhost:2583/.well-known/did.json"
+        );
+    }
+
+    #[test]
+    fn document_url_path() {
+        assert_eq!(
+            url("did:web:example.com:user:alice").expect("url should be valid"),
+            "https://example.com/user/alice/did.json"
+        );
+        assert_eq!(
+            url("did:web:example.com%3A3000:user:alice").expect("url should be valid"),
+            "https://example.com:3000/user/alice/did.json"
+        );
+        assert_eq!(
+            url("did:web:localhost%3A2583:users:bob%20smith").expect("url should be valid"),
+            "http://localhost:2583/users/bob%20smith/did.json"
+        );
+    }
+
+    #[test]
+    fn document_url_invalid() {
+        for did in [
+            "did:plc:example.com",
+            "did:web:example.com%2Fpath",
+            "did:web:example.com%3",
+            "did:web:example.com::alice",
+        ] {
+            assert!(matches!(url(did), Err(Error::Did(_))), "{did}");
+        }
+    }
+}
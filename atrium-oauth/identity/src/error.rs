@@ -1,4 +1,4 @@
-use atrium_api::types::string::Did;
+use atrium_api::types::string::{Did, Handle};
 use atrium_xrpc::http::uri::InvalidUri;
 use atrium_xrpc::http::StatusCode;
 use thiserror::Error;
@@ -13,12 +13,16 @@ pub enum Error {
     Did(String),
     #[error("invalid did document: {0}")]
     DidDocument(String),
+    #[error("did document for `{}` does not include the handle `{}`", .1.as_str(), .0.as_str())]
+    HandleMismatch(Handle, Did),
     #[error("protected resource metadata is invalid: {0}")]
     ProtectedResourceMetadata(String),
     #[error("authorization server metadata is invalid: {0}")]
     AuthorizationServerMetadata(String),
     #[error("dns resolver error: {0}")]
     DnsResolver(Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("invalid operation #{index} ({cid}) in plc audit log: {reason}")]
+    PlcAuditLog { index: usize, cid: String, reason: String },
     #[error("unsupported did method: {0:?}")]
     UnsupportedDidMethod(Did),
     #[error(transparent)]
@@ -3,6 +3,7 @@ mod atproto_resolver;
 mod dns_resolver;
 #[cfg(feature = "doh-handle-resolver")]
 mod doh_dns_txt_resolver;
+mod doh_txt_resolver;
 mod well_known_resolver;
 
 use crate::Error;
@@ -12,6 +13,9 @@ pub use self::atproto_resolver::{AtprotoHandleResolver, AtprotoHandleResolverCon
 pub use self::dns_resolver::DnsTxtResolver;
 #[cfg(feature = "doh-handle-resolver")]
 pub use self::doh_dns_txt_resolver::{DohDnsTxtResolver, DohDnsTxtResolverConfig};
+pub use self::doh_txt_resolver::{
+    DohTxtResolver, DohTxtResolverConfig, CLOUDFLARE_DOH_URL, GOOGLE_DOH_URL,
+};
 pub use self::well_known_resolver::{WellKnownHandleResolver, WellKnownHandleResolverConfig};
 use atrium_api::types::string::{Did, Handle};
 use atrium_common::resolver::Resolver;
@@ -0,0 +1,185 @@
+use super::DnsTxtResolver;
+use atrium_xrpc::http::header::ACCEPT;
+use atrium_xrpc::http::{Request, StatusCode};
+use atrium_xrpc::HttpClient;
+use serde::Deserialize;
+use std::sync::Arc;
+use thiserror::Error;
+
+const DNS_JSON_MEDIA_TYPE: &str = "application/dns-json";
+/// DNS RR type of TXT records.
+const TXT_TYPE: u16 = 16;
+/// DNS response code for a name that does not exist.
+const NXDOMAIN: u16 = 3;
+
+/// The JSON API endpoint of Cloudflare's DNS-over-HTTPS service.
+pub const CLOUDFLARE_DOH_URL: &str = "https://cloudflare-dns.com/dns-query";
+/// The JSON API endpoint of Google's DNS-over-HTTPS service.
+pub const GOOGLE_DOH_URL: &str = "https://dns.google/resolve";
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("http status: {0:?}")]
+    HttpStatus(StatusCode),
+    #[error("dns response code: {0}")]
+    DnsStatus(u16),
+}
+
+#[derive(Deserialize)]
+struct DnsJsonResponse {
+    #[serde(rename = "Status")]
+    status: u16,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DnsJsonAnswer>,
+}
+
+#[derive(Deserialize)]
+struct DnsJsonAnswer {
+    r#type: u16,
+    data: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct DohTxtResolverConfig<T> {
+    /// The DoH endpoint that serves the JSON API, e.g. [`CLOUDFLARE_DOH_URL`] or [`GOOGLE_DOH_URL`].
+    pub service_url: String,
+    pub http_client: Arc<T>,
+}
+
+/// A [`DnsTxtResolver`] that uses the JSON API of a DNS-over-HTTPS service.
+///
+/// Unlike `DohDnsTxtResolver`, it does not need to encode DNS messages, so it is available without
+/// any optional features. No system resolver is required either.
+pub struct DohTxtResolver<T> {
+    service_url: String,
+    http_client: Arc<T>,
+}
+
+impl<T> DohTxtResolver<T> {
+    pub fn new(config: DohTxtResolverConfig<T>) -> Self {
+        Self { service_url: config.service_url, http_client: config.http_client }
+    }
+}
+
+impl<T> DnsTxtResolver for DohTxtResolver<T>
+where
+    T: HttpClient + Send + Sync + 'static,
+{
+    async fn resolve(
+        &self,
+        query: &str,
+    ) -> core::result::Result<Vec<String>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let uri = format!(
+            "{}?{}",
+            self.service_url,
+            serde_html_form::to_string([("name", query), ("type", "TXT")])?
+        );
+        let res = self
+            .http_client
+            .send_http(
+                Request::builder().uri(uri).header(ACCEPT, DNS_JSON_MEDIA_TYPE).body(Vec::new())?,
+            )
+            .await?;
+        if !res.status().is_success() {
+            return Err(Box::new(Error::HttpStatus(res.status())));
+        }
+        let response = serde_json::from_slice::<DnsJsonResponse>(res.body())?;
+        match response.status {
+            0 => Ok(response
+                .answer
+                .into_iter()
+                .filter(|answer| answer.r#type == TXT_TYPE)
+                .map(|answer| parse_txt_data(&answer.data))
+                .collect()),
+            NXDOMAIN => Ok(Vec::new()),
+            status => Err(Box::new(Error::DnsStatus(status))),
+        }
+    }
+}
+
+/// Join the character-strings of a TXT record, which are quoted in the JSON answer (e.g.
+/// `"\"did=did:plc:abc\""`, or `"\"foo\" \"bar\""` for a record split into several strings).
+fn parse_txt_data(data: &str) -> String {
+    if !data.starts_with('"') {
+        return data.to_string();
+    }
+    let mut result = String::new();
+    let mut chars = data.chars();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' if quoted => result.extend(chars.next()),
+            c if quoted => result.push(c),
+            _ => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atrium_xrpc::http::Response;
+
+    struct MockHttpClient {
+        body: &'static str,
+    }
+
+    impl HttpClient for MockHttpClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            assert_eq!(
+                request.uri().to_string(),
+                "https://dns.example.com/dns-query?name=_atproto.alice.test&type=TXT"
+            );
+            assert_eq!(request.headers()[ACCEPT], DNS_JSON_MEDIA_TYPE);
+            Ok(Response::builder().status(200).body(self.body.as_bytes().to_vec())?)
+        }
+    }
+
+    fn resolver(body: &'static str) -> DohTxtResolver<MockHttpClient> {
+        DohTxtResolver::new(DohTxtResolverConfig {
+            service_url: String::from("https://dns.example.com/dns-query"),
+            http_client: Arc::new(MockHttpClient { body }),
+        })
+    }
+
+    #[tokio::test]
+    async fn resolve() {
+        let results = resolver(
+            r#"{"Status":0,"Answer":[
+                {"name":"_atproto.alice.test","type":5,"TTL":300,"data":"alias.test."},
+                {"name":"_atproto.alice.test","type":16,"TTL":300,"data":"\"did=did:plc:alice\""},
+                {"name":"_atproto.alice.test","type":16,"TTL":300,"data":"\"foo\" \"b\\\"ar\""}
+            ]}"#,
+        )
+        .resolve("_atproto.alice.test")
+        .await
+        .expect("resolve should succeed");
+        assert_eq!(results, ["did=did:plc:alice", "foob\"ar"].map(String::from));
+    }
+
+    #[tokio::test]
+    async fn resolve_nxdomain() {
+        let results = resolver(r#"{"Status":3}"#)
+            .resolve("_atproto.alice.test")
+            .await
+            .expect("resolve should succeed");
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn resolve_servfail() {
+        let err = resolver(r#"{"Status":2}"#)
+            .resolve("_atproto.alice.test")
+            .await
+            .expect_err("resolve should fail");
+        assert!(matches!(err.downcast_ref::<Error>(), Some(Error::DnsStatus(2))));
+    }
+}
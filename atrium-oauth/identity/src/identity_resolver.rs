@@ -2,6 +2,7 @@ use crate::error::{Error, Result};
 use crate::{did::DidResolver, handle::HandleResolver};
 use atrium_api::types::string::AtIdentifier;
 use atrium_common::resolver::Resolver;
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,11 +20,24 @@ pub struct IdentityResolverConfig<D, H> {
 pub struct IdentityResolver<D, H> {
     did_resolver: D,
     handle_resolver: H,
+    verify_handle: bool,
 }
 
 impl<D, H> IdentityResolver<D, H> {
     pub fn new(config: IdentityResolverConfig<D, H>) -> Self {
-        Self { did_resolver: config.did_resolver, handle_resolver: config.handle_resolver }
+        Self {
+            did_resolver: config.did_resolver,
+            handle_resolver: config.handle_resolver,
+            verify_handle: true,
+        }
+    }
+    /// Set whether to verify that the DID document resolved from a handle lists the handle
+    /// in its `alsoKnownAs`. Enabled by default.
+    ///
+    /// If the verification fails, resolution fails with [`Error::HandleMismatch`].
+    pub fn verify_handle(mut self, verify: bool) -> Self {
+        self.verify_handle = verify;
+        self
     }
 }
 
@@ -43,14 +57,13 @@ where
                 AtIdentifier::Handle(handle) => {
                     let did = self.handle_resolver.resolve(&handle).await?;
                     let document = self.did_resolver.resolve(&did).await?;
-                    if let Some(aka) = &document.also_known_as {
-                        if !aka.contains(&format!("at://{}", handle.as_str())) {
-                            return Err(Error::DidDocument(format!(
-                                "did document for `{}` does not include the handle `{}`",
-                                did.as_str(),
-                                handle.as_str()
-                            )));
-                        }
+                    if self.verify_handle
+                        && !document
+                            .also_known_as
+                            .as_ref()
+                            .is_some_and(|aka| aka.contains(&format!("at://{}", handle.as_str())))
+                    {
+                        return Err(Error::HandleMismatch(handle, did));
                     }
                     document
                 }
@@ -64,3 +77,127 @@ where
         Ok(ResolvedIdentity { did: document.id, pds: service })
     }
 }
+
+impl<D, H> IdentityResolver<D, H>
+where
+    D: DidResolver + Send + Sync + 'static,
+    H: HandleResolver + Send + Sync + 'static,
+{
+    /// Resolve multiple identities concurrently, with at most `limit` resolutions in flight.
+    ///
+    /// The results are returned in the same order as `inputs`.
+    pub async fn resolve_many<I>(&self, inputs: &[I], limit: usize) -> Vec<Result<ResolvedIdentity>>
+    where
+        I: AsRef<str>,
+    {
+        stream::iter(inputs)
+            .map(|input| self.resolve(input.as_ref()))
+            .buffered(limit.max(1))
+            .collect()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::StaticDidResolver;
+    use atrium_api::did_doc::{DidDocument, Service};
+    use atrium_api::types::string::{Did, Handle};
+
+    struct StaticHandleResolver;
+
+    impl Resolver for StaticHandleResolver {
+        type Input = Handle;
+        type Output = Did;
+        type Error = Error;
+
+        async fn resolve(&self, handle: &Self::Input) -> Result<Self::Output> {
+            match handle.as_str() {
+                "alice.test" => Ok("did:plc:alice".parse().expect("did should be valid")),
+                "mallory.test" => Ok("did:plc:bob".parse().expect("did should be valid")),
+                _ => Err(Error::NotFound),
+            }
+        }
+    }
+
+    impl HandleResolver for StaticHandleResolver {}
+
+    fn did_document(did: &str, handle: &str) -> DidDocument {
+        DidDocument {
+            context: None,
+            id: did.into(),
+            also_known_as: Some(vec![format!("at://{handle}")]),
+            verification_method: None,
+            service: Some(vec![Service {
+                id: String::from("#atproto_pds"),
+                r#type: String::from("AtprotoPersonalDataServer"),
+                service_endpoint: format!("https://{handle}"),
+            }]),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_many() {
+        let resolver = IdentityResolver::new(IdentityResolverConfig {
+            did_resolver: StaticDidResolver::new()
+                .with_document(
+                    "did:plc:alice".parse().expect("did should be valid"),
+                    did_document("did:plc:alice", "alice.test"),
+                )
+                .with_document(
+                    "did:plc:bob".parse().expect("did should be valid"),
+                    did_document("did:plc:bob", "bob.test"),
+                ),
+            handle_resolver: StaticHandleResolver,
+        });
+        let results =
+            resolver.resolve_many(&["alice.test", "did:plc:bob", "carol.test", "invalid"], 2).await;
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[0].as_ref().expect("alice should be resolved"),
+            &ResolvedIdentity {
+                did: String::from("did:plc:alice"),
+                pds: String::from("https://alice.test")
+            }
+        );
+        assert_eq!(
+            results[1].as_ref().expect("bob should be resolved"),
+            &ResolvedIdentity {
+                did: String::from("did:plc:bob"),
+                pds: String::from("https://bob.test")
+            }
+        );
+        assert!(matches!(results[2], Err(Error::NotFound)));
+        assert!(matches!(results[3], Err(Error::AtIdentifier(_))));
+    }
+
+    #[tokio::test]
+    async fn verify_handle() {
+        let did_resolver = StaticDidResolver::new().with_document(
+            "did:plc:bob".parse().expect("did should be valid"),
+            did_document("did:plc:bob", "bob.test"),
+        );
+        let resolver = IdentityResolver::new(IdentityResolverConfig {
+            did_resolver: did_resolver.clone(),
+            handle_resolver: StaticHandleResolver,
+        });
+        assert!(matches!(
+            resolver.resolve("mallory.test").await,
+            Err(Error::HandleMismatch(handle, did))
+                if handle.as_str() == "mallory.test" && did.as_str() == "did:plc:bob"
+        ));
+        let resolver = IdentityResolver::new(IdentityResolverConfig {
+            did_resolver,
+            handle_resolver: StaticHandleResolver,
+        })
+        .verify_handle(false);
+        assert_eq!(
+            resolver.resolve("mallory.test").await.expect("mallory should be resolved"),
+            ResolvedIdentity {
+                did: String::from("did:plc:bob"),
+                pds: String::from("https://bob.test")
+            }
+        );
+    }
+}
@@ -80,10 +80,14 @@ pub enum Scope {
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum KnownScope {
+    /// Required for all atproto OAuth sessions.
     #[serde(rename = "atproto")]
     Atproto,
+    /// Access to the same APIs as an app password.
     #[serde(rename = "transition:generic")]
     TransitionGeneric,
+    /// Access to the `chat.bsky.*` APIs, through the proxied `bsky_chat` service.
+    /// Only applies in combination with [`TransitionGeneric`](KnownScope::TransitionGeneric).
     #[serde(rename = "transition:chat.bsky")]
     TransitionChatBsky,
 }
@@ -235,6 +239,27 @@ MIGHAgEAMBMGByqGSM49AgEGCCqGSM49AwEHBG0wawIBAQQgED1AAgC7Fc9kPh5T
 gbGGr0pN+oSing7cZ0169JaRHTNh+0LNQXrFobInX6cj95FzEdRyT4T3
 -----END PRIVATE KEY-----"#;
 
+    #[test]
+    fn test_scopes() {
+        let scopes = serde_json::from_str::<Vec<Scope>>(
+            r#"["atproto","transition:generic","transition:chat.bsky","custom"]"#,
+        )
+        .expect("failed to deserialize scopes");
+        assert_eq!(
+            scopes,
+            [
+                Scope::Known(KnownScope::Atproto),
+                Scope::Known(KnownScope::TransitionGeneric),
+                Scope::Known(KnownScope::TransitionChatBsky),
+                Scope::Unknown(String::from("custom")),
+            ]
+        );
+        assert_eq!(
+            scopes.iter().map(AsRef::as_ref).collect::<Vec<_>>(),
+            ["atproto", "transition:generic", "transition:chat.bsky", "custom"]
+        );
+    }
+
     #[test]
     fn test_localhost_client_metadata_default() {
         let metadata = AtprotoLocalhostClientMetadata::default();
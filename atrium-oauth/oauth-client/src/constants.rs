@@ -1 +1,3 @@
 pub const FALLBACK_ALG: &str = "ES256";
+/// The default time after which an authorization state expires.
+pub const DEFAULT_STATE_TTL_SECS: i64 = 10 * 60;
@@ -9,13 +9,26 @@ pub enum Error {
     #[error(transparent)]
     Identity(#[from] atrium_identity::Error),
     #[error(transparent)]
+    Resolve(#[from] crate::resolver::ResolveError),
+    #[error(transparent)]
     ServerAgent(#[from] crate::server_agent::Error),
     #[error("authorize error: {0}")]
     Authorize(String),
+    #[error(
+        "authorization error: {error}{}",
+        description.as_ref().map(|d| format!(": {d}")).unwrap_or_default()
+    )]
+    Authorization { error: String, description: Option<String> },
     #[error("callback error: {0}")]
     Callback(String),
+    #[error("authorization state expired")]
+    ExpiredState,
     #[error("state store error: {0:?}")]
     StateStore(Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("no session found for `{0}`")]
+    SessionNotFound(String),
+    #[error("session store error: {0:?}")]
+    SessionStore(Box<dyn std::error::Error + Send + Sync + 'static>),
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
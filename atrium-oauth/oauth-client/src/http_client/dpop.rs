@@ -3,7 +3,7 @@ use crate::jose::jws::RegisteredHeader;
 use crate::jose::jwt::{Claims, PublicClaims, RegisteredClaims};
 use crate::store::memory::MemorySimpleStore;
 use crate::store::SimpleStore;
-use atrium_xrpc::http::{Request, Response};
+use atrium_xrpc::http::{Request, Response, Uri};
 use atrium_xrpc::HttpClient;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
@@ -36,6 +36,22 @@ pub enum Error {
 
 type Result<T> = core::result::Result<T, Error>;
 
+/// A DPoP proof sent by a [`DpopClient`], with its decoded claims.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DpopProof {
+    /// The signed JWT, as sent in the `DPoP` header.
+    pub jwt: String,
+    pub jti: String,
+    pub iat: i64,
+    pub htm: String,
+    pub htu: String,
+    pub ath: Option<String>,
+    pub nonce: Option<String>,
+}
+
+/// A function called with every DPoP proof sent by a [`DpopClient`].
+pub type DpopProofInspector = Arc<dyn Fn(&DpopProof) + Send + Sync + 'static>;
+
 pub struct DpopClient<T, S = MemorySimpleStore<String, String>>
 where
     S: SimpleStore<String, String>,
@@ -44,6 +60,7 @@ where
     pub(crate) key: Key,
     nonces: S,
     is_auth_server: bool,
+    inspector: Option<DpopProofInspector>,
 }
 
 impl<T> DpopClient<T> {
@@ -66,7 +83,7 @@ impl<T> DpopClient<T> {
             }
         }
         let nonces = MemorySimpleStore::<String, String>::default();
-        Ok(Self { inner: http_client, key, nonces, is_auth_server })
+        Ok(Self { inner: http_client, key, nonces, is_auth_server, inspector: None })
     }
 }
 
@@ -74,6 +91,21 @@ impl<T, S> DpopClient<T, S>
 where
     S: SimpleStore<String, String>,
 {
+    /// Set a function to be called with every DPoP proof, right before the request is sent.
+    ///
+    /// This is intended for debugging, e.g. to see the `htu`, `htm` and `nonce` of a proof that
+    /// was rejected by the server.
+    pub fn with_proof_inspector(
+        mut self,
+        inspector: impl Fn(&DpopProof) + Send + Sync + 'static,
+    ) -> Self {
+        self.inspector = Some(Arc::new(inspector));
+        self
+    }
+    pub(crate) fn with_shared_proof_inspector(mut self, inspector: DpopProofInspector) -> Self {
+        self.inspector = Some(inspector);
+        self
+    }
     fn build_proof(
         &self,
         htm: String,
@@ -89,15 +121,25 @@ where
                     key: Key::from(&crypto::Key::from(secret_key.public_key())),
                     prm: Default::default(),
                 });
+                let (jti, iat) = (Self::generate_jti(), Utc::now().timestamp());
                 let claims = Claims {
                     registered: RegisteredClaims {
-                        jti: Some(Self::generate_jti()),
-                        iat: Some(Utc::now().timestamp()),
+                        jti: Some(jti.clone()),
+                        iat: Some(iat),
                         ..Default::default()
                     },
-                    public: PublicClaims { htm: Some(htm), htu: Some(htu), ath, nonce },
+                    public: PublicClaims {
+                        htm: Some(htm.clone()),
+                        htu: Some(htu.clone()),
+                        ath: ath.clone(),
+                        nonce: nonce.clone(),
+                    },
                 };
-                Ok(create_signed_jwt(secret_key.into(), header.into(), claims)?)
+                let jwt = create_signed_jwt(secret_key.into(), header.into(), claims)?;
+                if let Some(inspector) = &self.inspector {
+                    inspector(&DpopProof { jwt: jwt.clone(), jti, iat, htm, htu, ath, nonce });
+                }
+                Ok(jwt)
             }
             _ => unimplemented!(),
         }
@@ -145,7 +187,11 @@ where
         let uri = request.uri();
         let nonce_key = uri.authority().unwrap().to_string();
         let htm = request.method().to_string();
-        let htu = uri.to_string();
+        // https://datatracker.ietf.org/doc/html/rfc9449#section-4.2
+        // "The HTTP target URI, without query and fragment parts"
+        let mut htu_parts = uri.clone().into_parts();
+        htu_parts.path_and_query = Some(uri.path().parse()?);
+        let htu = Uri::from_parts(htu_parts)?.to_string();
         // https://datatracker.ietf.org/doc/html/rfc9449#section-4.2
         let ath = request
             .headers()
@@ -182,3 +228,69 @@ where
         Ok(response)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generate_key;
+    use std::sync::Mutex;
+
+    struct MockHttpClient;
+
+    impl HttpClient for MockHttpClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            let builder = Response::builder().header("DPoP-Nonce", "fresh");
+            // reject the proofs without a nonce
+            let proof = request.headers()["DPoP"].to_str()?;
+            let payload = URL_SAFE_NO_PAD.decode(proof.split('.').nth(1).unwrap_or_default())?;
+            if serde_json::from_slice::<Claims>(&payload)?.public.nonce.is_none() {
+                return Ok(builder
+                    .status(401)
+                    .header("WWW-Authenticate", r#"DPoP error="use_dpop_nonce""#)
+                    .body(Vec::new())?);
+            }
+            Ok(builder.status(200).body(Vec::new())?)
+        }
+    }
+
+    #[tokio::test]
+    async fn proof_inspector() {
+        let proofs = Arc::new(Mutex::new(Vec::new()));
+        let client = DpopClient::new(
+            generate_key(&[String::from("ES256")]).expect("failed to generate key"),
+            Arc::new(MockHttpClient),
+            false,
+            &None,
+        )
+        .expect("failed to create client")
+        .with_proof_inspector({
+            let proofs = Arc::clone(&proofs);
+            move |proof| proofs.lock().expect("failed to lock").push(proof.clone())
+        });
+        let request = Request::builder()
+            .uri("https://pds.test/xrpc/com.example.get?foo=bar")
+            .header("Authorization", "DPoP token")
+            .body(Vec::new())
+            .expect("failed to build request");
+        let response = client.send_http(request).await.expect("failed to send request");
+        assert_eq!(response.status(), 200);
+
+        let proofs = proofs.lock().expect("failed to lock");
+        assert_eq!(proofs.len(), 2);
+        for proof in proofs.iter() {
+            assert_eq!(proof.htm, "GET");
+            assert_eq!(proof.htu, "https://pds.test/xrpc/com.example.get");
+            assert_eq!(proof.ath, Some(URL_SAFE_NO_PAD.encode(Sha256::digest("token"))));
+        }
+        assert_eq!(proofs[0].nonce, None);
+        assert_eq!(proofs[1].nonce.as_deref(), Some("fresh"));
+        assert_ne!(proofs[0].jti, proofs[1].jti);
+        assert_ne!(proofs[0].jwt, proofs[1].jwt);
+    }
+}
@@ -5,6 +5,7 @@ mod http_client;
 mod jose;
 mod keyset;
 mod oauth_client;
+mod oauth_session;
 mod resolver;
 mod server_agent;
 pub mod store;
@@ -17,9 +18,10 @@ pub use atproto::{
 pub use error::{Error, Result};
 #[cfg(feature = "default-client")]
 pub use http_client::default::DefaultHttpClient;
-pub use http_client::dpop::DpopClient;
-pub use oauth_client::{OAuthClient, OAuthClientConfig};
-pub use resolver::OAuthResolverConfig;
+pub use http_client::dpop::{DpopClient, DpopProof, DpopProofInspector};
+pub use oauth_client::{MetadataWarning, OAuthClient, OAuthClientConfig, RevokeOutcome};
+pub use oauth_session::OAuthSession;
+pub use resolver::{OAuthResolverConfig, ResolveError, ResolveStage};
 pub use types::{
     AuthorizeOptionPrompt, AuthorizeOptions, CallbackParams, OAuthClientMetadata, TokenSet,
 };
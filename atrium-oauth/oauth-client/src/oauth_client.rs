@@ -1,8 +1,11 @@
-use crate::constants::FALLBACK_ALG;
+use crate::constants::{DEFAULT_STATE_TTL_SECS, FALLBACK_ALG};
 use crate::error::{Error, Result};
+use crate::http_client::dpop::{DpopClient, DpopProofInspector};
 use crate::keyset::Keyset;
+use crate::oauth_session::OAuthSession;
 use crate::resolver::{OAuthResolver, OAuthResolverConfig};
 use crate::server_agent::{OAuthRequest, OAuthServerAgent};
+use crate::store::session::{Session, SessionStore};
 use crate::store::state::{InternalStateData, StateStore};
 use crate::types::{
     AuthorizationCodeChallengeMethod, AuthorizationResponseType, AuthorizeOptions, CallbackParams,
@@ -13,17 +16,56 @@ use crate::types::{
 use crate::utils::{compare_algos, generate_key, generate_nonce, get_random_values};
 use atrium_common::resolver::Resolver;
 use atrium_identity::{did::DidResolver, handle::HandleResolver};
+use atrium_xrpc::http::Uri;
 use atrium_xrpc::HttpClient;
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
+use chrono::{TimeDelta, Utc};
 use jose_jwk::{Jwk, JwkSet, Key};
 use rand::rngs::ThreadRng;
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 use std::sync::Arc;
 
+/// The outcome of [`OAuthClient::revoke()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevokeOutcome {
+    /// The token was revoked by the authorization server.
+    Revoked,
+    /// The authorization server has no revocation endpoint, so the token could not be revoked.
+    RevocationUnsupported,
+}
+
+/// A mismatch between the client metadata and the metadata of an authorization server, reported
+/// by [`OAuthClient::validate_metadata()`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MetadataWarning {
+    #[error("authorization server does not support the scope `{0}`")]
+    UnsupportedScope(String),
+    #[error("authorization server does not support the grant type `{0}`")]
+    UnsupportedGrantType(String),
+    #[error("authorization server does not support the token endpoint auth method `{0}`")]
+    UnsupportedAuthMethod(String),
+    #[error("authorization server does not support the token endpoint auth signing alg `{0}`")]
+    UnsupportedAuthSigningAlg(String),
+    #[error("authorization server does not support the `code` response type")]
+    UnsupportedResponseType,
+    #[error("authorization server does not support the `S256` code challenge method")]
+    UnsupportedCodeChallengeMethod,
+    #[error("authorization server does not provide a pushed authorization request endpoint")]
+    PushedAuthorizationRequestUnsupported,
+    #[error("authorization server does not support client id metadata documents")]
+    ClientIdMetadataDocumentUnsupported,
+    #[error("none of the DPoP signing algs of the authorization server can be used")]
+    UnsupportedDpopSigningAlgs,
+    #[error("client metadata does not declare `dpop_bound_access_tokens`")]
+    DpopNotBound,
+    #[error("redirect_uri `{0}` is not allowed for the client_id")]
+    RedirectUri(String),
+}
+
 #[cfg(feature = "default-client")]
-pub struct OAuthClientConfig<S, M, D, H>
+pub struct OAuthClientConfig<S, SS, M, D, H>
 where
     M: TryIntoOAuthClientMetadata,
 {
@@ -32,12 +74,20 @@ where
     pub keys: Option<Vec<Jwk>>,
     // Stores
     pub state_store: S,
+    /// The store of the sessions created by [`OAuthClient::callback()`].
+    pub session_store: SS,
+    /// The time after which an authorization state expires.
+    /// Defaults to 10 minutes if `None`.
+    pub state_ttl: Option<TimeDelta>,
+    /// A function called with every DPoP proof sent to the authorization servers, e.g. to debug
+    /// the proofs rejected by a server.
+    pub dpop_proof_inspector: Option<DpopProofInspector>,
     // Services
     pub resolver: OAuthResolverConfig<D, H>,
 }
 
 #[cfg(not(feature = "default-client"))]
-pub struct OAuthClientConfig<S, T, M, D, H>
+pub struct OAuthClientConfig<S, SS, T, M, D, H>
 where
     M: TryIntoOAuthClientMetadata,
 {
@@ -46,6 +96,14 @@ where
     pub keys: Option<Vec<Jwk>>,
     // Stores
     pub state_store: S,
+    /// The store of the sessions created by [`OAuthClient::callback()`].
+    pub session_store: SS,
+    /// The time after which an authorization state expires.
+    /// Defaults to 10 minutes if `None`.
+    pub state_ttl: Option<TimeDelta>,
+    /// A function called with every DPoP proof sent to the authorization servers, e.g. to debug
+    /// the proofs rejected by a server.
+    pub dpop_proof_inspector: Option<DpopProofInspector>,
     // Services
     pub resolver: OAuthResolverConfig<D, H>,
     // Others
@@ -53,37 +111,46 @@ where
 }
 
 #[cfg(feature = "default-client")]
-pub struct OAuthClient<S, D, H, T = crate::http_client::default::DefaultHttpClient>
+pub struct OAuthClient<S, SS, D, H, T = crate::http_client::default::DefaultHttpClient>
 where
     S: StateStore,
+    SS: SessionStore,
     T: HttpClient + Send + Sync + 'static,
 {
     pub client_metadata: OAuthClientMetadata,
     keyset: Option<Keyset>,
     resolver: Arc<OAuthResolver<T, D, H>>,
     state_store: S,
+    session_store: Arc<SS>,
+    state_ttl: TimeDelta,
+    dpop_proof_inspector: Option<DpopProofInspector>,
     http_client: Arc<T>,
 }
 
 #[cfg(not(feature = "default-client"))]
-pub struct OAuthClient<S, D, H, T>
+pub struct OAuthClient<S, SS, D, H, T>
 where
     S: StateStore,
+    SS: SessionStore,
     T: HttpClient + Send + Sync + 'static,
 {
     pub client_metadata: OAuthClientMetadata,
     keyset: Option<Keyset>,
     resolver: Arc<OAuthResolver<T, D, H>>,
     state_store: S,
+    session_store: Arc<SS>,
+    state_ttl: TimeDelta,
+    dpop_proof_inspector: Option<DpopProofInspector>,
     http_client: Arc<T>,
 }
 
 #[cfg(feature = "default-client")]
-impl<S, D, H> OAuthClient<S, D, H, crate::http_client::default::DefaultHttpClient>
+impl<S, SS, D, H> OAuthClient<S, SS, D, H, crate::http_client::default::DefaultHttpClient>
 where
     S: StateStore,
+    SS: SessionStore,
 {
-    pub fn new<M>(config: OAuthClientConfig<S, M, D, H>) -> Result<Self>
+    pub fn new<M>(config: OAuthClientConfig<S, SS, M, D, H>) -> Result<Self>
     where
         M: TryIntoOAuthClientMetadata<Error = crate::atproto::Error>,
     {
@@ -95,18 +162,24 @@ where
             keyset,
             resolver: Arc::new(OAuthResolver::new(config.resolver, http_client.clone())),
             state_store: config.state_store,
+            session_store: Arc::new(config.session_store),
+            state_ttl: config
+                .state_ttl
+                .unwrap_or_else(|| TimeDelta::seconds(DEFAULT_STATE_TTL_SECS)),
+            dpop_proof_inspector: config.dpop_proof_inspector,
             http_client,
         })
     }
 }
 
 #[cfg(not(feature = "default-client"))]
-impl<S, D, H, T> OAuthClient<S, D, H, T>
+impl<S, SS, D, H, T> OAuthClient<S, SS, D, H, T>
 where
     S: StateStore,
+    SS: SessionStore,
     T: HttpClient + Send + Sync + 'static,
 {
-    pub fn new<M>(config: OAuthClientConfig<S, T, M, D, H>) -> Result<Self>
+    pub fn new<M>(config: OAuthClientConfig<S, SS, T, M, D, H>) -> Result<Self>
     where
         M: TryIntoOAuthClientMetadata<Error = crate::atproto::Error>,
     {
@@ -118,14 +191,20 @@ where
             keyset,
             resolver: Arc::new(OAuthResolver::new(config.resolver, http_client.clone())),
             state_store: config.state_store,
+            session_store: Arc::new(config.session_store),
+            state_ttl: config
+                .state_ttl
+                .unwrap_or_else(|| TimeDelta::seconds(DEFAULT_STATE_TTL_SECS)),
+            dpop_proof_inspector: config.dpop_proof_inspector,
             http_client,
         })
     }
 }
 
-impl<S, D, H, T> OAuthClient<S, D, H, T>
+impl<S, SS, D, H, T> OAuthClient<S, SS, D, H, T>
 where
     S: StateStore,
+    SS: SessionStore,
     D: DidResolver + Send + Sync + 'static,
     H: HandleResolver + Send + Sync + 'static,
     T: HttpClient + Send + Sync + 'static,
@@ -156,7 +235,12 @@ where
             iss: metadata.issuer.clone(),
             dpop_key: dpop_key.clone(),
             verifier,
+            iat: Utc::now().timestamp(),
         };
+        self.state_store
+            .purge_expired(self.state_ttl)
+            .await
+            .map_err(|e| Error::StateStore(Box::new(e)))?;
         self.state_store
             .set(state.clone(), state_data)
             .await
@@ -181,12 +265,20 @@ where
                 self.resolver.clone(),
                 self.http_client.clone(),
                 self.keyset.clone(),
+                self.dpop_proof_inspector.clone(),
             )?;
             let par_response = server
                 .request::<OAuthPusehedAuthorizationRequestResponse>(
                     OAuthRequest::PushedAuthorizationRequest(parameters),
                 )
-                .await?;
+                .await
+                .map_err(|e| match e {
+                    crate::server_agent::Error::OAuth(_, res) => Error::Authorization {
+                        error: res.error,
+                        description: res.error_description,
+                    },
+                    e => Error::ServerAgent(e),
+                })?;
 
             #[derive(Serialize)]
             struct Parameters {
@@ -208,6 +300,10 @@ where
             todo!()
         }
     }
+    /// Complete an authorization with the parameters of the redirect to the callback URI.
+    ///
+    /// The issued tokens are saved to the session store with the DPoP key they are bound to,
+    /// keyed by the DID of the user (the `sub` of the returned [`TokenSet`]).
     pub async fn callback(&self, params: CallbackParams) -> Result<TokenSet> {
         let Some(state_key) = params.state else {
             return Err(Error::Callback("missing `state` parameter".into()));
@@ -220,6 +316,9 @@ where
         };
         // Prevent any kind of replay
         self.state_store.del(&state_key).await.map_err(|e| Error::StateStore(Box::new(e)))?;
+        if state.is_expired(self.state_ttl) {
+            return Err(Error::ExpiredState);
+        }
 
         let metadata = self.resolver.get_authorization_server_metadata(&state.iss).await?;
         // https://datatracker.ietf.org/doc/html/rfc9207#section-2.4
@@ -240,12 +339,92 @@ where
             self.resolver.clone(),
             self.http_client.clone(),
             self.keyset.clone(),
+            self.dpop_proof_inspector.clone(),
         )?;
         let token_set = server.exchange_code(&params.code, &state.verifier).await?;
-
-        // TODO: create session?
+        self.session_store
+            .set(
+                token_set.sub.clone(),
+                Session { dpop_key: state.dpop_key, token_set: token_set.clone() },
+            )
+            .await
+            .map_err(|e| Error::SessionStore(Box::new(e)))?;
         Ok(token_set)
     }
+    /// Revoke the tokens of the stored session of `sub` (the DID of the user) at the
+    /// authorization server that issued them, and remove the session from the session store.
+    ///
+    /// The refresh token is revoked if present, which also invalidates the access token. The
+    /// request is signed with the DPoP key of the session, which the tokens are bound to.
+    /// If the server does not provide a revocation endpoint,
+    /// [`RevokeOutcome::RevocationUnsupported`] is returned instead of an error.
+    ///
+    /// The session is removed even if the revocation fails.
+    pub async fn revoke(&self, sub: impl AsRef<str>) -> Result<RevokeOutcome> {
+        let sub = sub.as_ref().to_string();
+        let Some(session) =
+            self.session_store.get(&sub).await.map_err(|e| Error::SessionStore(Box::new(e)))?
+        else {
+            return Err(Error::SessionNotFound(sub));
+        };
+        let result = self.revoke_session(session).await;
+        self.session_store.del(&sub).await.map_err(|e| Error::SessionStore(Box::new(e)))?;
+        result
+    }
+    /// Restore the stored session of `sub` (the DID of the user), to send authenticated XRPC
+    /// requests to their PDS.
+    pub async fn restore(&self, sub: impl AsRef<str>) -> Result<OAuthSession<T, D, H, SS>> {
+        let sub = sub.as_ref().to_string();
+        let Some(Session { dpop_key, token_set }) =
+            self.session_store.get(&sub).await.map_err(|e| Error::SessionStore(Box::new(e)))?
+        else {
+            return Err(Error::SessionNotFound(sub));
+        };
+        let metadata = self.resolver.get_authorization_server_metadata(&token_set.iss).await?;
+        let server = OAuthServerAgent::new(
+            dpop_key.clone(),
+            metadata,
+            self.client_metadata.clone(),
+            self.resolver.clone(),
+            self.http_client.clone(),
+            self.keyset.clone(),
+            self.dpop_proof_inspector.clone(),
+        )?;
+        let mut dpop_client = DpopClient::new(dpop_key, self.http_client.clone(), false, &None)
+            .map_err(|e| Error::ServerAgent(e.into()))?;
+        if let Some(inspector) = &self.dpop_proof_inspector {
+            dpop_client = dpop_client.with_shared_proof_inspector(inspector.clone());
+        }
+        Ok(OAuthSession::new(server, dpop_client, Arc::clone(&self.session_store), token_set))
+    }
+    async fn revoke_session(&self, session: Session) -> Result<RevokeOutcome> {
+        let Session { dpop_key, token_set } = session;
+        let metadata = self.resolver.get_authorization_server_metadata(&token_set.iss).await?;
+        if metadata.revocation_endpoint.is_none() {
+            return Ok(RevokeOutcome::RevocationUnsupported);
+        }
+        let server = OAuthServerAgent::new(
+            dpop_key,
+            metadata,
+            self.client_metadata.clone(),
+            self.resolver.clone(),
+            self.http_client.clone(),
+            self.keyset.clone(),
+            self.dpop_proof_inspector.clone(),
+        )?;
+        server.revoke(token_set.refresh_token.as_ref().unwrap_or(&token_set.access_token)).await?;
+        Ok(RevokeOutcome::Revoked)
+    }
+    /// Check the client metadata against the metadata of the authorization server for `input`,
+    /// which can be anything accepted by [`authorize()`](Self::authorize) (a handle, a DID, or
+    /// the URL of a PDS or an entryway).
+    ///
+    /// The returned warnings describe what would make an authorization fail, or behave
+    /// unexpectedly, with this server. An empty list means that no mismatch was found.
+    pub async fn validate_metadata(&self, input: impl AsRef<str>) -> Result<Vec<MetadataWarning>> {
+        let (metadata, _) = self.resolver.resolve(input.as_ref()).await?;
+        Ok(check_metadata(&self.client_metadata, &metadata))
+    }
     fn generate_dpop_key(metadata: &OAuthAuthorizationServerMetadata) -> Option<Key> {
         let mut algs =
             metadata.dpop_signing_alg_values_supported.clone().unwrap_or(vec![FALLBACK_ALG.into()]);
@@ -259,3 +438,489 @@ where
         (URL_SAFE_NO_PAD.encode(Sha256::digest(&verifier)), verifier)
     }
 }
+
+fn check_metadata(
+    client: &OAuthClientMetadata,
+    server: &OAuthAuthorizationServerMetadata,
+) -> Vec<MetadataWarning> {
+    let unsupported = |supported: &Option<Vec<String>>, value: &String| {
+        supported.as_ref().is_some_and(|supported| !supported.contains(value))
+    };
+    let mut warnings = Vec::new();
+    for scope in client.scope.iter().flat_map(|scope| scope.split_whitespace()) {
+        if !server.scopes_supported.iter().any(|supported| supported == scope) {
+            warnings.push(MetadataWarning::UnsupportedScope(scope.into()));
+        }
+    }
+    for grant_type in client.grant_types.iter().flatten() {
+        if unsupported(&server.grant_types_supported, grant_type) {
+            warnings.push(MetadataWarning::UnsupportedGrantType(grant_type.clone()));
+        }
+    }
+    if let Some(method) = &client.token_endpoint_auth_method {
+        if unsupported(&server.token_endpoint_auth_methods_supported, method) {
+            warnings.push(MetadataWarning::UnsupportedAuthMethod(method.clone()));
+        }
+    }
+    if let Some(alg) = &client.token_endpoint_auth_signing_alg {
+        if unsupported(&server.token_endpoint_auth_signing_alg_values_supported, alg) {
+            warnings.push(MetadataWarning::UnsupportedAuthSigningAlg(alg.clone()));
+        }
+    }
+    if !server.response_types_supported.iter().any(|response_type| response_type == "code") {
+        warnings.push(MetadataWarning::UnsupportedResponseType);
+    }
+    if unsupported(&server.code_challenge_methods_supported, &String::from("S256")) {
+        warnings.push(MetadataWarning::UnsupportedCodeChallengeMethod);
+    }
+    if server.pushed_authorization_request_endpoint.is_none() {
+        warnings.push(MetadataWarning::PushedAuthorizationRequestUnsupported);
+    }
+    if server.client_id_metadata_document_supported != Some(true) {
+        warnings.push(MetadataWarning::ClientIdMetadataDocumentUnsupported);
+    }
+    if let Some(algs) = &server.dpop_signing_alg_values_supported {
+        if generate_key(algs).is_none() {
+            warnings.push(MetadataWarning::UnsupportedDpopSigningAlgs);
+        }
+        if client.dpop_bound_access_tokens != Some(true) {
+            warnings.push(MetadataWarning::DpopNotBound);
+        }
+    }
+    // Redirect URIs of non-loopback clients must use `https:`, or a custom scheme which is the
+    // hostname of the client_id in reverse-domain order.
+    // https://atproto.com/specs/oauth#clients
+    let client_host = client.client_id.parse::<Uri>().ok().and_then(|uri| {
+        uri.host()
+            .filter(|host| *host != "localhost")
+            .map(|host| host.split('.').rev().collect::<Vec<_>>().join("."))
+    });
+    if let Some(reversed_host) = client_host {
+        for redirect_uri in &client.redirect_uris {
+            // custom schemes have no authority, so they cannot be parsed as `Uri`
+            let allowed = match redirect_uri.split_once(':').map(|(scheme, _)| scheme) {
+                Some("https") => true,
+                Some("http") => false,
+                Some(scheme) => scheme == reversed_host,
+                None => false,
+            };
+            if !allowed {
+                warnings.push(MetadataWarning::RedirectUri(redirect_uri.clone()));
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::session::MemorySessionStore;
+    use crate::store::state::MemoryStateStore;
+    use crate::store::SimpleStore;
+    use atrium_api::did_doc::{DidDocument, Service};
+    use atrium_api::types::string::{Did, Handle};
+    use atrium_identity::did::StaticDidResolver;
+    use atrium_xrpc::http::{Method, Request, Response, StatusCode};
+    use atrium_xrpc::{XrpcClient, XrpcRequest};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockHttpClient {
+        revoked: Mutex<Vec<(String, serde_json::Value)>>,
+        refreshed: Mutex<Vec<String>>,
+        authorized: Mutex<Vec<String>>,
+    }
+
+    impl HttpClient for MockHttpClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            match (request.uri().host(), request.uri().path()) {
+                (Some(host), "/.well-known/oauth-authorization-server") => {
+                    let mut metadata = serde_json::to_value(OAuthAuthorizationServerMetadata {
+                        issuer: format!("https://{host}"),
+                        token_endpoint_auth_methods_supported: Some(vec![String::from("none")]),
+                        ..server_metadata()
+                    })?;
+                    if host != "issuer.test" {
+                        metadata["revocation_endpoint"] =
+                            serde_json::json!(format!("https://{host}/oauth/revoke"));
+                    }
+                    metadata["token_endpoint"] =
+                        serde_json::json!(format!("https://{host}/oauth/token"));
+                    Ok(Response::builder().status(200).body(serde_json::to_vec(&metadata)?)?)
+                }
+                (Some("pds.test"), "/.well-known/oauth-protected-resource") => {
+                    let metadata = serde_json::json!({
+                        "resource": "https://pds.test",
+                        "authorization_servers": ["https://issuer.test"],
+                        "scopes_supported": [],
+                    });
+                    Ok(Response::builder().status(200).body(serde_json::to_vec(&metadata)?)?)
+                }
+                (Some("issuer.test"), "/oauth/token") => {
+                    let body = String::from_utf8(request.body().clone())?;
+                    self.refreshed.lock().unwrap().push(body);
+                    let token = serde_json::json!({
+                        "access_token": "refreshed",
+                        "token_type": "DPoP",
+                        "expires_in": 3600,
+                        "refresh_token": "refresh2",
+                        "sub": "did:plc:alice",
+                    });
+                    Ok(Response::builder().status(200).body(serde_json::to_vec(&token)?)?)
+                }
+                (Some("pds.test"), "/xrpc/com.example.get") => {
+                    // let concurrent requests reach the server with the same token
+                    tokio::task::yield_now().await;
+                    let authorization = request
+                        .headers()
+                        .get("Authorization")
+                        .ok_or("missing Authorization header")?
+                        .to_str()?
+                        .to_string();
+                    self.authorized.lock().unwrap().push(authorization.clone());
+                    Ok(if authorization == "DPoP refreshed" {
+                        Response::builder().status(200).body(Vec::new())?
+                    } else {
+                        Response::builder()
+                            .status(401)
+                            .header("WWW-Authenticate", r#"DPoP error="invalid_token""#)
+                            .body(Vec::new())?
+                    })
+                }
+                (Some("revocable.test"), "/oauth/revoke") => {
+                    // the `jwk` in the header of the DPoP proof
+                    let proof = request.headers().get("DPoP").ok_or("missing DPoP proof")?;
+                    let header = proof.to_str()?.split('.').next().ok_or("invalid DPoP proof")?;
+                    let header = serde_json::from_slice::<serde_json::Value>(
+                        &URL_SAFE_NO_PAD.decode(header)?,
+                    )?;
+                    let body = String::from_utf8(request.body().clone())?;
+                    self.revoked.lock().unwrap().push((body, header["jwk"].clone()));
+                    Ok(Response::builder().status(200).body(Vec::new())?)
+                }
+                (Some("failing.test"), "/oauth/revoke") => {
+                    Ok(Response::builder().status(503).body(Vec::new())?)
+                }
+                _ => Ok(Response::builder().status(404).body(Vec::new())?),
+            }
+        }
+    }
+
+    struct NoHandleResolver;
+
+    impl Resolver for NoHandleResolver {
+        type Input = Handle;
+        type Output = Did;
+        type Error = atrium_identity::Error;
+
+        async fn resolve(&self, _: &Self::Input) -> atrium_identity::Result<Self::Output> {
+            Err(atrium_identity::Error::NotFound)
+        }
+    }
+
+    impl HandleResolver for NoHandleResolver {}
+
+    type TestOAuthClient = OAuthClient<
+        MemoryStateStore,
+        MemorySessionStore,
+        StaticDidResolver,
+        NoHandleResolver,
+        MockHttpClient,
+    >;
+
+    fn oauth_client() -> TestOAuthClient {
+        let http_client = Arc::new(MockHttpClient::default());
+        OAuthClient {
+            client_metadata: OAuthClientMetadata {
+                client_id: String::from("http://localhost"),
+                redirect_uris: vec![String::from("http://127.0.0.1/")],
+                token_endpoint_auth_method: Some(String::from("none")),
+                token_endpoint_auth_signing_alg: None,
+                ..client_metadata()
+            },
+            keyset: None,
+            resolver: Arc::new(OAuthResolver::new(
+                OAuthResolverConfig {
+                    did_resolver: StaticDidResolver::new().with_document(
+                        "did:plc:alice".parse().expect("invalid did"),
+                        DidDocument {
+                            context: None,
+                            id: String::from("did:plc:alice"),
+                            also_known_as: None,
+                            verification_method: None,
+                            service: Some(vec![Service {
+                                id: String::from("#atproto_pds"),
+                                r#type: String::from("AtprotoPersonalDataServer"),
+                                service_endpoint: String::from("https://pds.test"),
+                            }]),
+                        },
+                    ),
+                    handle_resolver: NoHandleResolver,
+                    authorization_server_metadata: Default::default(),
+                    protected_resource_metadata: Default::default(),
+                },
+                http_client.clone(),
+            )),
+            state_store: MemoryStateStore::default(),
+            session_store: Arc::new(MemorySessionStore::default()),
+            state_ttl: TimeDelta::seconds(DEFAULT_STATE_TTL_SECS),
+            dpop_proof_inspector: None,
+            http_client,
+        }
+    }
+
+    fn session(iss: &str) -> Session {
+        Session {
+            dpop_key: generate_key(&[String::from("ES256")]).expect("failed to generate key"),
+            token_set: serde_json::from_value(serde_json::json!({
+                "iss": iss,
+                "sub": "did:plc:alice",
+                "aud": "https://pds.test",
+                "refresh_token": "refresh",
+                "access_token": "access",
+                "token_type": "DPoP",
+            }))
+            .expect("failed to deserialize token set"),
+        }
+    }
+
+    #[tokio::test]
+    async fn revoke() -> Result<()> {
+        let client = oauth_client();
+        let session = session("https://revocable.test");
+        let sub = session.token_set.sub.clone();
+        client.session_store.set(sub.clone(), session.clone()).await.unwrap();
+        assert_eq!(client.revoke(&sub).await?, RevokeOutcome::Revoked);
+        assert_eq!(client.session_store.get(&sub).await.unwrap(), None);
+        // the refresh token is revoked, with a proof signed by the key of the session
+        let revoked = client.http_client.revoked.lock().unwrap().clone();
+        assert_eq!(revoked.len(), 1);
+        assert!(revoked[0].0.contains("token=refresh"));
+        let key = serde_json::to_value(&session.dpop_key).expect("failed to serialize key");
+        assert_eq!((&revoked[0].1["x"], &revoked[0].1["y"]), (&key["x"], &key["y"]));
+        // the session is gone
+        assert!(matches!(client.revoke(&sub).await, Err(Error::SessionNotFound(s)) if s == sub));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn revoke_with_proof_inspector() -> Result<()> {
+        let proofs = Arc::new(Mutex::new(Vec::new()));
+        let client = TestOAuthClient {
+            dpop_proof_inspector: Some(Arc::new({
+                let proofs = Arc::clone(&proofs);
+                move |proof: &crate::DpopProof| proofs.lock().unwrap().push(proof.clone())
+            })),
+            ..oauth_client()
+        };
+        let session = session("https://revocable.test");
+        let sub = session.token_set.sub.clone();
+        client.session_store.set(sub.clone(), session).await.unwrap();
+        client.revoke(&sub).await?;
+        let proofs = proofs.lock().unwrap();
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(
+            (proofs[0].htm.as_str(), proofs[0].htu.as_str()),
+            ("POST", "https://revocable.test/oauth/revoke")
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn revoke_unsupported() -> Result<()> {
+        let client = oauth_client();
+        let session = session("https://issuer.test");
+        let sub = session.token_set.sub.clone();
+        client.session_store.set(sub.clone(), session).await.unwrap();
+        assert_eq!(client.revoke(&sub).await?, RevokeOutcome::RevocationUnsupported);
+        assert_eq!(client.session_store.get(&sub).await.unwrap(), None);
+        assert!(client.http_client.revoked.lock().unwrap().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn revoke_failed() {
+        let client = oauth_client();
+        let session = session("https://failing.test");
+        let sub = session.token_set.sub.clone();
+        client.session_store.set(sub.clone(), session).await.unwrap();
+        // the revocation request fails, but the session is removed anyway
+        assert!(matches!(
+            client.revoke(&sub).await,
+            Err(Error::ServerAgent(crate::server_agent::Error::HttpStatus(
+                StatusCode::SERVICE_UNAVAILABLE
+            )))
+        ));
+        assert_eq!(client.session_store.get(&sub).await.unwrap(), None);
+    }
+
+    async fn get(
+        session: &OAuthSession<
+            MockHttpClient,
+            StaticDidResolver,
+            NoHandleResolver,
+            MemorySessionStore,
+        >,
+    ) -> bool {
+        session
+            .send_xrpc::<(), (), serde_json::Value, serde_json::Value>(&XrpcRequest {
+                method: Method::GET,
+                nsid: String::from("com.example.get"),
+                parameters: None,
+                input: None,
+                encoding: None,
+            })
+            .await
+            .is_ok()
+    }
+
+    #[tokio::test]
+    async fn refresh_invalid_token() -> Result<()> {
+        let client = oauth_client();
+        let session = session("https://issuer.test");
+        let sub = session.token_set.sub.clone();
+        client.session_store.set(sub.clone(), session.clone()).await.unwrap();
+        let oauth_session = client.restore(&sub).await?;
+        assert_eq!(oauth_session.base_uri(), "https://pds.test");
+        // concurrent requests rejected with the same token share a single refresh
+        assert_eq!(tokio::join!(get(&oauth_session), get(&oauth_session)), (true, true));
+        let refreshed = client.http_client.refreshed.lock().unwrap().clone();
+        assert_eq!(refreshed.len(), 1);
+        assert!(refreshed[0].contains("grant_type=refresh_token"));
+        assert!(refreshed[0].contains("refresh_token=refresh"));
+        assert_eq!(
+            client.http_client.authorized.lock().unwrap().as_slice(),
+            ["DPoP access", "DPoP access", "DPoP refreshed", "DPoP refreshed"]
+        );
+        // the refreshed tokens are saved with the same DPoP key
+        let stored = client.session_store.get(&sub).await.unwrap().expect("session not found");
+        assert_eq!(stored.dpop_key, session.dpop_key);
+        assert_eq!(stored.token_set.access_token, "refreshed");
+        assert_eq!(stored.token_set.refresh_token.as_deref(), Some("refresh2"));
+        assert_eq!(oauth_session.token_set(), stored.token_set);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn refresh_expired_token() -> Result<()> {
+        let client = oauth_client();
+        let mut session = session("https://issuer.test");
+        session.token_set.expires_at = Some(atrium_api::types::string::Datetime::new(
+            (Utc::now() - TimeDelta::minutes(1)).fixed_offset(),
+        ));
+        let sub = session.token_set.sub.clone();
+        client.session_store.set(sub.clone(), session).await.unwrap();
+        let oauth_session = client.restore(&sub).await?;
+        assert!(get(&oauth_session).await);
+        // the token is refreshed before the request is sent
+        assert_eq!(client.http_client.refreshed.lock().unwrap().len(), 1);
+        assert_eq!(client.http_client.authorized.lock().unwrap().as_slice(), ["DPoP refreshed"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn restore_unknown_session() {
+        let client = oauth_client();
+        assert!(matches!(
+            client.restore("did:plc:alice").await,
+            Err(Error::SessionNotFound(s)) if s == "did:plc:alice"
+        ));
+    }
+
+    fn client_metadata() -> OAuthClientMetadata {
+        OAuthClientMetadata {
+            client_id: String::from("https://app.example.com/client-metadata.json"),
+            client_uri: None,
+            redirect_uris: vec![
+                String::from("https://app.example.com/callback"),
+                String::from("com.example.app:/callback"),
+            ],
+            scope: Some(String::from("atproto transition:generic")),
+            grant_types: Some(vec![
+                String::from("authorization_code"),
+                String::from("refresh_token"),
+            ]),
+            token_endpoint_auth_method: Some(String::from("private_key_jwt")),
+            dpop_bound_access_tokens: Some(true),
+            jwks_uri: None,
+            jwks: None,
+            token_endpoint_auth_signing_alg: Some(String::from("ES256")),
+        }
+    }
+
+    fn server_metadata() -> OAuthAuthorizationServerMetadata {
+        serde_json::from_value(serde_json::json!({
+            "issuer": "https://bsky.social",
+            "authorization_endpoint": "https://bsky.social/oauth/authorize",
+            "token_endpoint": "https://bsky.social/oauth/token",
+            "pushed_authorization_request_endpoint": "https://bsky.social/oauth/par",
+            "scopes_supported": ["atproto", "transition:generic", "transition:chat.bsky"],
+            "response_types_supported": ["code"],
+            "grant_types_supported": ["authorization_code", "refresh_token"],
+            "code_challenge_methods_supported": ["S256"],
+            "token_endpoint_auth_methods_supported": ["none", "private_key_jwt"],
+            "token_endpoint_auth_signing_alg_values_supported": ["ES256", "ES256K"],
+            "dpop_signing_alg_values_supported": ["ES256", "ES256K"],
+            "client_id_metadata_document_supported": true,
+        }))
+        .expect("failed to deserialize metadata")
+    }
+
+    #[test]
+    fn check_valid_metadata() {
+        assert_eq!(check_metadata(&client_metadata(), &server_metadata()), []);
+    }
+
+    #[test]
+    fn check_invalid_metadata() {
+        let client = OAuthClientMetadata {
+            redirect_uris: vec![
+                String::from("http://app.example.com/callback"),
+                String::from("com.example.other:/callback"),
+            ],
+            scope: Some(String::from("atproto transition:email")),
+            dpop_bound_access_tokens: None,
+            ..client_metadata()
+        };
+        let server = OAuthAuthorizationServerMetadata {
+            pushed_authorization_request_endpoint: None,
+            token_endpoint_auth_methods_supported: Some(vec![String::from("none")]),
+            token_endpoint_auth_signing_alg_values_supported: Some(vec![String::from("ES256K")]),
+            dpop_signing_alg_values_supported: Some(vec![String::from("RS256")]),
+            ..server_metadata()
+        };
+        assert_eq!(
+            check_metadata(&client, &server),
+            [
+                MetadataWarning::UnsupportedScope(String::from("transition:email")),
+                MetadataWarning::UnsupportedAuthMethod(String::from("private_key_jwt")),
+                MetadataWarning::UnsupportedAuthSigningAlg(String::from("ES256")),
+                MetadataWarning::PushedAuthorizationRequestUnsupported,
+                MetadataWarning::UnsupportedDpopSigningAlgs,
+                MetadataWarning::DpopNotBound,
+                MetadataWarning::RedirectUri(String::from("http://app.example.com/callback")),
+                MetadataWarning::RedirectUri(String::from("com.example.other:/callback")),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_loopback_metadata() {
+        let client = OAuthClientMetadata {
+            client_id: String::from("http://localhost?scope=atproto"),
+            redirect_uris: vec![String::from("http://127.0.0.1/")],
+            scope: Some(String::from("atproto")),
+            token_endpoint_auth_method: Some(String::from("none")),
+            token_endpoint_auth_signing_alg: None,
+            ..client_metadata()
+        };
+        assert_eq!(check_metadata(&client, &server_metadata()), []);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::error::{Error, Result};
+use crate::http_client::dpop::DpopClient;
+use crate::server_agent::OAuthServerAgent;
+use crate::store::session::{Session, SessionStore};
+use crate::types::{OAuthTokenType, TokenSet};
+use atrium_identity::{did::DidResolver, handle::HandleResolver};
+use atrium_xrpc::http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use atrium_xrpc::http::{Request, Response, StatusCode};
+use atrium_xrpc::types::AuthorizationToken;
+use atrium_xrpc::{HttpClient, XrpcClient};
+use chrono::{TimeDelta, Utc};
+use std::sync::{Arc, RwLock};
+use tokio::sync::Mutex;
+
+/// An access token is refreshed this many seconds before it expires.
+const EXPIRY_MARGIN_SECS: i64 = 10;
+
+/// An authenticated session of a user, restored by
+/// [`OAuthClient::restore()`](crate::OAuthClient::restore).
+///
+/// XRPC requests are sent to the PDS of the user (the `aud` of the token set) with the access
+/// token, and a DPoP proof signed with the key the token is bound to.
+///
+/// The access token is refreshed when it is about to expire, or when the PDS rejects it with an
+/// `invalid_token` error, in which case the request is retried once. The refreshed token set is
+/// saved to the session store. Concurrent requests wait for a single refresh.
+pub struct OAuthSession<T, D, H, S>
+where
+    T: HttpClient + Send + Sync + 'static,
+{
+    server_agent: OAuthServerAgent<T, D, H>,
+    dpop_client: DpopClient<T>,
+    session_store: Arc<S>,
+    token_set: RwLock<TokenSet>,
+    refresh_lock: Mutex<()>,
+}
+
+impl<T, D, H, S> OAuthSession<T, D, H, S>
+where
+    T: HttpClient + Send + Sync + 'static,
+{
+    pub(crate) fn new(
+        server_agent: OAuthServerAgent<T, D, H>,
+        dpop_client: DpopClient<T>,
+        session_store: Arc<S>,
+        token_set: TokenSet,
+    ) -> Self {
+        Self {
+            server_agent,
+            dpop_client,
+            session_store,
+            token_set: RwLock::new(token_set),
+            refresh_lock: Mutex::new(()),
+        }
+    }
+    /// The current token set of the session.
+    pub fn token_set(&self) -> TokenSet {
+        self.token_set.read().expect("failed to read token set").clone()
+    }
+}
+
+impl<T, D, H, S> OAuthSession<T, D, H, S>
+where
+    T: HttpClient + Send + Sync + 'static,
+    D: DidResolver + Send + Sync + 'static,
+    H: HandleResolver + Send + Sync + 'static,
+    S: SessionStore + Send + Sync + 'static,
+{
+    /// Refresh the tokens, unless the access token `stale` has already been replaced by another
+    /// refresh.
+    async fn refresh(&self, stale: &str) -> Result<()> {
+        let _guard = self.refresh_lock.lock().await;
+        let token_set = self.token_set();
+        if token_set.access_token != stale {
+            return Ok(());
+        }
+        let refreshed = self.server_agent.refresh(&token_set).await?;
+        self.session_store
+            .set(
+                refreshed.sub.clone(),
+                Session { dpop_key: self.dpop_client.key.clone(), token_set: refreshed.clone() },
+            )
+            .await
+            .map_err(|e| Error::SessionStore(Box::new(e)))?;
+        *self.token_set.write().expect("failed to write token set") = refreshed;
+        Ok(())
+    }
+}
+
+impl<T, D, H, S> HttpClient for OAuthSession<T, D, H, S>
+where
+    T: HttpClient + Send + Sync + 'static,
+    D: DidResolver + Send + Sync + 'static,
+    H: HandleResolver + Send + Sync + 'static,
+    S: SessionStore + Send + Sync + 'static,
+{
+    async fn send_http(
+        &self,
+        mut request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        let response = self.dpop_client.send_http(request.clone()).await?;
+        if !is_invalid_token_error(&response) {
+            return Ok(response);
+        }
+        let Some(stale) = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("DPoP "))
+            .map(String::from)
+        else {
+            return Ok(response);
+        };
+        self.refresh(&stale).await?;
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, authorization_token(self.token_set()).try_into()?);
+        self.dpop_client.send_http(request).await
+    }
+}
+
+impl<T, D, H, S> XrpcClient for OAuthSession<T, D, H, S>
+where
+    T: HttpClient + Send + Sync + 'static,
+    D: DidResolver + Send + Sync + 'static,
+    H: HandleResolver + Send + Sync + 'static,
+    S: SessionStore + Send + Sync + 'static,
+{
+    fn base_uri(&self) -> String {
+        self.token_set().aud
+    }
+    async fn authorization_token(&self, _: bool) -> Option<AuthorizationToken> {
+        let token_set = self.token_set();
+        if is_expiring(&token_set) {
+            // If the refresh fails, the request is sent with the current token, and the refresh
+            // is retried (and its error returned) when the PDS rejects it.
+            if self.refresh(&token_set.access_token).await.is_ok() {
+                return Some(authorization_token(self.token_set()));
+            }
+        }
+        Some(authorization_token(token_set))
+    }
+}
+
+fn authorization_token(token_set: TokenSet) -> AuthorizationToken {
+    match token_set.token_type {
+        OAuthTokenType::DPoP => AuthorizationToken::Dpop(token_set.access_token),
+        OAuthTokenType::Bearer => AuthorizationToken::Bearer(token_set.access_token),
+    }
+}
+
+fn is_expiring(token_set: &TokenSet) -> bool {
+    token_set.expires_at.as_ref().is_some_and(|expires_at| {
+        *expires_at.as_ref() <= Utc::now() + TimeDelta::seconds(EXPIRY_MARGIN_SECS)
+    })
+}
+
+// https://datatracker.ietf.org/doc/html/rfc9449#section-7.1
+fn is_invalid_token_error(response: &Response<Vec<u8>>) -> bool {
+    response.status() == StatusCode::UNAUTHORIZED
+        && response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("DPoP") && v.contains(r#"error="invalid_token""#))
+}
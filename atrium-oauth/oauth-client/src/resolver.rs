@@ -18,10 +18,66 @@ use atrium_identity::identity_resolver::{
 use atrium_identity::{did::DidResolver, handle::HandleResolver};
 use atrium_identity::{Error, Result};
 use atrium_xrpc::HttpClient;
+use std::fmt;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::Duration;
 
+/// A stage of the resolution of an OAuth entry point by [`OAuthResolver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveStage {
+    /// Resolving a handle or a DID to the DID document and its PDS.
+    Identity,
+    /// Fetching the protected resource metadata of a PDS, to find its authorization server.
+    ProtectedResourceMetadata,
+    /// Fetching the metadata of an authorization server.
+    AuthorizationServerMetadata,
+}
+
+impl fmt::Display for ResolveStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Identity => "identity",
+            Self::ProtectedResourceMetadata => "protected resource metadata",
+            Self::AuthorizationServerMetadata => "authorization server metadata",
+        })
+    }
+}
+
+/// An error of [`OAuthResolver`], with the stage that failed and its input.
+#[derive(thiserror::Error, Debug)]
+#[error("failed to resolve {stage} for `{input}`: {source}")]
+pub struct ResolveError {
+    pub stage: ResolveStage,
+    pub input: String,
+    #[source]
+    pub source: Error,
+}
+
+type ResolveResult<T> = core::result::Result<T, ResolveError>;
+
+/// Run a stage of the resolution, and attach the stage and the input to its error.
+///
+/// With the `tracing` feature, the stage is also instrumented with a span.
+async fn stage<T>(
+    stage: ResolveStage,
+    input: &str,
+    future: impl Future<Output = Result<T>>,
+) -> ResolveResult<T> {
+    #[cfg(feature = "tracing")]
+    let future = tracing::Instrument::instrument(
+        future,
+        tracing::debug_span!("oauth_resolve", %stage, input),
+    );
+    let result = future.await;
+    #[cfg(feature = "tracing")]
+    if let Err(error) = &result {
+        tracing::debug!(%stage, input, %error, "oauth resolution failed");
+    }
+    result.map_err(|source| ResolveError { stage, input: input.into(), source })
+}
+
 #[derive(Clone, Debug)]
 pub struct OAuthAuthorizationServerMetadataResolverConfig {
     pub cache: CacheConfig,
@@ -112,12 +168,18 @@ where
     pub async fn get_authorization_server_metadata(
         &self,
         issuer: impl AsRef<str>,
-    ) -> Result<OAuthAuthorizationServerMetadata> {
-        let result =
-            self.authorization_server_resolver.resolve(&issuer.as_ref().to_string()).await?;
-        result.ok_or_else(|| Error::NotFound)
+    ) -> ResolveResult<OAuthAuthorizationServerMetadata> {
+        let issuer = issuer.as_ref();
+        stage(ResolveStage::AuthorizationServerMetadata, issuer, async {
+            let result = self.authorization_server_resolver.resolve(&issuer.to_string()).await?;
+            result.ok_or_else(|| Error::NotFound)
+        })
+        .await
     }
-    async fn resolve_from_service(&self, input: &str) -> Result<OAuthAuthorizationServerMetadata> {
+    async fn resolve_from_service(
+        &self,
+        input: &str,
+    ) -> ResolveResult<OAuthAuthorizationServerMetadata> {
         // Assume first that input is a PDS URL (as required by ATPROTO)
         if let Ok(metadata) = self.get_resource_server_metadata(input).await {
             return Ok(metadata);
@@ -128,42 +190,46 @@ where
     pub(crate) async fn resolve_from_identity(
         &self,
         input: &str,
-    ) -> Result<(OAuthAuthorizationServerMetadata, ResolvedIdentity)> {
-        let identity = self.identity_resolver.resolve(input).await?;
+    ) -> ResolveResult<(OAuthAuthorizationServerMetadata, ResolvedIdentity)> {
+        let identity =
+            stage(ResolveStage::Identity, input, self.identity_resolver.resolve(input)).await?;
         let metadata = self.get_resource_server_metadata(&identity.pds).await?;
         Ok((metadata, identity))
     }
     async fn get_resource_server_metadata(
         &self,
         pds: &str,
-    ) -> Result<OAuthAuthorizationServerMetadata> {
-        let result = self.protected_resource_resolver.resolve(&pds.to_string()).await?;
-        let rs_metadata = result.ok_or_else(|| Error::NotFound)?;
-        // ATPROTO requires one, and only one, authorization server entry
-        // > That document MUST contain a single item in the authorization_servers array.
-        // https://github.com/bluesky-social/proposals/tree/main/0004-oauth#server-metadata
-        let issuer = match &rs_metadata.authorization_servers {
-            Some(servers) if !servers.is_empty() => {
-                if servers.len() > 1 {
-                    return Err(Error::ProtectedResourceMetadata(format!(
-                        "unable to determine authorization server for PDS: {pds}"
-                    )));
+    ) -> ResolveResult<OAuthAuthorizationServerMetadata> {
+        let (resource, issuer) = stage(ResolveStage::ProtectedResourceMetadata, pds, async {
+            let result = self.protected_resource_resolver.resolve(&pds.to_string()).await?;
+            let rs_metadata = result.ok_or_else(|| Error::NotFound)?;
+            // ATPROTO requires one, and only one, authorization server entry
+            // > That document MUST contain a single item in the authorization_servers array.
+            // https://github.com/bluesky-social/proposals/tree/main/0004-oauth#server-metadata
+            match rs_metadata.authorization_servers {
+                Some(mut servers) if servers.len() == 1 => {
+                    Ok((rs_metadata.resource, servers.remove(0)))
                 }
-                &servers[0]
-            }
-            _ => {
-                return Err(Error::ProtectedResourceMetadata(format!(
+                Some(servers) if servers.len() > 1 => Err(Error::ProtectedResourceMetadata(
+                    format!("unable to determine authorization server for PDS: {pds}"),
+                )),
+                _ => Err(Error::ProtectedResourceMetadata(format!(
                     "no authorization server found for PDS: {pds}"
-                )))
+                ))),
             }
-        };
-        let as_metadata = self.get_authorization_server_metadata(issuer).await?;
+        })
+        .await?;
+        let as_metadata = self.get_authorization_server_metadata(&issuer).await?;
         // https://datatracker.ietf.org/doc/html/draft-ietf-oauth-resource-metadata-08#name-authorization-server-metada
         if let Some(protected_resources) = &as_metadata.protected_resources {
-            if !protected_resources.contains(&rs_metadata.resource) {
-                return Err(Error::AuthorizationServerMetadata(format!(
-                    "pds {pds} does not protected by issuer: {issuer}",
-                )));
+            if !protected_resources.contains(&resource) {
+                return Err(ResolveError {
+                    stage: ResolveStage::AuthorizationServerMetadata,
+                    input: issuer.clone(),
+                    source: Error::AuthorizationServerMetadata(format!(
+                        "pds {pds} does not protected by issuer: {issuer}",
+                    )),
+                });
             }
         }
 
@@ -190,9 +256,9 @@ where
 {
     type Input = str;
     type Output = (OAuthAuthorizationServerMetadata, Option<ResolvedIdentity>);
-    type Error = Error;
+    type Error = ResolveError;
 
-    async fn resolve(&self, input: &Self::Input) -> Result<Self::Output> {
+    async fn resolve(&self, input: &Self::Input) -> ResolveResult<Self::Output> {
         // Allow using an entryway, or PDS url, directly as login input (e.g.
         // when the user forgot their handle, or when the handle does not
         // resolve to a DID)
@@ -204,3 +270,103 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atrium_api::did_doc::{DidDocument, Service};
+    use atrium_api::types::string::{Did, Handle};
+    use atrium_identity::did::StaticDidResolver;
+    use atrium_xrpc::http::{Request, Response};
+
+    struct MockHttpClient;
+
+    impl HttpClient for MockHttpClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            let body = match (request.uri().host(), request.uri().path()) {
+                (Some("pds.test"), "/.well-known/oauth-protected-resource") => {
+                    serde_json::json!({
+                        "resource": "https://pds.test",
+                        "authorization_servers": ["https://issuer.test"],
+                        "scopes_supported": [],
+                    })
+                }
+                _ => return Ok(Response::builder().status(404).body(Vec::new())?),
+            };
+            Ok(Response::builder().status(200).body(serde_json::to_vec(&body)?)?)
+        }
+    }
+
+    struct StaticHandleResolver;
+
+    impl Resolver for StaticHandleResolver {
+        type Input = Handle;
+        type Output = Did;
+        type Error = Error;
+
+        async fn resolve(&self, handle: &Self::Input) -> Result<Self::Output> {
+            match handle.as_str() {
+                "alice.test" => Ok("did:plc:alice".parse().expect("did should be valid")),
+                _ => Err(Error::NotFound),
+            }
+        }
+    }
+
+    impl HandleResolver for StaticHandleResolver {}
+
+    fn resolver() -> OAuthResolver<MockHttpClient, StaticDidResolver, StaticHandleResolver> {
+        let did_document = DidDocument {
+            context: None,
+            id: String::from("did:plc:alice"),
+            also_known_as: Some(vec![String::from("at://alice.test")]),
+            verification_method: None,
+            service: Some(vec![Service {
+                id: String::from("#atproto_pds"),
+                r#type: String::from("AtprotoPersonalDataServer"),
+                service_endpoint: String::from("https://pds.test"),
+            }]),
+        };
+        OAuthResolver::new(
+            OAuthResolverConfig {
+                did_resolver: StaticDidResolver::new().with_document(
+                    "did:plc:alice".parse().expect("did should be valid"),
+                    did_document,
+                ),
+                handle_resolver: StaticHandleResolver,
+                authorization_server_metadata: Default::default(),
+                protected_resource_metadata: Default::default(),
+            },
+            Arc::new(MockHttpClient),
+        )
+    }
+
+    #[tokio::test]
+    async fn resolve_error_stage() {
+        let resolver = resolver();
+        let error = resolver.resolve("bob.test").await.expect_err("should fail");
+        assert_eq!((error.stage, error.input.as_str()), (ResolveStage::Identity, "bob.test"));
+        assert!(matches!(error.source, Error::NotFound));
+
+        let error = resolver.resolve("alice.test").await.expect_err("should fail");
+        assert_eq!(
+            (error.stage, error.input.as_str()),
+            (ResolveStage::AuthorizationServerMetadata, "https://issuer.test")
+        );
+        assert_eq!(
+            error.to_string(),
+            "failed to resolve authorization server metadata for `https://issuer.test`: http status: 404"
+        );
+
+        let error = resolver.resolve("https://entryway.test").await.expect_err("should fail");
+        assert_eq!(
+            (error.stage, error.input.as_str()),
+            (ResolveStage::AuthorizationServerMetadata, "https://entryway.test")
+        );
+    }
+}
@@ -1,17 +1,17 @@
 use crate::constants::FALLBACK_ALG;
-use crate::http_client::dpop::DpopClient;
+use crate::http_client::dpop::{DpopClient, DpopProofInspector};
 use crate::jose::jwt::{RegisteredClaims, RegisteredClaimsAud};
 use crate::keyset::Keyset;
 use crate::resolver::OAuthResolver;
 use crate::types::{
-    OAuthAuthorizationServerMetadata, OAuthClientMetadata, OAuthTokenResponse,
-    PushedAuthorizationRequestParameters, RefreshRequestParameters, TokenGrantType,
-    TokenRequestParameters, TokenSet,
+    OAuthAuthorizationServerMetadata, OAuthClientMetadata, OAuthErrorResponse, OAuthTokenResponse,
+    PushedAuthorizationRequestParameters, RefreshRequestParameters, RevocationRequestParameters,
+    TokenGrantType, TokenRequestParameters, TokenSet,
 };
 use crate::utils::{compare_algos, generate_nonce};
 use atrium_api::types::string::Datetime;
 use atrium_identity::{did::DidResolver, handle::HandleResolver};
-use atrium_xrpc::http::{Method, Request, StatusCode};
+use atrium_xrpc::http::{header, Method, Request, Response, StatusCode};
 use atrium_xrpc::HttpClient;
 use chrono::{TimeDelta, Utc};
 use jose_jwk::Key;
@@ -42,9 +42,13 @@ pub enum Error {
     HttpStatus(StatusCode),
     #[error("http status: {0}, body: {1:?}")]
     HttpStatusWithBody(StatusCode, Value),
+    #[error("http status: {0}, error: {1:?}")]
+    OAuth(StatusCode, OAuthErrorResponse),
     #[error(transparent)]
     Identity(#[from] atrium_identity::Error),
     #[error(transparent)]
+    Resolve(#[from] crate::resolver::ResolveError),
+    #[error(transparent)]
     Keyset(#[from] crate::keyset::Error),
     #[error(transparent)]
     SerdeHtmlForm(#[from] serde_html_form::ser::Error),
@@ -58,7 +62,7 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub enum OAuthRequest {
     Token(TokenRequestParameters),
     Refresh(RefreshRequestParameters),
-    Revocation,
+    Revocation(RevocationRequestParameters),
     Introspection,
     PushedAuthorizationRequest(PushedAuthorizationRequestParameters),
 }
@@ -68,14 +72,14 @@ impl OAuthRequest {
         String::from(match self {
             Self::Token(_) => "token",
             Self::Refresh(_) => "refresh",
-            Self::Revocation => "revocation",
+            Self::Revocation(_) => "revocation",
             Self::Introspection => "introspection",
             Self::PushedAuthorizationRequest(_) => "pushed_authorization_request",
         })
     }
     fn expected_status(&self) -> StatusCode {
         match self {
-            Self::Token(_) | Self::Refresh(_) => StatusCode::OK,
+            Self::Token(_) | Self::Refresh(_) | Self::Revocation(_) => StatusCode::OK,
             Self::PushedAuthorizationRequest(_) => StatusCode::CREATED,
             _ => unimplemented!(),
         }
@@ -120,13 +124,17 @@ where
         resolver: Arc<OAuthResolver<T, D, H>>,
         http_client: Arc<T>,
         keyset: Option<Keyset>,
+        proof_inspector: Option<DpopProofInspector>,
     ) -> Result<Self> {
-        let dpop_client = DpopClient::new(
+        let mut dpop_client = DpopClient::new(
             dpop_key,
             http_client,
             true,
             &server_metadata.token_endpoint_auth_signing_alg_values_supported,
         )?;
+        if let Some(inspector) = proof_inspector {
+            dpop_client = dpop_client.with_shared_proof_inspector(inspector);
+        }
         Ok(Self { server_metadata, client_metadata, dpop_client, resolver, keyset })
     }
     /**
@@ -175,16 +183,50 @@ where
         )
         .await
     }
+    /// Obtain a new token set with the refresh token of `token_set`.
+    ///
+    /// The new token set must be issued for the same user (`sub`) as `token_set`.
+    pub async fn refresh(&self, token_set: &TokenSet) -> Result<TokenSet> {
+        let Some(refresh_token) = &token_set.refresh_token else {
+            return Err(Error::Token("no refresh token available".into()));
+        };
+        let refreshed = self
+            .verify_token_response(
+                self.request(OAuthRequest::Refresh(RefreshRequestParameters {
+                    grant_type: TokenGrantType::RefreshToken,
+                    refresh_token: refresh_token.clone(),
+                    scope: None,
+                }))
+                .await?,
+            )
+            .await?;
+        if refreshed.sub != token_set.sub {
+            return Err(Error::Token("sub mismatch".into()));
+        }
+        Ok(refreshed)
+    }
+    pub async fn revoke(&self, token: &str) -> Result<()> {
+        self.send(OAuthRequest::Revocation(RevocationRequestParameters {
+            token: token.into(),
+            token_type_hint: None,
+        }))
+        .await?;
+        Ok(())
+    }
     pub async fn request<O>(&self, request: OAuthRequest) -> Result<O>
     where
         O: serde::de::DeserializeOwned,
     {
+        Ok(serde_json::from_slice(self.send(request).await?.body())?)
+    }
+    async fn send(&self, request: OAuthRequest) -> Result<Response<Vec<u8>>> {
         let Some(url) = self.endpoint(&request) else {
             return Err(Error::NoEndpoint(request.name()));
         };
         let body = match &request {
             OAuthRequest::Token(params) => self.build_body(params)?,
             OAuthRequest::Refresh(params) => self.build_body(params)?,
+            OAuthRequest::Revocation(params) => self.build_body(params)?,
             OAuthRequest::PushedAuthorizationRequest(params) => self.build_body(params)?,
             _ => unimplemented!(),
         };
@@ -195,8 +237,11 @@ where
             .body(body.into_bytes())?;
         let res = self.dpop_client.send_http(req).await.map_err(Error::HttpClient)?;
         if res.status() == request.expected_status() {
-            Ok(serde_json::from_slice(res.body())?)
+            Ok(res)
         } else if res.status().is_client_error() {
+            if let Some(error) = oauth_error_response(&res) {
+                return Err(Error::OAuth(res.status(), error));
+            }
             Err(Error::HttpStatusWithBody(res.status(), serde_json::from_slice(res.body())?))
         } else {
             Err(Error::HttpStatus(res.status()))
@@ -273,7 +318,7 @@ where
             OAuthRequest::Token(_) | OAuthRequest::Refresh(_) => {
                 Some(&self.server_metadata.token_endpoint)
             }
-            OAuthRequest::Revocation => self.server_metadata.revocation_endpoint.as_ref(),
+            OAuthRequest::Revocation(_) => self.server_metadata.revocation_endpoint.as_ref(),
             OAuthRequest::Introspection => self.server_metadata.introspection_endpoint.as_ref(),
             OAuthRequest::PushedAuthorizationRequest(_) => {
                 self.server_metadata.pushed_authorization_request_endpoint.as_ref()
@@ -281,3 +326,130 @@ where
         }
     }
 }
+
+/// Parse the body of an OAuth error response, which is sent as `application/json`.
+/// Other client error responses (e.g. HTML error pages from a proxy) are not parsed.
+///
+/// https://datatracker.ietf.org/doc/html/rfc6749#section-5.2
+/// https://datatracker.ietf.org/doc/html/rfc9126#section-2.3
+fn oauth_error_response(res: &Response<Vec<u8>>) -> Option<OAuthErrorResponse> {
+    let content_type = res.headers().get(header::CONTENT_TYPE)?.to_str().ok()?;
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    if !essence.eq_ignore_ascii_case("application/json") {
+        return None;
+    }
+    serde_json::from_slice(res.body()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver::OAuthResolverConfig;
+    use crate::utils::generate_key;
+    use atrium_api::types::string::{Did, Handle};
+    use atrium_common::resolver::Resolver;
+    use atrium_identity::did::StaticDidResolver;
+
+    struct MockHttpClient;
+
+    impl HttpClient for MockHttpClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            let body = String::from_utf8(request.body().clone())?;
+            let builder = Response::builder().status(400);
+            let error = br#"{"error":"invalid_request","error_description":"Bad token"}"#;
+            Ok(if body.contains("token=json") {
+                builder
+                    .header("Content-Type", "application/json; charset=utf-8")
+                    .body(error.to_vec())?
+            } else if body.contains("token=html") {
+                builder.header("Content-Type", "text/html").body(error.to_vec())?
+            } else {
+                builder
+                    .header("Content-Type", "application/json")
+                    .body(br#"{"message":"?"}"#.to_vec())?
+            })
+        }
+    }
+
+    struct NoHandleResolver;
+
+    impl Resolver for NoHandleResolver {
+        type Input = Handle;
+        type Output = Did;
+        type Error = atrium_identity::Error;
+
+        async fn resolve(&self, _: &Self::Input) -> atrium_identity::Result<Self::Output> {
+            Err(atrium_identity::Error::NotFound)
+        }
+    }
+
+    impl HandleResolver for NoHandleResolver {}
+
+    fn server_agent() -> OAuthServerAgent<MockHttpClient, StaticDidResolver, NoHandleResolver> {
+        let http_client = Arc::new(MockHttpClient);
+        let server_metadata = serde_json::from_value(serde_json::json!({
+            "issuer": "https://issuer.test",
+            "authorization_endpoint": "https://issuer.test/oauth/authorize",
+            "token_endpoint": "https://issuer.test/oauth/token",
+            "revocation_endpoint": "https://issuer.test/oauth/revoke",
+            "scopes_supported": ["atproto"],
+            "response_types_supported": ["code"],
+            "token_endpoint_auth_methods_supported": ["none"],
+        }))
+        .expect("failed to deserialize metadata");
+        let client_metadata = serde_json::from_value(serde_json::json!({
+            "client_id": "http://localhost",
+            "redirect_uris": ["http://127.0.0.1/"],
+            "token_endpoint_auth_method": "none",
+        }))
+        .expect("failed to deserialize metadata");
+        let resolver = OAuthResolver::new(
+            OAuthResolverConfig {
+                did_resolver: StaticDidResolver::new(),
+                handle_resolver: NoHandleResolver,
+                authorization_server_metadata: Default::default(),
+                protected_resource_metadata: Default::default(),
+            },
+            http_client.clone(),
+        );
+        OAuthServerAgent::new(
+            generate_key(&[String::from("ES256")]).expect("failed to generate key"),
+            server_metadata,
+            client_metadata,
+            Arc::new(resolver),
+            http_client,
+            None,
+            None,
+        )
+        .expect("failed to create server agent")
+    }
+
+    #[tokio::test]
+    async fn oauth_error_response() {
+        let agent = server_agent();
+        match agent.revoke("json").await {
+            Err(Error::OAuth(status, error)) => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(error.error, "invalid_request");
+                assert_eq!(error.error_description.as_deref(), Some("Bad token"));
+            }
+            result => panic!("unexpected result: {result:?}"),
+        }
+        // not parsed as an OAuth error without the JSON content type
+        assert!(matches!(
+            agent.revoke("html").await,
+            Err(Error::HttpStatusWithBody(StatusCode::BAD_REQUEST, _))
+        ));
+        // nor if the JSON is not an OAuth error
+        assert!(matches!(
+            agent.revoke("other").await,
+            Err(Error::HttpStatusWithBody(StatusCode::BAD_REQUEST, _))
+        ));
+    }
+}
@@ -1,4 +1,7 @@
+#[cfg(feature = "wasm")]
+pub mod local_storage;
 pub mod memory;
+pub mod session;
 pub mod state;
 
 use std::error::Error;
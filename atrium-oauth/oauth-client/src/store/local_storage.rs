@@ -0,0 +1,186 @@
+use super::session::{Session, SessionStore};
+use super::state::{InternalStateData, StateStore};
+use super::SimpleStore;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use thiserror::Error;
+use web_sys::Storage;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("localStorage is not available")]
+    Unavailable,
+    #[error("localStorage error: {0}")]
+    Storage(String),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+}
+
+/// A [`SimpleStore`] backed by the browser's [`localStorage`](https://developer.mozilla.org/docs/Web/API/Window/localStorage).
+///
+/// Values are stored as JSON, under their keys prefixed with the store's prefix.
+pub struct LocalStorageSimpleStore<K, V> {
+    prefix: String,
+    _phantom: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> LocalStorageSimpleStore<K, V> {
+    /// Create a new store whose entries are prefixed with `prefix`.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self { prefix: prefix.into(), _phantom: PhantomData }
+    }
+    fn storage() -> Result<Storage, Error> {
+        web_sys::window()
+            .ok_or(Error::Unavailable)?
+            .local_storage()
+            .map_err(|e| Error::Storage(format!("{e:?}")))?
+            .ok_or(Error::Unavailable)
+    }
+}
+
+impl<K, V> LocalStorageSimpleStore<K, V>
+where
+    K: AsRef<str>,
+{
+    fn storage_key(&self, key: &K) -> String {
+        format!("{}{}", self.prefix, key.as_ref())
+    }
+}
+
+impl<K, V> SimpleStore<K, V> for LocalStorageSimpleStore<K, V>
+where
+    K: AsRef<str> + Debug + Eq + Hash + Send + Sync + 'static,
+    V: Serialize + DeserializeOwned + Debug + Clone + Send + Sync + 'static,
+{
+    type Error = Error;
+
+    async fn get(&self, key: &K) -> Result<Option<V>, Self::Error> {
+        Self::storage()?
+            .get_item(&self.storage_key(key))
+            .map_err(|e| Error::Storage(format!("{e:?}")))?
+            .map(|value| serde_json::from_str(&value))
+            .transpose()
+            .map_err(Error::from)
+    }
+    async fn set(&self, key: K, value: V) -> Result<(), Self::Error> {
+        Self::storage()?
+            .set_item(&self.storage_key(&key), &serde_json::to_string(&value)?)
+            .map_err(|e| Error::Storage(format!("{e:?}")))
+    }
+    async fn del(&self, key: &K) -> Result<(), Self::Error> {
+        Self::storage()?
+            .remove_item(&self.storage_key(key))
+            .map_err(|e| Error::Storage(format!("{e:?}")))
+    }
+    async fn clear(&self) -> Result<(), Self::Error> {
+        let storage = Self::storage()?;
+        let len = storage.length().map_err(|e| Error::Storage(format!("{e:?}")))?;
+        let keys = (0..len)
+            .filter_map(|i| storage.key(i).ok().flatten())
+            .filter(|key| key.starts_with(&self.prefix))
+            .collect::<Vec<_>>();
+        for key in keys {
+            storage.remove_item(&key).map_err(|e| Error::Storage(format!("{e:?}")))?;
+        }
+        Ok(())
+    }
+}
+
+pub type LocalStorageStateStore = LocalStorageSimpleStore<String, InternalStateData>;
+
+impl Default for LocalStorageStateStore {
+    fn default() -> Self {
+        Self::new("atrium-oauth-state:")
+    }
+}
+
+impl StateStore for LocalStorageStateStore {}
+
+pub type LocalStorageSessionStore = LocalStorageSimpleStore<String, Session>;
+
+impl Default for LocalStorageSessionStore {
+    fn default() -> Self {
+        Self::new("atrium-oauth-session:")
+    }
+}
+
+impl SessionStore for LocalStorageSessionStore {}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::utils::generate_key;
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn state() -> InternalStateData {
+        InternalStateData {
+            iss: String::from("https://example.com"),
+            dpop_key: generate_key(&[String::from("ES256")]).expect("failed to generate key"),
+            verifier: String::from("verifier"),
+            iat: 0,
+        }
+    }
+
+    fn session() -> Session {
+        Session {
+            dpop_key: generate_key(&[String::from("ES256")]).expect("failed to generate key"),
+            token_set: serde_json::from_value(serde_json::json!({
+                "iss": "https://example.com",
+                "sub": "did:fake:handle.test",
+                "aud": "https://pds.example.com",
+                "scope": "atproto",
+                "refresh_token": "refresh",
+                "access_token": "access",
+                "token_type": "DPoP",
+            }))
+            .expect("failed to deserialize token set"),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn state_store() {
+        let store = LocalStorageStateStore::default();
+        let state = state();
+        assert_eq!(store.get(&String::from("foo")).await.expect("failed to get"), None);
+        store.set(String::from("foo"), state.clone()).await.expect("failed to set");
+        assert_eq!(store.get(&String::from("foo")).await.expect("failed to get"), Some(state));
+        store.del(&String::from("foo")).await.expect("failed to delete");
+        assert_eq!(store.get(&String::from("foo")).await.expect("failed to get"), None);
+    }
+
+    #[wasm_bindgen_test]
+    async fn session_store() {
+        let store = LocalStorageSessionStore::default();
+        let session = session();
+        let key = session.token_set.sub.clone();
+        store.set(key.clone(), session.clone()).await.expect("failed to set");
+        assert_eq!(store.get(&key).await.expect("failed to get"), Some(session));
+        // the stored value is JSON under the prefixed key
+        let stored = LocalStorageSessionStore::storage()
+            .expect("localStorage should be available")
+            .get_item("atrium-oauth-session:did:fake:handle.test")
+            .expect("failed to get item")
+            .expect("item should be stored");
+        assert!(serde_json::from_str::<Session>(&stored).is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    async fn clear_only_prefixed() {
+        let states = LocalStorageStateStore::default();
+        let sessions = LocalStorageSessionStore::default();
+        let session = session();
+        let key = session.token_set.sub.clone();
+        states.set(String::from("bar"), state()).await.expect("failed to set");
+        sessions.set(key.clone(), session.clone()).await.expect("failed to set");
+        states.clear().await.expect("failed to clear");
+        assert_eq!(states.get(&String::from("bar")).await.expect("failed to get"), None);
+        assert_eq!(sessions.get(&key).await.expect("failed to get"), Some(session));
+        sessions.clear().await.expect("failed to clear");
+        assert_eq!(sessions.get(&key).await.expect("failed to get"), None);
+    }
+}
@@ -20,6 +20,16 @@ impl<K, V> Default for MemorySimpleStore<K, V> {
     }
 }
 
+impl<K, V> MemorySimpleStore<K, V>
+where
+    K: Eq + Hash,
+{
+    /// Keep only the entries for which `f` returns `true`.
+    pub(crate) fn retain(&self, f: impl FnMut(&K, &mut V) -> bool) {
+        self.store.lock().unwrap().retain(f);
+    }
+}
+
 impl<K, V> SimpleStore<K, V> for MemorySimpleStore<K, V>
 where
     K: Debug + Eq + Hash + Send + Sync + 'static,
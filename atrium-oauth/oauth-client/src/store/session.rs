@@ -0,0 +1,19 @@
+use super::memory::MemorySimpleStore;
+use super::SimpleStore;
+use crate::types::TokenSet;
+use jose_jwk::Key;
+use serde::{Deserialize, Serialize};
+
+/// An OAuth session: the tokens issued to a user, with the DPoP key they are bound to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub dpop_key: Key,
+    pub token_set: TokenSet,
+}
+
+/// A store of [`Session`]s, keyed by the DIDs of their users (the `sub` of the token sets).
+pub trait SessionStore: SimpleStore<String, Session> {}
+
+pub type MemorySessionStore = MemorySimpleStore<String, Session>;
+
+impl SessionStore for MemorySessionStore {}
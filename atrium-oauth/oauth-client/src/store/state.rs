@@ -1,17 +1,90 @@
 use super::memory::MemorySimpleStore;
 use super::SimpleStore;
+use chrono::{TimeDelta, Utc};
 use jose_jwk::Key;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InternalStateData {
     pub iss: String,
     pub dpop_key: Key,
     pub verifier: String,
+    /// The time the state was created, as a UNIX timestamp in seconds.
+    ///
+    /// States stored without it are treated as expired.
+    #[serde(default)]
+    pub iat: i64,
 }
 
-pub trait StateStore: SimpleStore<String, InternalStateData> {}
+impl InternalStateData {
+    /// Returns whether the state is older than `ttl`.
+    pub fn is_expired(&self, ttl: TimeDelta) -> bool {
+        Utc::now().timestamp() - self.iat > ttl.num_seconds()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), trait_variant::make(Send))]
+pub trait StateStore: SimpleStore<String, InternalStateData> {
+    /// Remove the states older than `ttl`, e.g. of authorization flows that were abandoned.
+    ///
+    /// Called by [`OAuthClient::authorize()`](crate::OAuthClient::authorize) before a new state
+    /// is stored. Does nothing by default, e.g. for stores that expire entries by themselves.
+    fn purge_expired(&self, ttl: TimeDelta) -> impl Future<Output = Result<(), Self::Error>> {
+        let _ = ttl;
+        async { Ok(()) }
+    }
+}
 
 pub type MemoryStateStore = MemorySimpleStore<String, InternalStateData>;
 
-impl StateStore for MemoryStateStore {}
+impl StateStore for MemoryStateStore {
+    async fn purge_expired(&self, ttl: TimeDelta) -> Result<(), Self::Error> {
+        self.retain(|_, state| !state.is_expired(ttl));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generate_key;
+
+    #[test]
+    fn state_expiry() {
+        let key = generate_key(&[String::from("ES256")]).expect("failed to generate key");
+        let mut state = InternalStateData {
+            iss: String::from("https://example.com"),
+            dpop_key: key,
+            verifier: String::from("verifier"),
+            iat: Utc::now().timestamp(),
+        };
+        assert!(!state.is_expired(TimeDelta::minutes(10)));
+        state.iat -= 11 * 60;
+        assert!(state.is_expired(TimeDelta::minutes(10)));
+        // states stored before `iat` was added
+        let mut value = serde_json::to_value(&state).expect("failed to serialize state");
+        value.as_object_mut().expect("state should be an object").remove("iat");
+        let state = serde_json::from_value::<InternalStateData>(value)
+            .expect("failed to deserialize state");
+        assert!(state.is_expired(TimeDelta::minutes(10)));
+    }
+
+    #[tokio::test]
+    async fn purge_expired() {
+        let key = generate_key(&[String::from("ES256")]).expect("failed to generate key");
+        let state = |iat| InternalStateData {
+            iss: String::from("https://example.com"),
+            dpop_key: key.clone(),
+            verifier: String::from("verifier"),
+            iat,
+        };
+        let now = Utc::now().timestamp();
+        let store = MemoryStateStore::default();
+        store.set(String::from("fresh"), state(now)).await.expect("failed to set state");
+        store.set(String::from("stale"), state(now - 11 * 60)).await.expect("failed to set state");
+        store.purge_expired(TimeDelta::minutes(10)).await.expect("failed to purge states");
+        assert_eq!(store.get(&String::from("fresh")).await.expect("get"), Some(state(now)));
+        assert_eq!(store.get(&String::from("stale")).await.expect("get"), None);
+    }
+}
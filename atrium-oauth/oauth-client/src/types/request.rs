@@ -49,7 +49,6 @@ pub struct PushedAuthorizationRequestParameters {
 #[serde(rename_all = "snake_case")]
 pub enum TokenGrantType {
     AuthorizationCode,
-    #[allow(dead_code)]
     RefreshToken,
 }
 
@@ -70,3 +69,10 @@ pub struct RefreshRequestParameters {
     pub refresh_token: String,
     pub scope: Option<String>,
 }
+
+#[derive(Serialize)]
+pub struct RevocationRequestParameters {
+    // https://datatracker.ietf.org/doc/html/rfc7009#section-2.1
+    pub token: String,
+    pub token_type_hint: Option<String>,
+}
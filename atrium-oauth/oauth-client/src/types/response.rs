@@ -6,6 +6,14 @@ pub struct OAuthPusehedAuthorizationRequestResponse {
     pub expires_in: Option<u32>,
 }
 
+// https://datatracker.ietf.org/doc/html/rfc6749#section-5.2
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OAuthErrorResponse {
+    pub error: String,
+    pub error_description: Option<String>,
+    pub error_uri: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum OAuthTokenType {
     DPoP,
@@ -0,0 +1,180 @@
+#![doc = "XrpcClient implementation for [hyper]"]
+use atrium_xrpc::http::header::{HeaderValue, InvalidHeaderValue, USER_AGENT};
+use atrium_xrpc::http::{HeaderMap, Request, Response};
+use atrium_xrpc::{HttpClient, XrpcClient};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper_rustls::HttpsConnector;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::rt::TokioExecutor;
+use std::time::Duration;
+
+/// The [`hyper_util`] client type used by [`HyperClient`].
+pub type Client = hyper_util::client::legacy::Client<HttpsConnector<HttpConnector>, Full<Bytes>>;
+
+/// A [`hyper`] based asynchronous client to make XRPC requests with.
+///
+/// By default, TLS is provided by [`rustls`] with the Mozilla root certificates.
+/// To change the [`Client`] used internally or to set default headers,
+/// use the [`HyperClientBuilder`].
+///
+/// You do **not** have to wrap the `Client` in an [`Rc`] or [`Arc`] to **reuse** it,
+/// because it already uses an [`Arc`] internally.
+///
+/// [`Rc`]: std::rc::Rc
+/// [`Arc`]: std::sync::Arc
+#[derive(Clone)]
+pub struct HyperClient {
+    base_uri: String,
+    client: Client,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+}
+
+impl HyperClient {
+    /// Create a new [`HyperClient`] using the default configuration.
+    pub fn new(base_uri: impl AsRef<str>) -> Self {
+        HyperClientBuilder::new(base_uri).build()
+    }
+}
+
+/// A client builder, capable of creating custom [`HyperClient`] instances.
+pub struct HyperClientBuilder {
+    base_uri: String,
+    client: Option<Client>,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+}
+
+impl HyperClientBuilder {
+    /// Create a new [`HyperClientBuilder`] for building a custom client.
+    pub fn new(base_uri: impl AsRef<str>) -> Self {
+        Self {
+            base_uri: base_uri.as_ref().into(),
+            client: None,
+            headers: HeaderMap::new(),
+            timeout: None,
+        }
+    }
+    /// Sets the [`Client`] to use.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+    /// Sets the default headers for each request.
+    ///
+    /// Headers set by the request itself take precedence over these.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+    /// Sets the timeout for each request, including reading the response body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Sets the `User-Agent` header for each request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not a valid header value.
+    pub fn user_agent(mut self, user_agent: impl AsRef<str>) -> Result<Self, InvalidHeaderValue> {
+        self.headers.insert(USER_AGENT, HeaderValue::from_str(user_agent.as_ref())?);
+        Ok(self)
+    }
+    /// Build an [`HyperClient`] using the configured options.
+    pub fn build(self) -> HyperClient {
+        HyperClient {
+            base_uri: self.base_uri,
+            client: self.client.unwrap_or_else(default_client),
+            headers: self.headers,
+            timeout: self.timeout,
+        }
+    }
+}
+
+fn default_client() -> Client {
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_provider_and_webpki_roots(rustls::crypto::ring::default_provider())
+        .expect("failed to configure tls")
+        .https_or_http()
+        .enable_http1()
+        .build();
+    hyper_util::client::legacy::Client::builder(TokioExecutor::new()).build(connector)
+}
+
+impl HyperClient {
+    async fn send(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let (mut parts, body) = request.into_parts();
+        for (k, v) in &self.headers {
+            if !parts.headers.contains_key(k) {
+                parts.headers.insert(k, v.clone());
+            }
+        }
+        let response =
+            self.client.request(Request::from_parts(parts, Full::new(Bytes::from(body)))).await?;
+        let (parts, body) = response.into_parts();
+        let body = body.collect().await?.to_bytes();
+        Ok(Response::from_parts(parts, body.to_vec()))
+    }
+}
+
+impl HttpClient for HyperClient {
+    async fn send_http(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.send(request)).await?,
+            None => self.send(request).await,
+        }
+    }
+}
+
+impl XrpcClient for HyperClient {
+    fn base_uri(&self) -> String {
+        self.base_uri.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() -> Result<(), Box<dyn std::error::Error>> {
+        let client = HyperClient::new("http://localhost:8080");
+        assert_eq!(client.base_uri(), "http://localhost:8080");
+        Ok(())
+    }
+
+    #[test]
+    fn builder_without_client() -> Result<(), Box<dyn std::error::Error>> {
+        let client = HyperClientBuilder::new("http://localhost:8080").build();
+        assert_eq!(client.base_uri(), "http://localhost:8080");
+        Ok(())
+    }
+
+    #[test]
+    fn builder_with_client() -> Result<(), Box<dyn std::error::Error>> {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-custom", HeaderValue::from_static("value"));
+        let client = HyperClientBuilder::new("http://localhost:8080")
+            .client(default_client())
+            .headers(headers)
+            .user_agent("USER_AGENT")?
+            .timeout(Duration::from_millis(500))
+            .build();
+        assert_eq!(client.base_uri(), "http://localhost:8080");
+        assert_eq!(client.headers.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn builder_with_invalid_user_agent() {
+        assert!(HyperClientBuilder::new("http://localhost:8080").user_agent("invalid\n").is_err());
+    }
+}
@@ -1,8 +1,11 @@
 #![doc = "XrpcClient implementation for [isahc]"]
 use atrium_xrpc::http::{Request, Response};
 use atrium_xrpc::{HttpClient, XrpcClient};
+use isahc::config::Configurable;
+use isahc::http::header::{HeaderValue, InvalidHeaderValue, USER_AGENT};
 use isahc::{AsyncReadResponseExt, HttpClient as Client};
 use std::sync::Arc;
+use std::time::Duration;
 
 /// A [`isahc`] based asynchronous client to make XRPC requests with.
 ///
@@ -17,6 +20,8 @@ use std::sync::Arc;
 pub struct IsahcClient {
     base_uri: String,
     client: Client,
+    timeout: Option<Duration>,
+    user_agent: Option<HeaderValue>,
 }
 
 impl IsahcClient {
@@ -30,23 +35,41 @@ impl IsahcClient {
 pub struct IsahcClientBuilder {
     base_uri: String,
     client: Option<Client>,
+    timeout: Option<Duration>,
+    user_agent: Option<HeaderValue>,
 }
 
 impl IsahcClientBuilder {
     /// Create a new [`IsahcClientBuilder`] for building a custom client.
     pub fn new(base_uri: impl AsRef<str>) -> Self {
-        Self { base_uri: base_uri.as_ref().into(), client: None }
+        Self { base_uri: base_uri.as_ref().into(), client: None, timeout: None, user_agent: None }
     }
     /// Sets the [`isahc::HttpClient`] to use.
     pub fn client(mut self, client: Client) -> Self {
         self.client = Some(client);
         self
     }
+    /// Sets the timeout for each request, overriding the timeout of the client.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Sets the `User-Agent` header for each request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not a valid header value.
+    pub fn user_agent(mut self, user_agent: impl AsRef<str>) -> Result<Self, InvalidHeaderValue> {
+        self.user_agent = Some(HeaderValue::from_str(user_agent.as_ref())?);
+        Ok(self)
+    }
     /// Build an [`IsahcClient`] using the configured options.
     pub fn build(self) -> IsahcClient {
         IsahcClient {
             base_uri: self.base_uri,
             client: self.client.unwrap_or(Client::new().expect("failed to create isahc client")),
+            timeout: self.timeout,
+            user_agent: self.user_agent,
         }
     }
 }
@@ -62,6 +85,12 @@ impl HttpClient for IsahcClient {
         for (k, v) in &head.headers {
             request_builder = request_builder.header(k.as_str(), v.as_ref());
         }
+        if let Some(timeout) = self.timeout {
+            request_builder = request_builder.timeout(timeout);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            request_builder = request_builder.header(USER_AGENT, user_agent.clone());
+        }
         let mut response = self.client.send_async(request_builder.body(body)?).await?;
         let mut response_builder = Response::builder().status(response.status().as_u16());
         for (k, v) in response.headers() {
@@ -80,9 +109,7 @@ impl XrpcClient for IsahcClient {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use isahc::config::Configurable;
     use isahc::http;
-    use std::time::Duration;
 
     #[test]
     fn new() -> Result<(), Box<dyn std::error::Error>> {
@@ -111,4 +138,9 @@ mod tests {
         assert_eq!(client.base_uri(), "http://localhost:8080");
         Ok(())
     }
+
+    #[test]
+    fn builder_with_invalid_user_agent() {
+        assert!(IsahcClientBuilder::new("http://localhost:8080").user_agent("invalid\n").is_err());
+    }
 }
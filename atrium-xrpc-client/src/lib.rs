@@ -1,6 +1,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
 
+#[cfg_attr(docsrs, doc(cfg(feature = "hyper")))]
+#[cfg(feature = "hyper")]
+pub mod hyper;
 #[cfg_attr(docsrs, doc(cfg(feature = "isahc")))]
 #[cfg(feature = "isahc")]
 pub mod isahc;
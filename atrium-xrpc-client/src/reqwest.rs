@@ -1,10 +1,30 @@
 #![doc = "XrpcClient implementation for [reqwest]"]
 use atrium_xrpc::http::{Request, Response};
+#[cfg(not(target_arch = "wasm32"))]
+use atrium_xrpc::types::DownloadSink;
+#[cfg(all(feature = "upload-progress", not(target_arch = "wasm32")))]
+use atrium_xrpc::types::UploadProgress;
 use atrium_xrpc::{HttpClient, XrpcClient};
+#[cfg(all(feature = "upload-progress", not(target_arch = "wasm32")))]
+use reqwest::header::CONTENT_LENGTH;
+use reqwest::header::{HeaderValue, InvalidHeaderValue, USER_AGENT};
 use reqwest::Client;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+/// The size of the chunks in which a request body is sent when its progress is reported.
+#[cfg(all(feature = "upload-progress", not(target_arch = "wasm32")))]
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
 
 /// A [`reqwest`] based asynchronous client to make XRPC requests with.
 ///
+/// If a request has a [`DownloadSink`](atrium_xrpc::types::DownloadSink) extension,
+/// the body of a successful response is passed to it in chunks as they are downloaded.
+/// With the `upload-progress` feature, if a request has an
+/// [`UploadProgress`](atrium_xrpc::types::UploadProgress) extension, its body is streamed in
+/// chunks and the callback is called as each chunk is sent.
+/// Both extensions are ignored on `wasm32`.
+///
 /// To change the [`reqwest::Client`] used internally to a custom configured one,
 /// use the [`ReqwestClientBuilder`].
 ///
@@ -16,6 +36,9 @@ use reqwest::Client;
 pub struct ReqwestClient {
     base_uri: String,
     client: Client,
+    #[cfg(not(target_arch = "wasm32"))]
+    timeout: Option<Duration>,
+    user_agent: Option<HeaderValue>,
 }
 
 impl ReqwestClient {
@@ -29,21 +52,107 @@ impl ReqwestClient {
 pub struct ReqwestClientBuilder {
     base_uri: String,
     client: Option<Client>,
+    #[cfg(not(target_arch = "wasm32"))]
+    timeout: Option<Duration>,
+    user_agent: Option<HeaderValue>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pool_max_idle_per_host: Option<usize>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pool_idle_timeout: Option<Option<Duration>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    tcp_keepalive: Option<Option<Duration>>,
 }
 
 impl ReqwestClientBuilder {
     /// Create a new [`ReqwestClientBuilder`] for building a custom client.
     pub fn new(base_uri: impl AsRef<str>) -> Self {
-        Self { base_uri: base_uri.as_ref().into(), client: None }
+        Self {
+            base_uri: base_uri.as_ref().into(),
+            client: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            timeout: None,
+            user_agent: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pool_max_idle_per_host: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pool_idle_timeout: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            tcp_keepalive: None,
+        }
     }
     /// Sets the [`reqwest::Client`] to use.
     pub fn client(mut self, client: Client) -> Self {
         self.client = Some(client);
         self
     }
+    /// Sets the timeout for each request, overriding the timeout of the client.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Sets the `User-Agent` header for each request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not a valid header value.
+    pub fn user_agent(mut self, user_agent: impl AsRef<str>) -> Result<Self, InvalidHeaderValue> {
+        self.user_agent = Some(HeaderValue::from_str(user_agent.as_ref())?);
+        Ok(self)
+    }
+    /// Sets the maximum idle connections per host in the connection pool.
+    ///
+    /// This is ignored if a custom client is set with [`client()`](Self::client).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+    /// Sets the timeout for idle sockets in the connection pool. Pass `None` to disable it.
+    ///
+    /// This is ignored if a custom client is set with [`client()`](Self::client).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_idle_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.pool_idle_timeout = Some(timeout.into());
+        self
+    }
+    /// Sets the `SO_KEEPALIVE` interval for TCP connections. Pass `None` to disable it.
+    ///
+    /// This is ignored if a custom client is set with [`client()`](Self::client).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tcp_keepalive(mut self, interval: impl Into<Option<Duration>>) -> Self {
+        self.tcp_keepalive = Some(interval.into());
+        self
+    }
     /// Build an [`ReqwestClient`] using the configured options.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the default client cannot be built, as [`Client::new()`] does.
     pub fn build(self) -> ReqwestClient {
-        ReqwestClient { base_uri: self.base_uri, client: self.client.unwrap_or_default() }
+        #[cfg(not(target_arch = "wasm32"))]
+        let client = self.client.unwrap_or_else(|| {
+            let mut builder = Client::builder();
+            if let Some(max) = self.pool_max_idle_per_host {
+                builder = builder.pool_max_idle_per_host(max);
+            }
+            if let Some(timeout) = self.pool_idle_timeout {
+                builder = builder.pool_idle_timeout(timeout);
+            }
+            if let Some(interval) = self.tcp_keepalive {
+                builder = builder.tcp_keepalive(interval);
+            }
+            builder.build().expect("failed to build reqwest client")
+        });
+        #[cfg(target_arch = "wasm32")]
+        let client = self.client.unwrap_or_default();
+        ReqwestClient {
+            base_uri: self.base_uri,
+            client,
+            #[cfg(not(target_arch = "wasm32"))]
+            timeout: self.timeout,
+            user_agent: self.user_agent,
+        }
     }
 }
 
@@ -52,15 +161,81 @@ impl HttpClient for ReqwestClient {
         &self,
         request: Request<Vec<u8>>,
     ) -> Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>> {
-        let response = self.client.execute(request.try_into()?).await?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let sink = request.extensions().get::<DownloadSink>().cloned();
+        #[cfg(all(feature = "upload-progress", not(target_arch = "wasm32")))]
+        let progress = request
+            .extensions()
+            .get::<UploadProgress>()
+            .map(|progress| (progress.clone(), request.body().len() as u64));
+        #[cfg(all(feature = "upload-progress", not(target_arch = "wasm32")))]
+        let mut request = match &progress {
+            Some((progress, _)) => {
+                let (mut parts, body) = request.into_parts();
+                // Keep the length known, as a streamed body would otherwise be sent chunked.
+                parts.headers.insert(CONTENT_LENGTH, HeaderValue::from(body.len()));
+                reqwest::Request::try_from(Request::from_parts(
+                    parts,
+                    reqwest::Body::wrap_stream(progress_stream(body, progress.clone())),
+                ))?
+            }
+            None => reqwest::Request::try_from(request)?,
+        };
+        #[cfg(any(not(feature = "upload-progress"), target_arch = "wasm32"))]
+        let mut request = reqwest::Request::try_from(request)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(timeout) = self.timeout {
+            *request.timeout_mut() = Some(timeout);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            request.headers_mut().insert(USER_AGENT, user_agent.clone());
+        }
+        let response = self.client.execute(request).await?;
+        // The last chunk has been sent when the response is received.
+        #[cfg(all(feature = "upload-progress", not(target_arch = "wasm32")))]
+        if let Some((progress, total)) = progress {
+            progress.report(total, total);
+        }
         let mut builder = Response::builder().status(response.status());
         for (k, v) in response.headers() {
             builder = builder.header(k, v);
         }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(sink) = sink.filter(|_| response.status().is_success()) {
+            let mut response = response;
+            while let Some(chunk) = response.chunk().await? {
+                sink.write(&chunk);
+            }
+            return builder.body(Vec::new()).map_err(Into::into);
+        }
         builder.body(response.bytes().await?.to_vec()).map_err(Into::into)
     }
 }
 
+/// Split the body into chunks, reporting the progress as each chunk is sent.
+///
+/// A chunk is taken as sent when the next one is requested, since the HTTP client requests it
+/// only after writing the previous one. The last chunk is reported by the caller.
+#[cfg(all(feature = "upload-progress", not(target_arch = "wasm32")))]
+fn progress_stream(
+    body: Vec<u8>,
+    progress: UploadProgress,
+) -> impl futures::Stream<Item = Result<bytes::Bytes, std::convert::Infallible>> {
+    let body = bytes::Bytes::from(body);
+    let total = body.len() as u64;
+    let chunks = (0..body.len())
+        .step_by(UPLOAD_CHUNK_SIZE)
+        .map(move |start| body.slice(start..body.len().min(start + UPLOAD_CHUNK_SIZE)));
+    let mut sent = 0;
+    futures::stream::iter(chunks.map(move |chunk| {
+        if sent > 0 {
+            progress.report(sent, total);
+        }
+        sent += chunk.len() as u64;
+        Ok(chunk)
+    }))
+}
+
 impl XrpcClient for ReqwestClient {
     fn base_uri(&self) -> String {
         self.base_uri.clone()
@@ -70,8 +245,6 @@ impl XrpcClient for ReqwestClient {
 #[cfg(test)]
 mod tests {
     use super::*;
-    #[cfg(not(target_arch = "wasm32"))]
-    use std::time::Duration;
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
@@ -91,6 +264,18 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn builder_with_pool_options() -> Result<(), Box<dyn std::error::Error>> {
+        let client = ReqwestClientBuilder::new("http://localhost:8080")
+            .pool_max_idle_per_host(8)
+            .pool_idle_timeout(Duration::from_secs(30))
+            .tcp_keepalive(None)
+            .build();
+        assert_eq!(client.base_uri(), "http://localhost:8080");
+        Ok(())
+    }
+
     // TODO: Reqwest::Client doesn't have a `timeout` in wasm module
     // https://github.com/seanmonstar/reqwest/pull/1760
     #[cfg(not(target_arch = "wasm32"))]
@@ -107,4 +292,97 @@ mod tests {
         assert_eq!(client.base_uri(), "http://localhost:8080");
         Ok(())
     }
+
+    #[test]
+    fn builder_with_invalid_user_agent() {
+        assert!(ReqwestClientBuilder::new("http://localhost:8080")
+            .user_agent("invalid\n")
+            .is_err());
+    }
+
+    #[cfg(all(feature = "upload-progress", not(target_arch = "wasm32")))]
+    #[tokio::test]
+    async fn upload_progress() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use std::sync::{Arc, Mutex};
+
+        let body = vec![0x61; UPLOAD_CHUNK_SIZE * 2 + 1];
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/xrpc/com.atproto.repo.uploadBlob")
+            .match_header("content-length", body.len().to_string().as_str())
+            .match_body(body.clone())
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let progress = {
+            let reports = Arc::clone(&reports);
+            UploadProgress::new(move |sent, total| reports.lock().unwrap().push((sent, total)))
+        };
+        let mut request =
+            Request::post(format!("{}/xrpc/com.atproto.repo.uploadBlob", server.url()))
+                .body(body)?;
+        request.extensions_mut().insert(progress);
+        let response = ReqwestClient::new(server.url()).send_http(request).await?;
+        assert_eq!(response.status(), 200);
+        mock.assert_async().await;
+
+        let total = UPLOAD_CHUNK_SIZE as u64 * 2 + 1;
+        assert_eq!(
+            *reports.lock().unwrap(),
+            [
+                (UPLOAD_CHUNK_SIZE as u64, total),
+                (UPLOAD_CHUNK_SIZE as u64 * 2, total),
+                (total, total)
+            ]
+        );
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn download_sink() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use std::sync::{Arc, Mutex};
+
+        let body = vec![0x61; 256 * 1024];
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/xrpc/com.atproto.sync.getBlob")
+            .with_status(200)
+            .with_body(body.clone())
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/xrpc/com.atproto.sync.getRecord")
+            .with_status(400)
+            .with_body(r#"{"error":"RecordNotFound"}"#)
+            .create_async()
+            .await;
+
+        let downloaded = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let downloaded = Arc::clone(&downloaded);
+            DownloadSink::new(move |chunk| downloaded.lock().unwrap().extend_from_slice(chunk))
+        };
+        let client = ReqwestClient::new(server.url());
+        let mut request =
+            Request::get(format!("{}/xrpc/com.atproto.sync.getBlob", server.url())).body(vec![])?;
+        request.extensions_mut().insert(sink.clone());
+        let response = client.send_http(request).await?;
+        assert_eq!(response.status(), 200);
+        assert!(response.body().is_empty());
+        assert_eq!(*downloaded.lock().unwrap(), body);
+
+        // The body of an error response is returned as usual.
+        downloaded.lock().unwrap().clear();
+        let mut request = Request::get(format!("{}/xrpc/com.atproto.sync.getRecord", server.url()))
+            .body(vec![])?;
+        request.extensions_mut().insert(sink);
+        let response = client.send_http(request).await?;
+        assert_eq!(response.status(), 400);
+        assert_eq!(response.body(), br#"{"error":"RecordNotFound"}"#);
+        assert!(downloaded.lock().unwrap().is_empty());
+        Ok(())
+    }
 }
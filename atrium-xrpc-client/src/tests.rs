@@ -1,5 +1,5 @@
 use atrium_xrpc::http::Method;
-use atrium_xrpc::{InputDataOrBytes, OutputDataOrBytes, XrpcClient, XrpcRequest};
+use atrium_xrpc::{InputDataOrBytes, XrpcClient, XrpcRequest};
 use futures::future::join_all;
 use mockito::{Matcher, Server};
 use serde::{Deserialize, Serialize};
@@ -30,7 +30,7 @@ async fn run_query(
     client: impl XrpcClient + Send + Sync,
     path: String,
 ) -> Result<Output, atrium_xrpc::error::Error<Error>> {
-    let response = client
+    client
         .send_xrpc::<_, (), _, _>(&XrpcRequest {
             method: Method::GET,
             nsid: path,
@@ -38,18 +38,15 @@ async fn run_query(
             input: None,
             encoding: None,
         })
-        .await?;
-    match response {
-        OutputDataOrBytes::Bytes(_) => Err(atrium_xrpc::error::Error::UnexpectedResponseType),
-        OutputDataOrBytes::Data(out) => Ok(out),
-    }
+        .await?
+        .into_data()
 }
 
 async fn run_procedure(
     client: impl XrpcClient + Send + Sync,
     path: String,
 ) -> Result<Output, atrium_xrpc::error::Error<Error>> {
-    let response = client
+    client
         .send_xrpc::<(), _, _, _>(&XrpcRequest {
             method: Method::POST,
             nsid: path,
@@ -57,11 +54,8 @@ async fn run_procedure(
             input: Some(InputDataOrBytes::Data(Input { data: "foo".into() })),
             encoding: Some("application/json".into()),
         })
-        .await?;
-    match response {
-        OutputDataOrBytes::Bytes(_) => Err(atrium_xrpc::error::Error::UnexpectedResponseType),
-        OutputDataOrBytes::Data(out) => Ok(out),
-    }
+        .await?
+        .into_data()
 }
 
 #[tokio::test]
@@ -94,6 +88,8 @@ async fn send_query() -> Result<(), Box<dyn std::error::Error>> {
         path: &str,
     ) -> Vec<Result<Result<Output, atrium_xrpc::error::Error<Error>>, JoinError>> {
         let handles = vec![
+            #[cfg(feature = "hyper")]
+            tokio::spawn(run_query(crate::hyper::HyperClient::new(base_uri), path.to_string())),
             #[cfg(feature = "isahc")]
             tokio::spawn(run_query(
                 crate::isahc::IsahcClientBuilder::new(base_uri)
@@ -211,6 +207,8 @@ async fn send_procedure() -> Result<(), Box<dyn std::error::Error>> {
         path: &str,
     ) -> Vec<Result<Result<Output, atrium_xrpc::error::Error<Error>>, JoinError>> {
         let handles = vec![
+            #[cfg(feature = "hyper")]
+            tokio::spawn(run_procedure(crate::hyper::HyperClient::new(base_uri), path.to_string())),
             #[cfg(feature = "isahc")]
             tokio::spawn(run_procedure(
                 crate::isahc::IsahcClientBuilder::new(base_uri)
@@ -295,3 +293,53 @@ async fn send_procedure() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+#[tokio::test]
+async fn send_with_options() -> Result<(), Box<dyn std::error::Error>> {
+    let mut server = Server::new_async().await;
+    let mock_ok = server
+        .mock("GET", "/xrpc/test/ok")
+        .match_query(Matcher::UrlEncoded("query".into(), "foo".into()))
+        .match_header("user-agent", "atrium-test")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"data": "bar"}"#)
+        .create_async()
+        .await;
+
+    let base_uri = server.url();
+    let handles = vec![
+        #[cfg(feature = "hyper")]
+        tokio::spawn(run_query(
+            crate::hyper::HyperClientBuilder::new(&base_uri)
+                .timeout(std::time::Duration::from_secs(10))
+                .user_agent("atrium-test")?
+                .build(),
+            "test/ok".into(),
+        )),
+        #[cfg(feature = "isahc")]
+        tokio::spawn(run_query(
+            crate::isahc::IsahcClientBuilder::new(&base_uri)
+                .timeout(std::time::Duration::from_secs(10))
+                .user_agent("atrium-test")?
+                .build(),
+            "test/ok".into(),
+        )),
+        #[cfg(feature = "reqwest")]
+        tokio::spawn(run_query(
+            crate::reqwest::ReqwestClientBuilder::new(&base_uri)
+                .timeout(std::time::Duration::from_secs(10))
+                .user_agent("atrium-test")?
+                .build(),
+            "test/ok".into(),
+        )),
+    ];
+    let results = join_all(handles).await;
+    let len = results.len();
+    for result in results {
+        let output = result?.expect("xrpc response should be ok");
+        assert_eq!(output.data, "bar");
+    }
+    mock_ok.expect(len).assert_async().await;
+    Ok(())
+}
@@ -0,0 +1,192 @@
+//! Pluggable sources of authorization tokens for XRPC clients.
+use crate::types::AuthorizationToken;
+use crate::{HttpClient, XrpcClient};
+use http::{Request, Response};
+use std::future::Future;
+
+/// A source of authorization tokens.
+///
+/// This is the counterpart of [`XrpcClient::authorization_token()`], as a separate trait so that
+/// strategies (app passwords, OAuth with DPoP, service auth, ...) can be combined with any client
+/// by an [`AuthorizedClient`].
+#[cfg_attr(not(target_arch = "wasm32"), trait_variant::make(Send))]
+pub trait AuthorizationProvider {
+    /// Get the token to use in the `Authorization` header.
+    ///
+    /// `is_refresh` is `true` for the `com.atproto.server.refreshSession` request.
+    fn token(&self, is_refresh: bool) -> impl Future<Output = Option<AuthorizationToken>>;
+}
+
+/// A static token, which is used for all requests.
+impl AuthorizationProvider for AuthorizationToken {
+    async fn token(&self, _: bool) -> Option<AuthorizationToken> {
+        Some(self.clone())
+    }
+}
+
+/// An [`XrpcClient`] that sends requests with the inner client, authorized with the tokens of an
+/// [`AuthorizationProvider`].
+///
+/// # Example
+///
+/// ```
+/// use atrium_xrpc::auth::AuthorizedClient;
+/// use atrium_xrpc::types::AuthorizationToken;
+/// # use atrium_xrpc::{HttpClient, XrpcClient};
+/// # use atrium_xrpc::http::{Request, Response};
+/// # struct Client;
+/// # impl HttpClient for Client {
+/// #     async fn send_http(
+/// #         &self,
+/// #         _: Request<Vec<u8>>,
+/// #     ) -> Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+/// #         unimplemented!()
+/// #     }
+/// # }
+/// # impl XrpcClient for Client {
+/// #     fn base_uri(&self) -> String {
+/// #         unimplemented!()
+/// #     }
+/// # }
+///
+/// let client = AuthorizedClient::new(Client, AuthorizationToken::Bearer(String::from("token")));
+/// ```
+pub struct AuthorizedClient<C, P> {
+    client: C,
+    provider: P,
+}
+
+impl<C, P> AuthorizedClient<C, P> {
+    /// Create a new client authorized by the given provider.
+    pub fn new(client: C, provider: P) -> Self {
+        Self { client, provider }
+    }
+    /// Get a reference to the inner client.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+    /// Get a reference to the authorization provider.
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+}
+
+impl<C, P> HttpClient for AuthorizedClient<C, P>
+where
+    C: HttpClient + Send + Sync,
+    P: Send + Sync,
+{
+    async fn send_http(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        self.client.send_http(request).await
+    }
+}
+
+impl<C, P> XrpcClient for AuthorizedClient<C, P>
+where
+    C: XrpcClient + Send + Sync,
+    P: AuthorizationProvider + Send + Sync,
+{
+    fn base_uri(&self) -> String {
+        self.client.base_uri()
+    }
+    async fn authorization_token(&self, is_refresh: bool) -> Option<AuthorizationToken> {
+        self.provider.token(is_refresh).await
+    }
+    async fn atproto_proxy_header(&self) -> Option<String> {
+        self.client.atproto_proxy_header().await
+    }
+    async fn atproto_accept_labelers_header(&self) -> Option<Vec<String>> {
+        self.client.atproto_accept_labelers_header().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NSID_REFRESH_SESSION;
+    use crate::XrpcRequest;
+    use http::header::AUTHORIZATION;
+    use http::Method;
+    use std::sync::Mutex;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[derive(Default)]
+    struct RecordingClient {
+        authorizations: Mutex<Vec<Option<String>>>,
+    }
+
+    impl HttpClient for RecordingClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            self.authorizations.lock().expect("failed to lock").push(
+                request
+                    .headers()
+                    .get(AUTHORIZATION)
+                    .map(|value| value.to_str().expect("header should be a string").to_string()),
+            );
+            Ok(Response::builder().status(200).body(Vec::new())?)
+        }
+    }
+
+    impl XrpcClient for RecordingClient {
+        fn base_uri(&self) -> String {
+            String::from("https://example.com")
+        }
+    }
+
+    struct SessionProvider;
+
+    impl AuthorizationProvider for SessionProvider {
+        async fn token(&self, is_refresh: bool) -> Option<AuthorizationToken> {
+            Some(AuthorizationToken::Dpop(String::from(if is_refresh {
+                "refresh"
+            } else {
+                "access"
+            })))
+        }
+    }
+
+    async fn send<C: XrpcClient + Send + Sync>(client: &C, nsid: &str) {
+        client
+            .send_xrpc::<(), (), (), ()>(&XrpcRequest {
+                method: Method::POST,
+                nsid: nsid.into(),
+                parameters: None,
+                input: None,
+                encoding: None,
+            })
+            .await
+            .expect("request should succeed");
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn authorized_client() {
+        let client = AuthorizedClient::new(
+            RecordingClient::default(),
+            AuthorizationToken::Bearer(String::from("token")),
+        );
+        send(&client, "example").await;
+        assert_eq!(
+            *client.client().authorizations.lock().expect("failed to lock"),
+            [Some(String::from("Bearer token"))]
+        );
+        let client = AuthorizedClient::new(RecordingClient::default(), SessionProvider);
+        send(&client, "example").await;
+        send(&client, NSID_REFRESH_SESSION).await;
+        assert_eq!(
+            *client.client().authorizations.lock().expect("failed to lock"),
+            [Some(String::from("DPoP access")), Some(String::from("DPoP refresh"))]
+        );
+    }
+}
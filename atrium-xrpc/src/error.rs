@@ -21,6 +21,24 @@ where
     SerdeHtmlForm(#[from] serde_html_form::ser::Error),
     #[error("unexpected response type")]
     UnexpectedResponseType,
+    #[error("invalid endpoint: {0}")]
+    InvalidEndpoint(String),
+}
+
+impl<E> Error<E>
+where
+    E: Debug,
+{
+    /// Returns the error defined in the Lexicon schema, if the XRPC response contains one.
+    ///
+    /// This allows matching on the custom error without destructuring
+    /// [`Error::XrpcResponse`] and [`XrpcErrorKind::Custom`] by hand.
+    pub fn as_api_error(&self) -> Option<&E> {
+        match self {
+            Self::XrpcResponse(XrpcError { error: Some(XrpcErrorKind::Custom(e)), .. }) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 /// Type alias to use this library's [`Error`] type in a [`Result`](core::result::Result).
@@ -1,5 +1,13 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc = include_str!("../README.md")]
+pub mod auth;
 pub mod error;
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+#[cfg(feature = "tower")]
+pub mod tower;
 mod traits;
 pub mod types;
 
@@ -52,19 +60,15 @@ mod tests {
         where
             T: crate::XrpcClient + Send + Sync,
         {
-            let response = xrpc
-                .send_xrpc::<_, (), _, _>(&XrpcRequest {
-                    method: http::Method::GET,
-                    nsid: "example".into(),
-                    parameters: Some(params),
-                    input: None,
-                    encoding: None,
-                })
-                .await?;
-            match response {
-                crate::OutputDataOrBytes::Data(data) => Ok(data),
-                _ => Err(crate::Error::UnexpectedResponseType),
-            }
+            xrpc.send_xrpc::<_, (), _, _>(&XrpcRequest {
+                method: http::Method::GET,
+                nsid: "example".into(),
+                parameters: Some(params),
+                input: None,
+                encoding: None,
+            })
+            .await?
+            .into_data()
         }
 
         #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -149,6 +153,10 @@ mod tests {
                 }
                 _ => panic!("must be Error::XrpcResponse, got {error:?}"),
             }
+            assert_eq!(
+                error.as_api_error(),
+                Some(&Error::InvalidToken(Some(String::from("Message"))))
+            );
         }
 
         #[tokio::test]
@@ -178,6 +186,7 @@ mod tests {
                 }
                 _ => panic!("must be Error::XrpcResponse, got {error:?}"),
             };
+            assert_eq!(error.as_api_error(), None);
         }
     }
 
@@ -191,19 +200,15 @@ mod tests {
             where
                 T: crate::XrpcClient + Send + Sync,
             {
-                let response = xrpc
-                    .send_xrpc::<_, (), (), _>(&XrpcRequest {
-                        method: http::Method::GET,
-                        nsid: "example".into(),
-                        parameters: Some(params),
-                        input: None,
-                        encoding: None,
-                    })
-                    .await?;
-                match response {
-                    crate::OutputDataOrBytes::Bytes(bytes) => Ok(bytes),
-                    _ => Err(crate::Error::UnexpectedResponseType),
-                }
+                xrpc.send_xrpc::<_, (), (), _>(&XrpcRequest {
+                    method: http::Method::GET,
+                    nsid: "example".into(),
+                    parameters: Some(params),
+                    input: None,
+                    encoding: None,
+                })
+                .await?
+                .into_bytes()
             }
 
             #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -313,19 +318,15 @@ mod tests {
             where
                 T: crate::XrpcClient + Send + Sync,
             {
-                let response = xrpc
-                    .send_xrpc::<(), Vec<u8>, _, _>(&XrpcRequest {
-                        method: http::Method::POST,
-                        nsid: "example".into(),
-                        parameters: None,
-                        input: Some(InputDataOrBytes::Bytes(input)),
-                        encoding: None,
-                    })
-                    .await?;
-                match response {
-                    crate::OutputDataOrBytes::Data(data) => Ok(data),
-                    _ => Err(crate::Error::UnexpectedResponseType),
-                }
+                xrpc.send_xrpc::<(), Vec<u8>, _, _>(&XrpcRequest {
+                    method: http::Method::POST,
+                    nsid: "example".into(),
+                    parameters: None,
+                    input: Some(InputDataOrBytes::Bytes(input)),
+                    encoding: None,
+                })
+                .await?
+                .into_data()
             }
 
             #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -0,0 +1,238 @@
+#![doc = "Utilities for testing code that uses XRPC clients."]
+use crate::traits::{HttpClient, XrpcClient};
+use http::{HeaderMap, Request, Response, StatusCode};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone)]
+struct MockResponse {
+    status: StatusCode,
+    content_type: Option<&'static str>,
+    body: Vec<u8>,
+}
+
+/// A request received by [`MockXrpcClient`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: http::Method,
+    pub nsid: String,
+    pub query: Option<String>,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+/// A mock [`XrpcClient`] which returns canned responses registered by NSID.
+///
+/// Requests for NSIDs without a registered response receive
+/// `401 Unauthorized` with an `AuthenticationRequired` error.
+///
+/// # Example
+///
+/// ```
+/// use atrium_xrpc::test_util::MockXrpcClient;
+///
+/// let client = MockXrpcClient::default();
+/// client.expect("com.example.getValue").returns(&serde_json::json!({"value": 42}));
+/// assert_eq!(client.call_count("com.example.getValue"), 0);
+/// ```
+#[derive(Clone)]
+pub struct MockXrpcClient {
+    base_uri: String,
+    responses: Arc<Mutex<HashMap<String, MockResponse>>>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockXrpcClient {
+    /// Create a new mock client with the given base URI.
+    pub fn new(base_uri: impl AsRef<str>) -> Self {
+        Self {
+            base_uri: base_uri.as_ref().into(),
+            responses: Arc::new(Mutex::new(HashMap::new())),
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+    /// Start registering a response for the given NSID.
+    pub fn expect(&self, nsid: impl AsRef<str>) -> Expectation<'_> {
+        Expectation { client: self, nsid: nsid.as_ref().into() }
+    }
+    /// Returns the number of requests received for the given NSID.
+    pub fn call_count(&self, nsid: impl AsRef<str>) -> usize {
+        self.requests
+            .lock()
+            .expect("failed to lock requests")
+            .iter()
+            .filter(|r| r.nsid == nsid.as_ref())
+            .count()
+    }
+    /// Returns all requests received so far, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().expect("failed to lock requests").clone()
+    }
+    /// Returns the headers of the requests received for the given NSID, in order.
+    pub fn headers(&self, nsid: impl AsRef<str>) -> Vec<HeaderMap> {
+        self.requests
+            .lock()
+            .expect("failed to lock requests")
+            .iter()
+            .filter(|r| r.nsid == nsid.as_ref())
+            .map(|r| r.headers.clone())
+            .collect()
+    }
+    fn register(&self, nsid: String, response: MockResponse) {
+        self.responses.lock().expect("failed to lock responses").insert(nsid, response);
+    }
+}
+
+impl Default for MockXrpcClient {
+    fn default() -> Self {
+        Self::new("http://localhost:8080")
+    }
+}
+
+impl HttpClient for MockXrpcClient {
+    async fn send_http(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        let nsid = request.uri().path().trim_start_matches("/xrpc/").to_string();
+        let response = self.responses.lock().expect("failed to lock responses").get(&nsid).cloned();
+        let (parts, body) = request.into_parts();
+        self.requests.lock().expect("failed to lock requests").push(RecordedRequest {
+            method: parts.method,
+            nsid,
+            query: parts.uri.query().map(String::from),
+            headers: parts.headers,
+            body,
+        });
+        let response = response.unwrap_or_else(|| MockResponse {
+            status: StatusCode::UNAUTHORIZED,
+            content_type: Some("application/json"),
+            body: br#"{"error":"AuthenticationRequired","message":"Authentication Required"}"#
+                .to_vec(),
+        });
+        let mut builder = Response::builder().status(response.status);
+        if let Some(content_type) = response.content_type {
+            builder = builder.header(http::header::CONTENT_TYPE, content_type);
+        }
+        Ok(builder.body(response.body)?)
+    }
+}
+
+impl XrpcClient for MockXrpcClient {
+    fn base_uri(&self) -> String {
+        self.base_uri.clone()
+    }
+}
+
+/// A pending registration of a response, created by [`MockXrpcClient::expect()`].
+pub struct Expectation<'a> {
+    client: &'a MockXrpcClient,
+    nsid: String,
+}
+
+impl Expectation<'_> {
+    /// Respond with `200 OK` and the given output serialized as JSON.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output cannot be serialized.
+    pub fn returns<T: Serialize>(self, output: &T) {
+        self.client.register(
+            self.nsid,
+            MockResponse {
+                status: StatusCode::OK,
+                content_type: Some("application/json"),
+                body: serde_json::to_vec(output).expect("failed to serialize output"),
+            },
+        );
+    }
+    /// Respond with `200 OK` and the given raw bytes.
+    pub fn returns_bytes(self, bytes: Vec<u8>) {
+        self.client.register(
+            self.nsid,
+            MockResponse { status: StatusCode::OK, content_type: None, body: bytes },
+        );
+    }
+    /// Respond with the given status and an XRPC error body.
+    pub fn returns_error(self, status: StatusCode, error: &str, message: Option<&str>) {
+        self.client.register(
+            self.nsid,
+            MockResponse {
+                status,
+                content_type: Some("application/json"),
+                body: serde_json::to_vec(&crate::error::ErrorResponseBody {
+                    error: Some(error.into()),
+                    message: message.map(String::from),
+                })
+                .expect("failed to serialize error"),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Error, ErrorResponseBody, XrpcErrorKind};
+    use crate::{OutputDataOrBytes, XrpcRequest};
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
+    struct Output {
+        value: i32,
+    }
+
+    fn request(nsid: &str) -> XrpcRequest<(), ()> {
+        XrpcRequest {
+            method: http::Method::GET,
+            nsid: nsid.into(),
+            parameters: None,
+            input: None,
+            encoding: None,
+        }
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn registered_response() {
+        let client = MockXrpcClient::default();
+        client.expect("com.example.get").returns(&Output { value: 42 });
+        let output = client
+            .send_xrpc::<_, _, Output, ()>(&request("com.example.get"))
+            .await
+            .expect("request should succeed");
+        match output {
+            OutputDataOrBytes::Data(data) => assert_eq!(data, Output { value: 42 }),
+            OutputDataOrBytes::Bytes(_) => panic!("unexpected bytes response"),
+        }
+        assert_eq!(client.call_count("com.example.get"), 1);
+        assert_eq!(client.call_count("com.example.other"), 0);
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn unregistered_response() {
+        let client = MockXrpcClient::default();
+        let Err(error) = client.send_xrpc::<_, _, Output, ()>(&request("com.example.get")).await
+        else {
+            panic!("request should fail");
+        };
+        match error {
+            Error::XrpcResponse(err) => {
+                assert_eq!(err.status, StatusCode::UNAUTHORIZED);
+                assert_eq!(
+                    err.error,
+                    Some(XrpcErrorKind::Undefined(ErrorResponseBody {
+                        error: Some(String::from("AuthenticationRequired")),
+                        message: Some(String::from("Authentication Required")),
+                    }))
+                );
+            }
+            _ => panic!("must be Error::XrpcResponse, got {error:?}"),
+        }
+        assert_eq!(client.call_count("com.example.get"), 1);
+    }
+}
@@ -0,0 +1,174 @@
+#![doc = "Adapters between [`tower`](::tower) services and XRPC clients."]
+use crate::error::Error;
+use crate::traits::{HttpClient, XrpcClient};
+use crate::{OutputDataOrBytes, XrpcRequest};
+use ::tower::{Service, ServiceExt};
+use http::{Request, Response};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+#[cfg(not(target_arch = "wasm32"))]
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+#[cfg(target_arch = "wasm32")]
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+/// `Send` and `Sync` bounds that apply only where the client traits require `Send` futures,
+/// as with `trait_variant::make(Send)` on targets other than wasm32.
+mod sealed {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub trait MaybeSend: Send {}
+    #[cfg(not(target_arch = "wasm32"))]
+    impl<T: Send + ?Sized> MaybeSend for T {}
+    #[cfg(target_arch = "wasm32")]
+    pub trait MaybeSend {}
+    #[cfg(target_arch = "wasm32")]
+    impl<T: ?Sized> MaybeSend for T {}
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub trait MaybeSync: Sync {}
+    #[cfg(not(target_arch = "wasm32"))]
+    impl<T: Sync + ?Sized> MaybeSync for T {}
+    #[cfg(target_arch = "wasm32")]
+    pub trait MaybeSync {}
+    #[cfg(target_arch = "wasm32")]
+    impl<T: ?Sized> MaybeSync for T {}
+}
+
+use self::sealed::{MaybeSend, MaybeSync};
+
+/// A [`Service`] that sends [`XrpcRequest`]s with an [`XrpcClient`].
+///
+/// This allows composing any [`XrpcClient`] with `tower` middleware such as timeouts,
+/// concurrency limits or tracing.
+///
+/// The output type `O` and the error type `E` correspond to the type parameters of
+/// [`XrpcClient::send_xrpc()`].
+pub struct XrpcService<C, O, E> {
+    client: Arc<C>,
+    _phantom: PhantomData<fn() -> (O, E)>,
+}
+
+impl<C, O, E> XrpcService<C, O, E> {
+    /// Create a new service that sends requests with the given client.
+    pub fn new(client: impl Into<Arc<C>>) -> Self {
+        Self { client: client.into(), _phantom: PhantomData }
+    }
+}
+
+impl<C, O, E> Clone for XrpcService<C, O, E> {
+    fn clone(&self) -> Self {
+        Self { client: Arc::clone(&self.client), _phantom: PhantomData }
+    }
+}
+
+impl<C, P, I, O, E> Service<XrpcRequest<P, I>> for XrpcService<C, O, E>
+where
+    C: XrpcClient + MaybeSend + MaybeSync + 'static,
+    P: Serialize + Send + Sync + 'static,
+    I: Serialize + Send + Sync + 'static,
+    O: DeserializeOwned + Send + Sync + 'static,
+    E: DeserializeOwned + Send + Sync + Debug + 'static,
+{
+    type Response = OutputDataOrBytes<O>;
+    type Error = Error<E>;
+    type Future = BoxFuture<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+    fn call(&mut self, request: XrpcRequest<P, I>) -> Self::Future {
+        let client = Arc::clone(&self.client);
+        Box::pin(async move { client.send_xrpc(&request).await })
+    }
+}
+
+/// An [`HttpClient`] (and [`XrpcClient`]) backed by a [`Service`]
+/// that takes an [`http::Request`] and returns an [`http::Response`].
+///
+/// The inner service is cloned for each request, so it should be cheap to clone
+/// (as most `tower` services are).
+#[derive(Clone)]
+pub struct HttpService<S> {
+    base_uri: String,
+    service: S,
+}
+
+impl<S> HttpService<S> {
+    /// Create a new client that sends requests to `base_uri` via the given service.
+    pub fn new(base_uri: impl AsRef<str>, service: S) -> Self {
+        Self { base_uri: base_uri.as_ref().into(), service }
+    }
+}
+
+impl<S> HttpClient for HttpService<S>
+where
+    S: Service<Request<Vec<u8>>, Response = Response<Vec<u8>>> + Clone + MaybeSend + MaybeSync,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    S::Future: MaybeSend,
+{
+    async fn send_http(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        self.service.clone().oneshot(request).await.map_err(Into::into)
+    }
+}
+
+impl<S> XrpcClient for HttpService<S>
+where
+    S: Service<Request<Vec<u8>>, Response = Response<Vec<u8>>> + Clone + MaybeSend + MaybeSync,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    S::Future: MaybeSend,
+{
+    fn base_uri(&self) -> String {
+        self.base_uri.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::tower::service_fn;
+    use http::Method;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct Output {
+        path: String,
+    }
+
+    #[tokio::test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    async fn send_xrpc_via_services() {
+        let client = HttpService::new(
+            "https://example.com",
+            service_fn(|request: Request<Vec<u8>>| async move {
+                Response::builder()
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(format!(r#"{{"path":"{}"}}"#, request.uri().path()).into_bytes())
+            }),
+        );
+        let response = XrpcService::<_, Output, ()>::new(client)
+            .oneshot(XrpcRequest::<(), ()> {
+                method: Method::GET,
+                nsid: String::from("com.example.get"),
+                parameters: None,
+                input: None,
+                encoding: None,
+            })
+            .await
+            .expect("request should succeed");
+        match response {
+            OutputDataOrBytes::Data(output) => assert_eq!(output.path, "/xrpc/com.example.get"),
+            OutputDataOrBytes::Bytes(_) => panic!("unexpected bytes response"),
+        }
+    }
+}
@@ -3,7 +3,11 @@ use crate::types::{AuthorizationToken, Header, NSID_REFRESH_SESSION};
 use crate::{InputDataOrBytes, OutputDataOrBytes, XrpcRequest};
 use http::{Method, Request, Response};
 use serde::{de::DeserializeOwned, Serialize};
+#[cfg(all(any(feature = "metrics", feature = "tracing"), not(target_arch = "wasm32")))]
+use std::time::Instant;
 use std::{fmt::Debug, future::Future};
+#[cfg(all(any(feature = "metrics", feature = "tracing"), target_arch = "wasm32"))]
+use web_time::Instant;
 
 /// An abstract HTTP client.
 #[cfg_attr(not(target_arch = "wasm32"), trait_variant::make(Send))]
@@ -78,11 +82,43 @@ pub trait XrpcClient: HttpClient {
     }
 }
 
+#[cfg(not(feature = "tracing"))]
 #[inline(always)]
 async fn send_xrpc<P, I, O, E, C: XrpcClient + ?Sized>(
     client: &C,
     request: &XrpcRequest<P, I>,
 ) -> XrpcResult<O, E>
+where
+    P: Serialize + Send + Sync,
+    I: Serialize + Send + Sync,
+    O: DeserializeOwned + Send + Sync,
+    E: DeserializeOwned + Send + Sync + Debug,
+{
+    send_xrpc_inner(client, request).await
+}
+
+#[cfg(feature = "tracing")]
+async fn send_xrpc<P, I, O, E, C: XrpcClient + ?Sized>(
+    client: &C,
+    request: &XrpcRequest<P, I>,
+) -> XrpcResult<O, E>
+where
+    P: Serialize + Send + Sync,
+    I: Serialize + Send + Sync,
+    O: DeserializeOwned + Send + Sync,
+    E: DeserializeOwned + Send + Sync + Debug,
+{
+    use tracing::Instrument;
+
+    let span = tracing::debug_span!("send_xrpc", nsid = %request.nsid, method = %request.method);
+    send_xrpc_inner(client, request).instrument(span).await
+}
+
+#[inline(always)]
+async fn send_xrpc_inner<P, I, O, E, C: XrpcClient + ?Sized>(
+    client: &C,
+    request: &XrpcRequest<P, I>,
+) -> XrpcResult<O, E>
 where
     P: Serialize + Send + Sync,
     I: Serialize + Send + Sync,
@@ -124,8 +160,28 @@ where
         Vec::new()
     };
     // Send
-    let (parts, body) =
-        client.send_http(builder.body(body)?).await.map_err(Error::HttpClient)?.into_parts();
+    #[cfg(feature = "metrics")]
+    let nsid = request.nsid.clone();
+    let request = builder.body(body)?;
+    #[cfg(feature = "tracing")]
+    tracing::trace!(uri = %request.uri(), headers = ?redacted_headers(request.headers()), "sending xrpc request");
+    #[cfg(any(feature = "metrics", feature = "tracing"))]
+    let start = Instant::now();
+    let result = client.send_http(request).await;
+    #[cfg(feature = "metrics")]
+    record_metrics(nsid, &result, start);
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(response) => tracing::debug!(
+            status = response.status().as_u16(),
+            elapsed = ?start.elapsed(),
+            "xrpc request completed"
+        ),
+        Err(err) => {
+            tracing::debug!(error = %err, elapsed = ?start.elapsed(), "xrpc request failed")
+        }
+    }
+    let (parts, body) = result.map_err(Error::HttpClient)?.into_parts();
     if parts.status.is_success() {
         if parts
             .headers
@@ -144,3 +200,104 @@ where
         }))
     }
 }
+
+#[cfg(feature = "metrics")]
+fn record_metrics<T, E>(
+    nsid: String,
+    result: &core::result::Result<Response<T>, E>,
+    start: Instant,
+) {
+    // Failures of the HTTP client itself have no status code.
+    let status = match result {
+        Ok(response) => response.status().as_str().to_string(),
+        Err(_) => String::from("error"),
+    };
+    metrics::counter!("atrium_xrpc_requests_total", "nsid" => nsid.clone(), "status" => status)
+        .increment(1);
+    metrics::histogram!("atrium_xrpc_request_duration_seconds", "nsid" => nsid)
+        .record(start.elapsed().as_secs_f64());
+}
+
+#[cfg(feature = "tracing")]
+fn redacted_headers(headers: &http::HeaderMap) -> Vec<(&str, &str)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            if name == http::header::AUTHORIZATION {
+                (name.as_str(), "<redacted>")
+            } else {
+                (name.as_str(), value.to_str().unwrap_or("<non-utf8>"))
+            }
+        })
+        .collect()
+}
+
+#[cfg(all(test, any(feature = "metrics", feature = "tracing")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn redact_authorization_header() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(http::header::AUTHORIZATION, "Bearer secret".parse().unwrap());
+        headers.insert(http::header::CONTENT_TYPE, "application/json".parse().unwrap());
+        let redacted = redacted_headers(&headers);
+        assert!(redacted.contains(&("authorization", "<redacted>")));
+        assert!(redacted.contains(&("content-type", "application/json")));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn record_xrpc_metrics() {
+        use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, SharedString, Unit};
+        use std::sync::Mutex;
+
+        #[derive(Default)]
+        struct KeyRecorder(Mutex<Vec<String>>);
+
+        impl KeyRecorder {
+            fn push(&self, key: &Key) {
+                let labels = key
+                    .labels()
+                    .map(|label| format!("{}={}", label.key(), label.value()))
+                    .collect::<Vec<_>>();
+                self.0.lock().unwrap().push(format!("{}{{{}}}", key.name(), labels.join(",")));
+            }
+        }
+
+        impl metrics::Recorder for KeyRecorder {
+            fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+                self.push(key);
+                Counter::noop()
+            }
+            fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+                self.push(key);
+                Gauge::noop()
+            }
+            fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+                self.push(key);
+                Histogram::noop()
+            }
+        }
+
+        let recorder = KeyRecorder::default();
+        metrics::with_local_recorder(&recorder, || {
+            let ok = Response::builder().status(200).body(Vec::<u8>::new()).unwrap();
+            record_metrics(String::from("com.example.get"), &Ok::<_, ()>(ok), Instant::now());
+            record_metrics::<Vec<u8>, _>(String::from("com.example.get"), &Err(()), Instant::now());
+        });
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                "atrium_xrpc_requests_total{nsid=com.example.get,status=200}",
+                "atrium_xrpc_request_duration_seconds{nsid=com.example.get}",
+                "atrium_xrpc_requests_total{nsid=com.example.get,status=error}",
+                "atrium_xrpc_request_duration_seconds{nsid=com.example.get}",
+            ]
+        );
+    }
+}
@@ -1,9 +1,13 @@
+use crate::error::Error;
 use http::header::{HeaderName, HeaderValue, InvalidHeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use http::Method;
 use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::{self, Debug};
+use std::sync::Arc;
 
 pub(crate) const NSID_REFRESH_SESSION: &str = "com.atproto.server.refreshSession";
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AuthorizationToken {
     Bearer(String),
     Dpop(String),
@@ -51,6 +55,59 @@ where
     pub encoding: Option<String>,
 }
 
+/// A callback to report the progress of sending a request body.
+///
+/// It is called with the number of bytes sent so far and the total length of the body.
+/// It can be inserted into the [extensions](http::Request::extensions_mut) of a request passed
+/// to [`HttpClient::send_http()`](crate::HttpClient::send_http).
+/// Implementations which do not support it ignore it.
+#[derive(Clone)]
+pub struct UploadProgress(Arc<dyn Fn(u64, u64) + Send + Sync>);
+
+impl UploadProgress {
+    pub fn new(callback: impl Fn(u64, u64) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+    /// Report that `sent` bytes of `total` have been sent.
+    pub fn report(&self, sent: u64, total: u64) {
+        (self.0)(sent, total)
+    }
+}
+
+impl Debug for UploadProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadProgress").finish_non_exhaustive()
+    }
+}
+
+/// A callback to receive the body of a successful response in chunks, as they are downloaded.
+///
+/// It can be inserted into the [extensions](http::Request::extensions_mut) of a request passed
+/// to [`HttpClient::send_http()`](crate::HttpClient::send_http). Implementations which support
+/// it pass each chunk of the body to the callback, and return the response with an empty body.
+/// Implementations which do not support it ignore it, and return the whole body as usual.
+/// The body of an unsuccessful response is always returned as usual.
+#[derive(Clone)]
+pub struct DownloadSink(Arc<DownloadCallback>);
+
+type DownloadCallback = dyn Fn(&[u8]) + Send + Sync;
+
+impl DownloadSink {
+    pub fn new(callback: impl Fn(&[u8]) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+    /// Pass a downloaded chunk of the body.
+    pub fn write(&self, chunk: &[u8]) {
+        (self.0)(chunk)
+    }
+}
+
+impl Debug for DownloadSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DownloadSink").finish_non_exhaustive()
+    }
+}
+
 /// A type which can be used as a parameter of [`XrpcRequest`].
 ///
 /// JSON serializable data or raw bytes.
@@ -72,3 +129,29 @@ where
     Data(T),
     Bytes(Vec<u8>),
 }
+
+impl<T> OutputDataOrBytes<T>
+where
+    T: DeserializeOwned,
+{
+    /// Returns the deserialized data, or [`Error::UnexpectedResponseType`] for raw bytes.
+    pub fn into_data<E>(self) -> crate::Result<T, E>
+    where
+        E: Debug,
+    {
+        match self {
+            Self::Data(data) => Ok(data),
+            Self::Bytes(_) => Err(Error::UnexpectedResponseType),
+        }
+    }
+    /// Returns the raw bytes, or [`Error::UnexpectedResponseType`] for deserialized data.
+    pub fn into_bytes<E>(self) -> crate::Result<Vec<u8>, E>
+    where
+        E: Debug,
+    {
+        match self {
+            Self::Bytes(bytes) => Ok(bytes),
+            Self::Data(_) => Err(Error::UnexpectedResponseType),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use bsky_cli::{Command, Runner};
+use bsky_cli::{Command, OutputFormat, Runner};
 use clap::Parser;
 use std::fmt::Debug;
 
@@ -18,6 +18,9 @@ struct Args {
     /// Debug print
     #[arg(short, long)]
     debug: bool,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t)]
+    output: OutputFormat,
     #[command(subcommand)]
     // command: Command,
     command: Command,
@@ -30,7 +33,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         args.pds_host,
         args.limit.try_into()?,
         args.debug,
-        matches!(args.command, Command::Login(_)),
+        args.output,
+        // Identity and repository commands do not need a logged-in session either.
+        matches!(
+            args.command,
+            Command::Login(_)
+                | Command::OauthLogin(_)
+                | Command::Resolve(_)
+                | Command::ExportRepo(_)
+                | Command::VerifyRepo(_)
+        ),
     )
     .await?
     .run(args.command)
@@ -1,12 +1,13 @@
-use bsky_sdk::api::types::string::AtIdentifier;
+use bsky_sdk::api::types::string::{AtIdentifier, AtUri, Did};
 use clap::Parser;
 use std::path::PathBuf;
-use std::str::FromStr;
 
 #[derive(Parser, Debug)]
 pub enum Command {
     /// Login (Create an authentication session).
     Login(LoginArgs),
+    /// Login with OAuth (Authorization code flow with DPoP).
+    OauthLogin(OauthLoginArgs),
     /// Get a view of an actor's home timeline.
     GetTimeline,
     /// Get a view of an actor's feed.
@@ -43,16 +44,41 @@ pub enum Command {
     CreatePost(CreatePostArgs),
     /// Delete a post.
     DeletePost(UriArgs),
+    /// Resolve a handle or DID to its DID, PDS endpoint and verified handle.
+    Resolve(ResolveArgs),
+    /// Export an actor's repository to a CAR file.
+    ExportRepo(ExportRepoArgs),
+    /// Verify the commit signature of a repository CAR file.
+    VerifyRepo(VerifyRepoArgs),
 }
 
 #[derive(Parser, Debug)]
 pub struct LoginArgs {
     /// Handle or other identifier supported by the server for the authenticating user.
-    #[arg(short, long)]
+    #[arg(short, long, env = "ATRIUM_IDENTIFIER")]
+    pub(crate) identifier: String,
+    /// Password. Prefer the environment variable, so that it does not appear in the shell history.
+    #[cfg_attr(
+        feature = "keyring",
+        doc = "Looked up in this order: this option, the environment variable, the OS keychain."
+    )]
+    #[arg(short, long, env = "ATRIUM_PASSWORD", hide_env_values = true)]
+    pub(crate) password: Option<String>,
+    /// Save the password to the OS keychain (the kernel keyring on Linux, cleared on reboot)
+    #[cfg(feature = "keyring")]
+    #[arg(long, conflicts_with = "no_keyring")]
+    pub(crate) save_password: bool,
+    /// Do not read the password from the OS keychain
+    #[cfg(feature = "keyring")]
+    #[arg(long)]
+    pub(crate) no_keyring: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct OauthLoginArgs {
+    /// Handle, DID or PDS URL of the authenticating user.
+    #[arg(short, long, env = "ATRIUM_IDENTIFIER", default_value = "https://bsky.social")]
     pub(crate) identifier: String,
-    /// Password
-    #[arg(short, long)]
-    pub(crate) password: String,
 }
 
 #[derive(Parser, Debug)]
@@ -89,32 +115,28 @@ pub struct CreatePostArgs {
     pub(crate) images: Vec<PathBuf>,
 }
 
-#[derive(Debug, Clone)]
-pub(crate) struct AtUri {
-    pub(crate) did: String,
-    pub(crate) collection: String,
-    pub(crate) rkey: String,
+#[derive(Parser, Debug)]
+pub struct ResolveArgs {
+    /// Handle or DID to resolve
+    #[arg(value_parser)]
+    pub(crate) identifier: AtIdentifier,
 }
 
-impl FromStr for AtUri {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s
-            .strip_prefix("at://did:plc:")
-            .ok_or(r#"record uri must start with "at://did:plc:""#)?
-            .splitn(3, '/')
-            .collect::<Vec<_>>();
-        Ok(Self {
-            did: format!("did:plc:{}", parts[0]),
-            collection: parts[1].to_string(),
-            rkey: parts[2].to_string(),
-        })
-    }
+#[derive(Parser, Debug)]
+pub struct ExportRepoArgs {
+    /// Actor's handle or did
+    #[arg(value_parser)]
+    pub(crate) actor: AtIdentifier,
+    /// Output CAR file path
+    #[arg(long)]
+    pub(crate) out: PathBuf,
 }
 
-impl std::fmt::Display for AtUri {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "at://{}/{}/{}", self.did, self.collection, self.rkey)
-    }
+#[derive(Parser, Debug)]
+pub struct VerifyRepoArgs {
+    /// Repository CAR file path
+    pub(crate) car: PathBuf,
+    /// DID of the repository owner
+    #[arg(short, long, value_parser)]
+    pub(crate) did: Did,
 }
@@ -1,5 +1,9 @@
 mod commands;
+mod oauth;
+mod output;
+mod repo;
 mod runner;
 
 pub use commands::Command;
+pub use output::OutputFormat;
 pub use runner::Runner;
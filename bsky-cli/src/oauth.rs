@@ -0,0 +1,181 @@
+use atrium_identity::{did::DidResolver, handle::HandleResolver};
+use atrium_oauth_client::store::session::{Session, SessionStore};
+use atrium_oauth_client::store::SimpleStore;
+use atrium_oauth_client::OAuthSession;
+use bsky_sdk::api::types::string::Did;
+use bsky_sdk::api::xrpc::http::{Request, Response};
+use bsky_sdk::api::xrpc::types::AuthorizationToken;
+use bsky_sdk::api::xrpc::{HttpClient, XrpcClient};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs::{read, remove_file, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+/// A [`SessionStore`] that saves a single OAuth session, with its DPoP key, to a JSON file.
+///
+/// The file is readable only by the owner on unix, since the DPoP key is a private key.
+pub struct SessionFile {
+    path: PathBuf,
+}
+
+impl SessionFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+    /// The DID of the user of the saved session, if any.
+    pub async fn sub(&self) -> std::io::Result<Option<String>> {
+        Ok(self.read().await?.map(|session| session.token_set.sub))
+    }
+    async fn read(&self) -> std::io::Result<Option<Session>> {
+        match read(&self.path).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl SimpleStore<String, Session> for SessionFile {
+    type Error = std::io::Error;
+
+    async fn get(&self, key: &String) -> Result<Option<Session>, Self::Error> {
+        Ok(self.read().await?.filter(|session| &session.token_set.sub == key))
+    }
+    async fn set(&self, _: String, value: Session) -> Result<(), Self::Error> {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&self.path).await?;
+        // `mode` applies only when the file is created
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600)).await?;
+        }
+        file.write_all(&serde_json::to_vec_pretty(&value)?).await?;
+        file.flush().await
+    }
+    async fn del(&self, key: &String) -> Result<(), Self::Error> {
+        if self.get(key).await?.is_some() {
+            self.clear().await?;
+        }
+        Ok(())
+    }
+    async fn clear(&self) -> Result<(), Self::Error> {
+        match remove_file(&self.path).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl SessionStore for SessionFile {}
+
+/// An XRPC client that sends requests with a shared [`OAuthSession`], optionally proxied by the
+/// PDS to another service with the `atproto-proxy` header.
+pub struct SessionClient<T, D, H>
+where
+    T: HttpClient + Send + Sync + 'static,
+{
+    session: Arc<OAuthSession<T, D, H, SessionFile>>,
+    proxy_header: Option<String>,
+}
+
+impl<T, D, H> SessionClient<T, D, H>
+where
+    T: HttpClient + Send + Sync + 'static,
+{
+    pub fn new(session: Arc<OAuthSession<T, D, H, SessionFile>>) -> Self {
+        Self { session, proxy_header: None }
+    }
+    pub fn with_proxy(&self, did: Did, service_type: impl AsRef<str>) -> Self {
+        Self {
+            session: Arc::clone(&self.session),
+            proxy_header: Some(format!("{}#{}", did.as_ref(), service_type.as_ref())),
+        }
+    }
+}
+
+impl<T, D, H> HttpClient for SessionClient<T, D, H>
+where
+    T: HttpClient + Send + Sync + 'static,
+    D: DidResolver + Send + Sync + 'static,
+    H: HandleResolver + Send + Sync + 'static,
+{
+    async fn send_http(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        self.session.send_http(request).await
+    }
+}
+
+impl<T, D, H> XrpcClient for SessionClient<T, D, H>
+where
+    T: HttpClient + Send + Sync + 'static,
+    D: DidResolver + Send + Sync + 'static,
+    H: HandleResolver + Send + Sync + 'static,
+{
+    fn base_uri(&self) -> String {
+        self.session.base_uri()
+    }
+    async fn authorization_token(&self, is_refresh: bool) -> Option<AuthorizationToken> {
+        self.session.authorization_token(is_refresh).await
+    }
+    async fn atproto_proxy_header(&self) -> Option<String> {
+        self.proxy_header.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(sub: &str) -> Session {
+        serde_json::from_value(serde_json::json!({
+            "dpop_key": {
+                "kty": "EC",
+                "crv": "P-256",
+                "x": "qhKSL_UzyO8tWc2ZQHgHwQSO3xh0sFbgmSBZFTLU7Q4",
+                "y": "yZ38NC1YhyHDoVn3ErddktuMW3Jd6Z1PUhRqpaqvNDk",
+                "d": "bYqzGk3x5a9aUIAjT55U6bJ5bsqP6W8j8-v6jyd9F6A",
+            },
+            "token_set": {
+                "iss": "https://bsky.social",
+                "sub": sub,
+                "aud": "https://pds.test",
+                "access_token": "access",
+                "token_type": "DPoP",
+            },
+        }))
+        .expect("failed to deserialize session")
+    }
+
+    #[tokio::test]
+    async fn session_file() -> std::io::Result<()> {
+        let path = std::env::temp_dir().join(format!("bsky-cli-oauth-{}.json", std::process::id()));
+        let store = SessionFile::new(path.clone());
+        let alice = String::from("did:plc:alice");
+        assert_eq!(store.get(&alice).await?, None);
+        store.set(alice.clone(), session(&alice)).await?;
+        assert_eq!(store.get(&alice).await?, Some(session(&alice)));
+        assert_eq!(store.get(&String::from("did:plc:bob")).await?, None);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path)?.permissions().mode() & 0o777, 0o600);
+        }
+        // the DPoP key is saved with the tokens
+        assert!(serde_json::from_slice::<serde_json::Value>(&read(&path).await?)?["dpop_key"]
+            .get("d")
+            .is_some());
+        store.del(&String::from("did:plc:bob")).await?;
+        assert!(path.exists());
+        store.del(&alice).await?;
+        assert!(!path.exists());
+        Ok(())
+    }
+}
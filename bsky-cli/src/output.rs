@@ -0,0 +1,142 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Output format of command results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Pretty-printed JSON
+    #[default]
+    Json,
+    /// YAML
+    Yaml,
+    /// Columns of the scalar fields, one row per item of a list
+    Table,
+}
+
+impl OutputFormat {
+    pub(crate) fn format<T: Serialize>(&self, result: &T) -> Result<String> {
+        Ok(match self {
+            Self::Json => serde_json::to_string_pretty(result)?,
+            Self::Yaml => serde_yaml::to_string(result)?.trim_end().to_string(),
+            Self::Table => table(&serde_json::to_value(result)?),
+        })
+    }
+}
+
+fn is_nested(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => !map.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+fn table(value: &Value) -> String {
+    // List endpoints return an object with an array of items (e.g. `followers`) beside fields
+    // like `cursor`, so the first such array becomes the rows.
+    let rows = match value {
+        Value::Array(items) => items.iter().filter_map(Value::as_object).collect(),
+        Value::Object(map) => map
+            .values()
+            .filter_map(Value::as_array)
+            .find(|items| !items.is_empty() && items.iter().all(Value::is_object))
+            .map_or_else(|| vec![map], |items| items.iter().filter_map(Value::as_object).collect()),
+        _ => return value.to_string(),
+    };
+    let mut columns = Vec::<&str>::new();
+    for row in &rows {
+        for (key, value) in row.iter() {
+            if !is_nested(value) && !columns.contains(&key.as_str()) {
+                columns.push(key);
+            }
+        }
+    }
+    let cells = rows
+        .iter()
+        .map(|row| columns.iter().map(|column| table_cell(row, column)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let widths = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            cells.iter().map(|row| row[i].chars().count()).fold(column.chars().count(), usize::max)
+        })
+        .collect::<Vec<_>>();
+    let mut lines = vec![
+        table_row(columns.iter().copied(), &widths),
+        table_row(widths.iter().map(|width| "-".repeat(*width)), &widths),
+    ];
+    lines.extend(cells.iter().map(|row| table_row(row.iter(), &widths)));
+    lines.join("\n")
+}
+
+fn table_cell(row: &Map<String, Value>, column: &str) -> String {
+    match row.get(column) {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.replace('\n', " "),
+        Some(value) if is_nested(value) => String::new(),
+        Some(value) => value.to_string(),
+    }
+}
+
+fn table_row<S: AsRef<str>>(cells: impl Iterator<Item = S>, widths: &[usize]) -> String {
+    cells
+        .zip(widths)
+        .map(|(cell, width)| format!("{:width$}", cell.as_ref()))
+        .collect::<Vec<_>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn yaml() {
+        let value = json!({"handle": "alice.test", "labels": [], "viewer": {"muted": false}});
+        assert_eq!(
+            OutputFormat::Yaml.format(&value).expect("failed to format"),
+            "handle: alice.test\nlabels: []\nviewer:\n  muted: false"
+        );
+    }
+
+    #[test]
+    fn table_of_list() {
+        let value = json!({
+            "cursor": "next",
+            "followers": [
+                {"handle": "alice.test", "displayName": "Alice\nA.", "labels": []},
+                {"handle": "bob.test", "followersCount": 10},
+            ],
+        });
+        assert_eq!(
+            OutputFormat::Table.format(&value).expect("failed to format"),
+            [
+                "displayName  handle      labels  followersCount",
+                "-----------  ----------  ------  --------------",
+                "Alice A.     alice.test  []",
+                "             bob.test            10",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn table_of_object() {
+        let value = json!({"did": "did:plc:alice", "handle": "alice.test", "viewer": {}});
+        assert_eq!(
+            OutputFormat::Table.format(&value).expect("failed to format"),
+            [
+                "did            handle      viewer",
+                "-------------  ----------  ------",
+                "did:plc:alice  alice.test  {}",
+            ]
+            .join("\n")
+        );
+    }
+}
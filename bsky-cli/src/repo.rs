@@ -0,0 +1,246 @@
+//! Minimal reading of repository CAR files, for verifying exported repositories.
+use anyhow::{bail, Context, Result};
+use atrium_crypto::did::parse_multikey;
+use atrium_crypto::verify::Verifier;
+use ipld_core::cid::Cid;
+use ipld_core::ipld::Ipld;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+/// Multihash code of SHA-256.
+const SHA2_256: u64 = 0x12;
+/// Maximum depth of MST nodes to walk. An MST of this depth would need an astronomical number of
+/// records, so a deeper tree is malformed.
+const MAX_MST_DEPTH: usize = 128;
+
+/// A repository loaded from a CAR file.
+pub(crate) struct CarRepo {
+    root: Cid,
+    blocks: HashMap<Cid, Vec<u8>>,
+}
+
+/// Summary of a verified repository.
+#[derive(Debug)]
+pub(crate) struct RepoSummary {
+    pub(crate) rev: String,
+    pub(crate) records: usize,
+    pub(crate) depth: usize,
+}
+
+impl CarRepo {
+    /// Parse a CARv1 file with a single root, checking that each block matches its CID.
+    pub(crate) fn parse(mut bytes: &[u8]) -> Result<Self> {
+        let header_len = read_varint(&mut bytes)?;
+        let header = serde_ipld_dagcbor::from_slice::<Ipld>(take(&mut bytes, header_len)?)?;
+        let root = match header.get("roots")? {
+            Some(Ipld::List(roots)) if roots.len() == 1 => match &roots[0] {
+                Ipld::Link(cid) => *cid,
+                _ => bail!("invalid CAR root"),
+            },
+            _ => bail!("CAR file must have exactly one root"),
+        };
+        let mut blocks = HashMap::new();
+        while !bytes.is_empty() {
+            let len = read_varint(&mut bytes)?;
+            let mut block = take(&mut bytes, len)?;
+            let cid = Cid::read_bytes(&mut block)?;
+            if cid.hash().code() != SHA2_256 {
+                bail!("unsupported hash function for block: {cid}");
+            }
+            if cid.hash().digest() != Sha256::digest(block).as_slice() {
+                bail!("block does not match its CID: {cid}");
+            }
+            blocks.insert(cid, block.to_vec());
+        }
+        Ok(Self { root, blocks })
+    }
+    /// Verify the signature of the root commit with the given multibase-encoded public key,
+    /// and walk the MST to count the records.
+    pub(crate) fn verify(&self, did: &str, public_key_multibase: &str) -> Result<RepoSummary> {
+        let Ipld::Map(mut commit) = self.decode(&self.root)? else {
+            bail!("commit must be a map");
+        };
+        if commit.get("did") != Some(&Ipld::String(did.into())) {
+            bail!("commit is not for {did}");
+        }
+        let Some(Ipld::Bytes(sig)) = commit.remove("sig") else {
+            bail!("commit is not signed");
+        };
+        let (alg, public_key) = parse_multikey(public_key_multibase)?;
+        Verifier::default()
+            .verify(alg, &public_key, &serde_ipld_dagcbor::to_vec(&commit)?, &sig)
+            .context("invalid commit signature")?;
+        let (Some(Ipld::String(rev)), Some(Ipld::Link(data))) =
+            (commit.get("rev"), commit.get("data"))
+        else {
+            bail!("invalid commit");
+        };
+        let (records, depth) = self.walk(data, 0, &mut HashSet::new())?;
+        Ok(RepoSummary { rev: rev.clone(), records, depth: depth.unwrap_or_default() })
+    }
+    /// Returns the number of entries under the MST node, and the layer of the node if known.
+    fn walk(
+        &self,
+        cid: &Cid,
+        depth: usize,
+        visited: &mut HashSet<Cid>,
+    ) -> Result<(usize, Option<usize>)> {
+        if depth > MAX_MST_DEPTH {
+            bail!("MST is deeper than {MAX_MST_DEPTH}");
+        }
+        if !visited.insert(*cid) {
+            bail!("MST node is referenced more than once: {cid}");
+        }
+        let node = self.decode(cid)?;
+        let mut count = 0;
+        let mut layer_of_node = None;
+        if let Some(Ipld::Link(left)) = node.get("l")? {
+            let (n, l) = self.walk(left, depth + 1, visited)?;
+            count += n;
+            layer_of_node = l.map(|l| l + 1);
+        }
+        let Some(Ipld::List(entries)) = node.get("e")? else {
+            bail!("invalid MST node: {cid}");
+        };
+        for (i, entry) in entries.iter().enumerate() {
+            // The first key of a node is never prefix-compressed, so it gives the layer.
+            if i == 0 {
+                if let Some(Ipld::Bytes(key)) = entry.get("k")? {
+                    layer_of_node = Some(layer(key));
+                }
+            }
+            count += 1;
+            if let Some(Ipld::Link(right)) = entry.get("t")? {
+                count += self.walk(right, depth + 1, visited)?.0;
+            }
+        }
+        Ok((count, layer_of_node))
+    }
+    fn decode(&self, cid: &Cid) -> Result<Ipld> {
+        let block = self.blocks.get(cid).with_context(|| format!("missing block: {cid}"))?;
+        Ok(serde_ipld_dagcbor::from_slice(block)?)
+    }
+}
+
+/// The MST layer of a key: the number of leading zero bits of its SHA-256 hash, divided by 2.
+fn layer(key: &[u8]) -> usize {
+    let mut zeros = 0;
+    for byte in Sha256::digest(key) {
+        zeros += byte.leading_zeros() as usize;
+        if byte != 0 {
+            break;
+        }
+    }
+    zeros / 2
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<usize> {
+    let mut value = 0usize;
+    for shift in (0..63).step_by(7) {
+        let (&byte, rest) = bytes.split_first().context("unexpected end of CAR")?;
+        *bytes = rest;
+        value |= usize::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    bail!("varint too long")
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if bytes.len() < len {
+        bail!("unexpected end of CAR");
+    }
+    let (head, rest) = bytes.split_at(len);
+    *bytes = rest;
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ipld_core::cid::multihash::Multihash;
+    use std::collections::BTreeMap;
+
+    const DAG_CBOR: u64 = 0x71;
+
+    fn cid(block: &[u8]) -> Cid {
+        let hash = Multihash::wrap(SHA2_256, &Sha256::digest(block)).expect("valid digest");
+        Cid::new_v1(DAG_CBOR, hash)
+    }
+
+    fn car(root: &Cid, blocks: &[(Cid, Vec<u8>)]) -> Vec<u8> {
+        fn write_varint(out: &mut Vec<u8>, mut value: usize) {
+            while value >= 0x80 {
+                out.push((value as u8 & 0x7f) | 0x80);
+                value >>= 7;
+            }
+            out.push(value as u8);
+        }
+        let header = serde_ipld_dagcbor::to_vec(&Ipld::Map(BTreeMap::from_iter([
+            (String::from("roots"), Ipld::List(vec![Ipld::Link(*root)])),
+            (String::from("version"), Ipld::Integer(1)),
+        ])))
+        .expect("failed to encode header");
+        let mut out = Vec::new();
+        write_varint(&mut out, header.len());
+        out.extend(header);
+        for (cid, block) in blocks {
+            let cid = cid.to_bytes();
+            write_varint(&mut out, cid.len() + block.len());
+            out.extend(cid);
+            out.extend(block);
+        }
+        out
+    }
+
+    fn node(left: Option<Cid>) -> Vec<u8> {
+        let mut node = BTreeMap::from_iter([(String::from("e"), Ipld::List(Vec::new()))]);
+        if let Some(left) = left {
+            node.insert(String::from("l"), Ipld::Link(left));
+        }
+        serde_ipld_dagcbor::to_vec(&Ipld::Map(node)).expect("failed to encode node")
+    }
+
+    #[test]
+    fn parse() {
+        let block = node(None);
+        let root = cid(&block);
+        let repo = CarRepo::parse(&car(&root, &[(root, block.clone())])).expect("valid CAR");
+        assert_eq!(repo.root, root);
+        assert_eq!(repo.walk(&root, 0, &mut HashSet::new()).expect("valid MST"), (0, None));
+
+        let mut tampered = block.clone();
+        *tampered.last_mut().expect("non-empty block") ^= 1;
+        let err = CarRepo::parse(&car(&root, &[(root, tampered)])).err().expect("tampered block");
+        assert!(err.to_string().contains("does not match"), "{err}");
+    }
+
+    #[test]
+    fn cyclic_mst() {
+        // Content addressing makes cycles impossible in a parsed CAR, so the blocks are
+        // inserted directly.
+        let a = cid(b"a");
+        let b = cid(b"b");
+        let repo = CarRepo {
+            root: a,
+            blocks: HashMap::from_iter([(a, node(Some(b))), (b, node(Some(a)))]),
+        };
+        let err = repo.walk(&a, 0, &mut HashSet::new()).expect_err("cyclic MST");
+        assert!(err.to_string().contains("more than once"), "{err}");
+
+        // A chain deeper than the limit is rejected without overflowing the stack.
+        let leaf = node(None);
+        let mut blocks = HashMap::new();
+        let mut next = cid(&leaf);
+        blocks.insert(next, leaf);
+        for _ in 0..=MAX_MST_DEPTH {
+            let block = node(Some(next));
+            next = cid(&block);
+            blocks.insert(next, block);
+        }
+        let repo = CarRepo { root: next, blocks };
+        let err = repo.walk(&next, 0, &mut HashSet::new()).expect_err("deep MST");
+        assert!(err.to_string().contains("deeper than"), "{err}");
+    }
+}
@@ -1,21 +1,86 @@
-use crate::commands::Command;
+use crate::commands::{Command, ExportRepoArgs, OauthLoginArgs, ResolveArgs, VerifyRepoArgs};
+use crate::oauth::{SessionClient, SessionFile};
+use crate::output::OutputFormat;
+use crate::repo::CarRepo;
 use anyhow::{Context, Result};
 use api::agent::bluesky::{AtprotoServiceType, BSKY_CHAT_DID};
-use api::types::string::{AtIdentifier, Datetime, Handle};
+use api::types::string::{AtIdentifier, Datetime, Did, Handle};
 use api::types::LimitedNonZeroU8;
+use atrium_common::resolver::Resolver;
+use atrium_identity::did::{CommonDidResolver, CommonDidResolverConfig, DEFAULT_PLC_DIRECTORY_URL};
+use atrium_identity::handle::{AtprotoHandleResolver, AtprotoHandleResolverConfig, DnsTxtResolver};
+use atrium_oauth_client::store::state::MemoryStateStore;
+use atrium_oauth_client::store::SimpleStore;
+use atrium_oauth_client::{
+    AtprotoLocalhostClientMetadata, AuthorizeOptions, DefaultHttpClient, KnownScope, OAuthClient,
+    OAuthClientConfig, OAuthResolverConfig, OAuthSession, Scope,
+};
+use atrium_xrpc_client::reqwest::ReqwestClient;
 use bsky_sdk::agent::config::{Config, FileStore};
 use bsky_sdk::api;
+use bsky_sdk::api::client::{AtpServiceClient, Service};
+use bsky_sdk::api::types::TryIntoUnknown;
+use bsky_sdk::api::xrpc::XrpcClient;
 use bsky_sdk::BskyAgent;
+use hickory_resolver::TokioAsyncResolver;
 use serde::Serialize;
 use std::ffi::OsStr;
+use std::io::{stdin, stdout, BufRead, Write};
 use std::path::PathBuf;
-use tokio::fs::{create_dir_all, File};
+use std::sync::Arc;
+use tokio::fs::{create_dir_all, read, write, File};
 use tokio::io::AsyncReadExt;
 
+struct HickoryDnsTxtResolver {
+    resolver: TokioAsyncResolver,
+}
+
+impl HickoryDnsTxtResolver {
+    fn new() -> Result<Self> {
+        Ok(Self {
+            resolver: TokioAsyncResolver::tokio_from_system_conf()
+                .context("failed to create DNS resolver from the system configuration")?,
+        })
+    }
+}
+
+impl DnsTxtResolver for HickoryDnsTxtResolver {
+    async fn resolve(
+        &self,
+        query: &str,
+    ) -> core::result::Result<Vec<String>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok(self.resolver.txt_lookup(query).await?.iter().map(|txt| txt.to_string()).collect())
+    }
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_entry(identifier: &str) -> keyring::Result<keyring::Entry> {
+    keyring::Entry::new("bsky-cli", identifier)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResolvedIdentity {
+    did: Did,
+    pds: Option<String>,
+    handle: Option<Handle>,
+    handle_verified: bool,
+}
+
+type DidResolverImpl = CommonDidResolver<ReqwestClient>;
+type HandleResolverImpl = AtprotoHandleResolver<HickoryDnsTxtResolver, ReqwestClient>;
+type OAuthClientImpl =
+    OAuthClient<MemoryStateStore, SessionFile, DidResolverImpl, HandleResolverImpl>;
+type OAuthSessionImpl =
+    OAuthSession<DefaultHttpClient, DidResolverImpl, HandleResolverImpl, SessionFile>;
+
 pub struct Runner {
     agent: BskyAgent,
+    /// The session created by `oauth-login`, used instead of the one created by `login`.
+    oauth_session: Option<Arc<OAuthSessionImpl>>,
     limit: LimitedNonZeroU8<100>,
     debug: bool,
+    output: OutputFormat,
     config_path: PathBuf,
 }
 
@@ -24,6 +89,7 @@ impl Runner {
         pds_host: String,
         limit: LimitedNonZeroU8<100>,
         debug: bool,
+        output: OutputFormat,
         is_login: bool,
     ) -> Result<Self> {
         let config_dir = dirs::config_dir()
@@ -31,12 +97,19 @@ impl Runner {
         let dir = config_dir.join("bsky-cli");
         create_dir_all(&dir).await?;
         let config_path = dir.join("config.json");
+        let oauth_path = dir.join("oauth.json");
 
+        let mut oauth_session = None;
         let agent = if is_login {
             BskyAgent::builder()
                 .config(Config { endpoint: pds_host, ..Default::default() })
                 .build()
                 .await?
+        } else if let Some(sub) = SessionFile::new(oauth_path.clone()).sub().await? {
+            let session = oauth_client(pds_host, oauth_path)?.restore(sub).await?;
+            let endpoint = session.token_set().aud;
+            oauth_session = Some(Arc::new(session));
+            BskyAgent::builder().config(Config { endpoint, ..Default::default() }).build().await?
         } else {
             let store = FileStore::new(&config_path);
             let agent = BskyAgent::builder()
@@ -46,25 +119,90 @@ impl Runner {
             agent.to_config().await.save(&store).await?;
             agent
         };
-        Ok(Self { agent, limit, debug, config_path })
+        Ok(Self { agent, oauth_session, limit, debug, output, config_path })
     }
     pub async fn run(&self, command: Command) -> Result<()> {
-        let limit = self.limit;
         match command {
             Command::Login(args) => {
-                self.agent.login(args.identifier, args.password).await?;
+                let password = match args.password {
+                    Some(password) => password,
+                    #[cfg(feature = "keyring")]
+                    None if args.no_keyring => anyhow::bail!("No password given"),
+                    #[cfg(feature = "keyring")]
+                    None => keyring_entry(&args.identifier)?
+                        .get_password()
+                        .with_context(|| "No password given or saved in the keychain")?,
+                    #[cfg(not(feature = "keyring"))]
+                    None => anyhow::bail!("No password given"),
+                };
+                self.agent.login(&args.identifier, &password).await?;
+                #[cfg(feature = "keyring")]
+                if args.save_password {
+                    keyring_entry(&args.identifier)?.set_password(&password)?;
+                }
                 // Set labelers from preferences
                 let preferences = self.agent.get_preferences(true).await?;
                 self.agent.configure_labelers_from_preferences(&preferences);
                 // Save config to file
                 self.agent.to_config().await.save(&FileStore::new(&self.config_path)).await?;
+                // Otherwise the session of `oauth-login` would still be used
+                SessionFile::new(self.config_path.with_file_name("oauth.json")).clear().await?;
                 println!("Login successful! Saved config to {:?}", self.config_path);
                 Ok(())
             }
+            Command::OauthLogin(args) => self.oauth_login(args).await,
+            Command::Resolve(args) => self.resolve(args).await,
+            Command::ExportRepo(args) => self.export_repo(args).await,
+            Command::VerifyRepo(args) => self.verify_repo(args).await,
+            command => match &self.oauth_session {
+                Some(session) => {
+                    let client = SessionClient::new(Arc::clone(session));
+                    let chat_client = client.with_proxy(
+                        BSKY_CHAT_DID.parse().expect("valid DID"),
+                        AtprotoServiceType::BskyChat,
+                    );
+                    let did = session.token_set().sub.parse().map_err(anyhow::Error::msg)?;
+                    self.run_with(
+                        &AtpServiceClient::new(client).service,
+                        &AtpServiceClient::new(chat_client).service,
+                        did,
+                        command,
+                    )
+                    .await
+                }
+                None => {
+                    let session =
+                        self.agent.get_session().await.with_context(|| "Not logged in")?;
+                    self.run_with(
+                        &self.agent.api,
+                        &self.agent.api_with_proxy(
+                            BSKY_CHAT_DID.parse().expect("valid DID"),
+                            AtprotoServiceType::BskyChat,
+                        ),
+                        session.data.did,
+                        command,
+                    )
+                    .await
+                }
+            },
+        }
+    }
+    /// Run a command that needs a logged-in session, with the API service of the session, the
+    /// chat service proxied by its PDS, and the DID of its user.
+    async fn run_with<C>(
+        &self,
+        service: &Service<C>,
+        chat_service: &Service<C>,
+        did: Did,
+        command: Command,
+    ) -> Result<()>
+    where
+        C: XrpcClient + Send + Sync,
+    {
+        let limit = self.limit;
+        match command {
             Command::GetTimeline => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .feed
@@ -79,15 +217,13 @@ impl Runner {
                     .await?,
             ),
             Command::GetAuthorFeed(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .feed
                     .get_author_feed(
                         api::app::bsky::feed::get_author_feed::ParametersData {
-                            actor: args.actor.unwrap_or(self.handle().await?.into()),
+                            actor: args.actor.unwrap_or(did.clone().into()),
                             cursor: None,
                             filter: None,
                             include_pins: None,
@@ -98,9 +234,7 @@ impl Runner {
                     .await?,
             ),
             Command::GetLikes(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .feed
@@ -116,9 +250,7 @@ impl Runner {
                     .await?,
             ),
             Command::GetRepostedBy(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .feed
@@ -134,15 +266,13 @@ impl Runner {
                     .await?,
             ),
             Command::GetActorFeeds(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .feed
                     .get_actor_feeds(
                         api::app::bsky::feed::get_actor_feeds::ParametersData {
-                            actor: args.actor.unwrap_or(self.handle().await?.into()),
+                            actor: args.actor.unwrap_or(did.clone().into()),
                             cursor: None,
                             limit: Some(limit),
                         }
@@ -151,9 +281,7 @@ impl Runner {
                     .await?,
             ),
             Command::GetFeed(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .feed
@@ -168,9 +296,7 @@ impl Runner {
                     .await?,
             ),
             Command::GetListFeed(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .feed
@@ -185,15 +311,13 @@ impl Runner {
                     .await?,
             ),
             Command::GetFollows(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .graph
                     .get_follows(
                         api::app::bsky::graph::get_follows::ParametersData {
-                            actor: args.actor.unwrap_or(self.handle().await?.into()),
+                            actor: args.actor.unwrap_or(did.clone().into()),
                             cursor: None,
                             limit: Some(limit),
                         }
@@ -202,15 +326,13 @@ impl Runner {
                     .await?,
             ),
             Command::GetFollowers(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .graph
                     .get_followers(
                         api::app::bsky::graph::get_followers::ParametersData {
-                            actor: args.actor.unwrap_or(self.handle().await?.into()),
+                            actor: args.actor.unwrap_or(did.clone().into()),
                             cursor: None,
                             limit: Some(limit),
                         }
@@ -219,15 +341,13 @@ impl Runner {
                     .await?,
             ),
             Command::GetLists(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .graph
                     .get_lists(
                         api::app::bsky::graph::get_lists::ParametersData {
-                            actor: args.actor.unwrap_or(self.handle().await?.into()),
+                            actor: args.actor.unwrap_or(did.clone().into()),
                             cursor: None,
                             limit: Some(limit),
                         }
@@ -236,9 +356,7 @@ impl Runner {
                     .await?,
             ),
             Command::GetList(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .graph
@@ -253,24 +371,20 @@ impl Runner {
                     .await?,
             ),
             Command::GetProfile(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .actor
                     .get_profile(
                         api::app::bsky::actor::get_profile::ParametersData {
-                            actor: args.actor.unwrap_or(self.handle().await?.into()),
+                            actor: args.actor.unwrap_or(did.clone().into()),
                         }
                         .into(),
                     )
                     .await?,
             ),
             Command::GetPreferences => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .actor
@@ -280,9 +394,7 @@ impl Runner {
                     .await?,
             ),
             Command::ListNotifications => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .app
                     .bsky
                     .notification
@@ -298,12 +410,7 @@ impl Runner {
                     .await?,
             ),
             Command::ListConvos => self.print(
-                &self
-                    .agent
-                    .api_with_proxy(
-                        BSKY_CHAT_DID.parse().expect("valid DID"),
-                        AtprotoServiceType::BskyChat,
-                    )
+                &chat_service
                     .chat
                     .bsky
                     .convo
@@ -319,8 +426,7 @@ impl Runner {
             Command::SendConvoMessage(args) => {
                 let did = match args.actor {
                     AtIdentifier::Handle(handle) => {
-                        self.agent
-                            .api
+                        service
                             .com
                             .atproto
                             .identity
@@ -336,13 +442,7 @@ impl Runner {
                     }
                     AtIdentifier::Did(did) => did,
                 };
-                let chat = &self
-                    .agent
-                    .api_with_proxy(
-                        BSKY_CHAT_DID.parse().expect("valid DID"),
-                        AtprotoServiceType::BskyChat,
-                    )
-                    .chat;
+                let chat = &chat_service.chat;
                 let convo = chat
                     .bsky
                     .convo
@@ -378,15 +478,8 @@ impl Runner {
                     if let Ok(mut file) = File::open(image).await {
                         let mut buf = Vec::new();
                         file.read_to_end(&mut buf).await.expect("read image file");
-                        let output = self
-                            .agent
-                            .api
-                            .com
-                            .atproto
-                            .repo
-                            .upload_blob(buf)
-                            .await
-                            .expect("upload blob");
+                        let output =
+                            service.com.atproto.repo.upload_blob(buf).await.expect("upload blob");
                         images.push(
                             api::app::bsky::embed::images::ImageData {
                                 alt: image
@@ -406,35 +499,53 @@ impl Runner {
                         api::app::bsky::embed::images::MainData { images }.into(),
                     )),
                 ));
+                let record = api::app::bsky::feed::post::Record::from(
+                    api::app::bsky::feed::post::RecordData {
+                        created_at: Datetime::now(),
+                        embed,
+                        entities: None,
+                        facets: None,
+                        labels: None,
+                        langs: None,
+                        reply: None,
+                        tags: None,
+                        text: args.text,
+                    },
+                );
                 self.print(
-                    &self
-                        .agent
-                        .create_record(api::app::bsky::feed::post::RecordData {
-                            created_at: Datetime::now(),
-                            embed,
-                            entities: None,
-                            facets: None,
-                            labels: None,
-                            langs: None,
-                            reply: None,
-                            tags: None,
-                            text: args.text,
-                        })
+                    &service
+                        .com
+                        .atproto
+                        .repo
+                        .create_record(
+                            api::com::atproto::repo::create_record::InputData {
+                                collection: "app.bsky.feed.post".parse().expect("valid"),
+                                record: record.try_into_unknown()?,
+                                repo: did.into(),
+                                rkey: None,
+                                swap_commit: None,
+                                validate: None,
+                            }
+                            .into(),
+                        )
                         .await?,
                 )
             }
             Command::DeletePost(args) => self.print(
-                &self
-                    .agent
-                    .api
+                &service
                     .com
                     .atproto
                     .repo
                     .delete_record(
                         api::com::atproto::repo::delete_record::InputData {
                             collection: "app.bsky.feed.post".parse().expect("valid"),
-                            repo: self.handle().await?.into(),
-                            rkey: args.uri.rkey,
+                            repo: did.into(),
+                            rkey: args
+                                .uri
+                                .rkey()
+                                .with_context(|| "Record URI must have a record key")?
+                                .as_str()
+                                .into(),
                             swap_commit: None,
                             swap_record: None,
                         }
@@ -442,17 +553,146 @@ impl Runner {
                     )
                     .await?,
             ),
+            // handled by `run()`
+            Command::Login(_)
+            | Command::OauthLogin(_)
+            | Command::Resolve(_)
+            | Command::ExportRepo(_)
+            | Command::VerifyRepo(_) => unreachable!(),
         }
     }
+    async fn oauth_login(&self, args: OauthLoginArgs) -> Result<()> {
+        let path = self.config_path.with_file_name("oauth.json");
+        let client = oauth_client(self.agent.get_endpoint().await, path.clone())?;
+        let url = client
+            .authorize(
+                args.identifier,
+                AuthorizeOptions { scopes: oauth_scopes(), ..Default::default() },
+            )
+            .await?;
+        println!("Open the following URL and sign in:\n{url}");
+        // The localhost redirect is not served, so the redirected URL is pasted from the browser.
+        print!("Redirected URL: ");
+        stdout().lock().flush()?;
+        let mut redirected = String::new();
+        stdin().lock().read_line(&mut redirected)?;
+        let query = redirected
+            .trim()
+            .split_once('?')
+            .map(|(_, query)| query)
+            .with_context(|| "Redirected URL must have query parameters")?;
+        // The session is saved to the file with its DPoP key by the session store
+        let token_set = client.callback(serde_html_form::from_str(query)?).await?;
+        println!("Login successful as {}! Saved session to {:?}", token_set.sub, path);
+        Ok(())
+    }
+    async fn resolve(&self, args: ResolveArgs) -> Result<()> {
+        let (did_resolver, handle_resolver) = self.identity_resolvers().await?;
+        let did = match args.identifier {
+            AtIdentifier::Did(did) => did,
+            AtIdentifier::Handle(handle) => handle_resolver.resolve(&handle).await?,
+        };
+        let document = did_resolver.resolve(&did).await?;
+        // The handle is verified only if it resolves back to the same DID.
+        let handle = document
+            .also_known_as
+            .iter()
+            .flatten()
+            .find_map(|aka| aka.strip_prefix("at://")?.parse::<Handle>().ok());
+        let handle_verified = match &handle {
+            Some(handle) => handle_resolver.resolve(handle).await.is_ok_and(|d| d == did),
+            None => false,
+        };
+        let resolved =
+            ResolvedIdentity { pds: document.get_pds_endpoint(), did, handle, handle_verified };
+        self.print(&resolved)
+    }
+    async fn export_repo(&self, args: ExportRepoArgs) -> Result<()> {
+        let (did_resolver, handle_resolver) = self.identity_resolvers().await?;
+        let did = match args.actor {
+            AtIdentifier::Did(did) => did,
+            AtIdentifier::Handle(handle) => handle_resolver.resolve(&handle).await?,
+        };
+        let pds = did_resolver
+            .resolve(&did)
+            .await?
+            .get_pds_endpoint()
+            .with_context(|| format!("No PDS endpoint for {}", did.as_str()))?;
+        let car = AtpServiceClient::new(ReqwestClient::new(pds))
+            .service
+            .com
+            .atproto
+            .sync
+            .get_repo(api::com::atproto::sync::get_repo::ParametersData { did, since: None }.into())
+            .await?;
+        write(&args.out, &car).await?;
+        println!("Exported {} bytes to {:?}", car.len(), args.out);
+        Ok(())
+    }
+    async fn verify_repo(&self, args: VerifyRepoArgs) -> Result<()> {
+        let repo = CarRepo::parse(&read(&args.car).await?)?;
+        let (did_resolver, _) = self.identity_resolvers().await?;
+        let document = did_resolver.resolve(&args.did).await?;
+        let public_key = document
+            .get_signing_key()
+            .and_then(|method| method.public_key_multibase.as_deref())
+            .with_context(|| format!("No signing key for {}", args.did.as_str()))?;
+        let summary = repo.verify(args.did.as_str(), public_key)?;
+        println!("Signature: valid");
+        println!("Revision:  {}", summary.rev);
+        println!("Records:   {}", summary.records);
+        println!("MST depth: {}", summary.depth);
+        Ok(())
+    }
+    async fn identity_resolvers(&self) -> Result<(DidResolverImpl, HandleResolverImpl)> {
+        identity_resolvers(self.agent.get_endpoint().await)
+    }
     fn print<T: std::fmt::Debug + Serialize>(&self, result: &T) -> Result<()> {
         if self.debug {
             println!("{:#?}", result);
         } else {
-            println!("{}", serde_json::to_string_pretty(result)?);
+            println!("{}", self.output.format(result)?);
         }
         Ok(())
     }
-    async fn handle(&self) -> Result<Handle> {
-        Ok(self.agent.get_session().await.with_context(|| "Not logged in")?.data.handle)
-    }
+}
+
+fn identity_resolvers(endpoint: String) -> Result<(DidResolverImpl, HandleResolverImpl)> {
+    let http_client = Arc::new(ReqwestClient::new(endpoint));
+    Ok((
+        CommonDidResolver::new(CommonDidResolverConfig {
+            plc_directory_url: DEFAULT_PLC_DIRECTORY_URL.to_string(),
+            http_client: http_client.clone(),
+        }),
+        AtprotoHandleResolver::new(AtprotoHandleResolverConfig {
+            dns_txt_resolver: HickoryDnsTxtResolver::new()?,
+            http_client,
+        }),
+    ))
+}
+
+fn oauth_scopes() -> Vec<Scope> {
+    vec![Scope::Known(KnownScope::Atproto), Scope::Known(KnownScope::TransitionGeneric)]
+}
+
+/// Create an OAuth client for a loopback client, which saves its session to `path`.
+fn oauth_client(endpoint: String, path: PathBuf) -> Result<OAuthClientImpl> {
+    let (did_resolver, handle_resolver) = identity_resolvers(endpoint)?;
+    Ok(OAuthClient::new(OAuthClientConfig {
+        client_metadata: AtprotoLocalhostClientMetadata {
+            redirect_uris: Some(vec![String::from("http://127.0.0.1/callback")]),
+            scopes: Some(oauth_scopes()),
+        },
+        keys: None,
+        resolver: OAuthResolverConfig {
+            did_resolver,
+            handle_resolver,
+            authorization_server_metadata: Default::default(),
+            protected_resource_metadata: Default::default(),
+        },
+        state_store: MemoryStateStore::default(),
+        session_store: SessionFile::new(path),
+        state_ttl: None,
+        dpop_proof_inspector: None,
+    })?)
 }
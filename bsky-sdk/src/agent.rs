@@ -1,23 +1,62 @@
 //! Implementation of [`BskyAgent`] and their builders.
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod builder;
 pub mod config;
+mod feed;
+mod notification;
+#[cfg_attr(docsrs, doc(cfg(feature = "video")))]
+#[cfg(feature = "video")]
+mod video;
 
 pub use self::builder::BskyAgentBuilder;
 use self::config::Config;
+pub use self::feed::Posts;
+pub use self::notification::Notifications;
 use crate::error::Result;
 use crate::moderation::util::interpret_label_value_definitions;
-use crate::moderation::{ModerationPrefsLabeler, Moderator};
-use crate::preference::{FeedViewPreferenceData, Preferences, ThreadViewPreferenceData};
+use crate::moderation::{ModerationPrefs, Moderator};
+use crate::preference::{
+    FeedViewPreferenceData, Preferences, RawPreferences, ThreadViewPreferenceData,
+};
 use atrium_api::agent::store::MemorySessionStore;
 use atrium_api::agent::{store::SessionStore, AtpAgent};
 use atrium_api::app::bsky::actor::defs::PreferencesItem;
+use atrium_api::client::AtpServiceClient;
+use atrium_api::com::atproto::repo::describe_repo;
+#[cfg(feature = "upload-progress")]
+use atrium_api::com::atproto::repo::upload_blob;
+use atrium_api::com::atproto::sync::get_blob;
+use atrium_api::types::string::{AtIdentifier, Cid, Did};
 use atrium_api::types::{Object, Union};
-use atrium_api::xrpc::XrpcClient;
+use atrium_api::xrpc::error::XrpcErrorKind;
+use atrium_api::xrpc::http::{Request, Response};
+use atrium_api::xrpc::types::DownloadSink;
+#[cfg(feature = "upload-progress")]
+use atrium_api::xrpc::{
+    http::{Extensions, Method},
+    types::UploadProgress,
+    InputDataOrBytes, XrpcRequest,
+};
+use atrium_api::xrpc::{HttpClient, XrpcClient};
+use atrium_common::resolver::Resolver;
+use atrium_identity::did::{CommonDidResolver, CommonDidResolverConfig, DEFAULT_PLC_DIRECTORY_URL};
+use atrium_identity::handle::{AppViewHandleResolver, AppViewHandleResolverConfig};
+use atrium_identity::identity_resolver::{IdentityResolver, IdentityResolverConfig};
 #[cfg(feature = "default-client")]
 use atrium_xrpc_client::reqwest::ReqwestClient;
-use std::collections::HashMap;
+use futures::io::AsyncRead;
+use futures::{future, ready, Future};
+use std::collections::{HashMap, VecDeque};
+use std::io::Read;
 use std::ops::Deref;
-use std::sync::Arc;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+/// The errors of `com.atproto.repo.describeRepo` for a repository that is not available.
+const REPO_UNAVAILABLE_ERRORS: [&str; 3] = ["RepoNotFound", "RepoDeactivated", "RepoTakendown"];
 
 /// A Bluesky agent.
 ///
@@ -85,12 +124,7 @@ where
     ///
     /// `enable_bsky_labeler` - If `true`, the [Bluesky's moderation labeler](atrium_api::agent::bluesky::BSKY_LABELER_DID) will be included in the moderation preferences.
     pub async fn get_preferences(&self, enable_bsky_labeler: bool) -> Result<Preferences> {
-        let mut prefs = Preferences::default();
-        if enable_bsky_labeler {
-            prefs.moderation_prefs.labelers.push(ModerationPrefsLabeler::default());
-        }
-        let mut label_prefs = Vec::new();
-        for pref in self
+        let preferences = self
             .api
             .app
             .bsky
@@ -100,15 +134,17 @@ where
             )
             .await?
             .data
-            .preferences
-        {
+            .preferences;
+        let mut prefs = Preferences {
+            // the label value definitions are not known until the labelers are read from here
+            moderation_prefs: ModerationPrefs::from_preferences(&preferences, &HashMap::new()),
+            ..Default::default()
+        };
+        if !enable_bsky_labeler {
+            prefs.moderation_prefs.labelers.retain(|labeler| !labeler.is_default_labeler);
+        }
+        for pref in preferences {
             match pref {
-                Union::Refs(PreferencesItem::AdultContentPref(p)) => {
-                    prefs.moderation_prefs.adult_content_enabled = p.enabled;
-                }
-                Union::Refs(PreferencesItem::ContentLabelPref(p)) => {
-                    label_prefs.push(p);
-                }
                 Union::Refs(PreferencesItem::SavedFeedsPrefV2(p)) => {
                     prefs.saved_feeds = p.data.items;
                 }
@@ -150,43 +186,43 @@ where
                         extra_data: p.extra_data, // pass through extra data
                     };
                 }
-                Union::Refs(PreferencesItem::MutedWordsPref(p)) => {
-                    prefs.moderation_prefs.muted_words = p.data.items;
-                }
-                Union::Refs(PreferencesItem::HiddenPostsPref(p)) => {
-                    prefs.moderation_prefs.hidden_posts = p.data.items;
-                }
-                Union::Refs(PreferencesItem::LabelersPref(p)) => {
-                    prefs.moderation_prefs.labelers.extend(p.data.labelers.into_iter().map(
-                        |item| ModerationPrefsLabeler {
-                            did: item.data.did,
-                            labels: HashMap::default(),
-                            is_default_labeler: false,
-                        },
-                    ));
-                }
                 _ => {
-                    // TODO
-                }
-            }
-        }
-        for pref in label_prefs {
-            if let Some(did) = pref.data.labeler_did {
-                if let Some(l) = prefs.moderation_prefs.labelers.iter_mut().find(|l| l.did == did) {
-                    l.labels.insert(
-                        pref.data.label,
-                        pref.data.visibility.parse().expect("invalid visibility"),
-                    );
+                    // moderation preferences are read by `ModerationPrefs::from_preferences`
                 }
-            } else {
-                prefs.moderation_prefs.labels.insert(
-                    pref.data.label,
-                    pref.data.visibility.parse().expect("invalid visibility"),
-                );
             }
         }
         Ok(prefs)
     }
+    /// Get the logged-in user's preferences as [`RawPreferences`], keeping all items as they are.
+    pub async fn get_raw_preferences(&self) -> Result<RawPreferences> {
+        Ok(self
+            .api
+            .app
+            .bsky
+            .actor
+            .get_preferences(
+                atrium_api::app::bsky::actor::get_preferences::ParametersData {}.into(),
+            )
+            .await?
+            .data
+            .preferences
+            .into())
+    }
+    /// Replace the logged-in user's preferences with the given [`RawPreferences`].
+    pub async fn put_raw_preferences(&self, preferences: RawPreferences) -> Result<()> {
+        Ok(self
+            .api
+            .app
+            .bsky
+            .actor
+            .put_preferences(
+                atrium_api::app::bsky::actor::put_preferences::InputData {
+                    preferences: preferences.into_inner(),
+                }
+                .into(),
+            )
+            .await?)
+    }
     /// Configure the labelers header.
     ///
     /// Read labelers preferences from the provided [`Preferences`] and set the labelers header up to 10 labelers.
@@ -243,6 +279,271 @@ where
             label_defs,
         ))
     }
+    /// Upload a blob to the PDS of the session, like `com.atproto.repo.uploadBlob`,
+    /// calling `progress` with the number of bytes sent so far and the total size.
+    ///
+    /// The progress is only reported if the HTTP client supports [`UploadProgress`], as
+    /// [`ReqwestClient`] does on non-wasm targets with the `upload-progress` feature.
+    /// As with the other requests, the session is refreshed and the upload is retried if the
+    /// access token has expired.
+    #[cfg_attr(docsrs, doc(cfg(feature = "upload-progress")))]
+    #[cfg(feature = "upload-progress")]
+    pub async fn upload_blob_with_progress(
+        &self,
+        data: Vec<u8>,
+        progress: impl Fn(u64, u64) + Send + Sync + 'static,
+    ) -> Result<upload_blob::Output> {
+        let mut extensions = Extensions::new();
+        extensions.insert(UploadProgress::new(progress));
+        Ok(self
+            .send_xrpc_with_extensions::<(), Vec<u8>, _, upload_blob::Error>(
+                &XrpcRequest {
+                    method: Method::POST,
+                    nsid: upload_blob::NSID.into(),
+                    parameters: None,
+                    input: Some(InputDataOrBytes::Bytes(data)),
+                    encoding: Some(String::from("*/*")),
+                },
+                extensions,
+            )
+            .await?
+            .into_data::<upload_blob::Error>()?)
+    }
+}
+
+impl<T, S> BskyAgent<T, S>
+where
+    T: XrpcClient + Send + Sync + 'static,
+    S: SessionStore + Send + Sync + 'static,
+{
+    /// Fetch a blob from the PDS of the account that owns it, with `com.atproto.sync.getBlob`.
+    ///
+    /// The PDS endpoint is resolved from the DID document of `did`, so this works for blobs of
+    /// any account, not only the logged-in one, as in [`for_actor()`](Self::for_actor).
+    pub async fn get_blob(&self, did: &Did, cid: &Cid) -> Result<Vec<u8>> {
+        Ok(self
+            .for_actor(&AtIdentifier::Did(did.clone()))
+            .await?
+            .service
+            .com
+            .atproto
+            .sync
+            .get_blob(get_blob::ParametersData { cid: cid.clone(), did: did.clone() }.into())
+            .await?)
+    }
+    /// Fetch a blob like [`get_blob()`](Self::get_blob), and return it as an [`AsyncRead`]
+    /// which streams the blob as it is downloaded.
+    ///
+    /// This returns once the first chunk of the blob has been downloaded, and the rest is
+    /// downloaded as it is read. The blob is only streamed if the HTTP client supports
+    /// [`DownloadSink`], as [`ReqwestClient`] does on non-wasm targets. Otherwise the whole blob
+    /// is read into memory before this returns.
+    ///
+    /// # Errors
+    ///
+    /// Errors before the first chunk (e.g. the blob is not found) are returned from this method.
+    /// Errors after that are returned from the reader as [`std::io::Error`]s.
+    pub async fn get_blob_reader(&self, did: &Did, cid: &Cid) -> Result<impl AsyncRead + Unpin> {
+        let endpoint = self.resolve_pds_endpoint(&AtIdentifier::Did(did.clone())).await?;
+        let buffer = Arc::new(Mutex::new(VecDeque::new()));
+        let sink = {
+            let buffer = Arc::clone(&buffer);
+            DownloadSink::new(move |chunk| {
+                buffer.lock().expect("failed to lock buffer").extend(chunk);
+            })
+        };
+        let client =
+            EndpointClient { client: self.inner.xrpc_client(), endpoint, sink: Some(sink) };
+        let params = get_blob::ParametersData { cid: cid.clone(), did: did.clone() }.into();
+        let download = async move {
+            Ok(AtpServiceClient::new(client).service.com.atproto.sync.get_blob(params).await?)
+        };
+        let mut reader = BlobReader { download: Some(Box::pin(download)), buffer };
+        future::poll_fn(|cx| reader.poll_buffered(cx)).await?;
+        Ok(reader)
+    }
+    /// Describe the repository of `actor` with `com.atproto.repo.describeRepo`, sent to the PDS
+    /// that hosts it.
+    ///
+    /// The output includes the DID, the handle and whether it is currently valid
+    /// (`handle_is_correct`), and the collections which contain at least one record.
+    pub async fn describe_repo(&self, actor: &AtIdentifier) -> Result<describe_repo::Output> {
+        Ok(self
+            .for_actor(actor)
+            .await?
+            .service
+            .com
+            .atproto
+            .repo
+            .describe_repo(describe_repo::ParametersData { repo: actor.clone() }.into())
+            .await?)
+    }
+    /// Check whether the repository of `actor` exists and is available,
+    /// with `com.atproto.repo.describeRepo` like [`describe_repo()`](Self::describe_repo).
+    ///
+    /// Returns `false` if the PDS responds with `RepoNotFound`, `RepoDeactivated` or
+    /// `RepoTakendown`. Any other error, including a failure to resolve the identity of `actor`,
+    /// is returned as it is.
+    pub async fn repo_exists(&self, actor: &AtIdentifier) -> Result<bool> {
+        let result = self
+            .for_actor(actor)
+            .await?
+            .service
+            .com
+            .atproto
+            .repo
+            .describe_repo(describe_repo::ParametersData { repo: actor.clone() }.into())
+            .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(atrium_api::xrpc::Error::XrpcResponse(response))
+                if matches!(
+                    &response.error,
+                    Some(XrpcErrorKind::Undefined(body))
+                        if REPO_UNAVAILABLE_ERRORS.contains(&body.error.as_deref().unwrap_or_default())
+                ) =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+    /// Get a client for the PDS that hosts the repository of `actor`,
+    /// e.g. for reading records of other accounts.
+    ///
+    /// The PDS endpoint is resolved with [`atrium_identity`]: a handle is resolved to a DID with
+    /// `com.atproto.identity.resolveHandle` and verified against the DID document, and the DID
+    /// document is fetched from the PLC directory (`did:plc`) or the web (`did:web`).
+    /// The client sends requests with the HTTP client of this agent, but without its session,
+    /// because session tokens are only valid for the PDS of the logged-in account.
+    pub async fn for_actor(
+        &self,
+        actor: &AtIdentifier,
+    ) -> Result<AtpServiceClient<impl XrpcClient + Send + Sync>> {
+        Ok(AtpServiceClient::new(EndpointClient {
+            client: self.inner.xrpc_client(),
+            endpoint: self.resolve_pds_endpoint(actor).await?,
+            sink: None,
+        }))
+    }
+    async fn resolve_pds_endpoint(&self, actor: &AtIdentifier) -> Result<String> {
+        let http_client = Arc::new(AgentHttpClient { client: self.inner.xrpc_client() });
+        let resolver = IdentityResolver::new(IdentityResolverConfig {
+            did_resolver: CommonDidResolver::new(CommonDidResolverConfig {
+                plc_directory_url: DEFAULT_PLC_DIRECTORY_URL.into(),
+                http_client: Arc::clone(&http_client),
+            }),
+            handle_resolver: AppViewHandleResolver::new(AppViewHandleResolverConfig {
+                service_url: self.get_endpoint().await,
+                http_client,
+            }),
+        });
+        Ok(resolver.resolve(actor.as_ref()).await?.pds)
+    }
+}
+
+/// An [`AsyncRead`] of a blob, returned by [`BskyAgent::get_blob_reader()`].
+///
+/// The chunks of the blob are buffered by the [`DownloadSink`] of the `download` request,
+/// which is polled as the reader is read.
+struct BlobReader<F> {
+    download: Option<Pin<Box<F>>>,
+    buffer: Arc<Mutex<VecDeque<u8>>>,
+}
+
+impl<F> BlobReader<F>
+where
+    F: Future<Output = Result<Vec<u8>>>,
+{
+    /// Poll the download until a chunk is buffered or the download is complete.
+    fn poll_buffered(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let Some(download) = self.download.as_mut() else {
+            return Poll::Ready(Ok(()));
+        };
+        let poll = download.as_mut().poll(cx);
+        let mut buffer = self.buffer.lock().expect("failed to lock buffer");
+        match poll {
+            Poll::Ready(result) => {
+                self.download = None;
+                // The whole blob is returned if the HTTP client does not support the sink.
+                buffer.extend(result?);
+                Poll::Ready(Ok(()))
+            }
+            Poll::Pending if buffer.is_empty() => Poll::Pending,
+            Poll::Pending => Poll::Ready(Ok(())),
+        }
+    }
+}
+
+impl<F> AsyncRead for BlobReader<F>
+where
+    F: Future<Output = Result<Vec<u8>>>,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        if this.buffer.lock().expect("failed to lock buffer").is_empty() {
+            if let Err(err) = ready!(this.poll_buffered(cx)) {
+                return Poll::Ready(Err(std::io::Error::other(err)));
+            }
+        }
+        Poll::Ready(this.buffer.lock().expect("failed to lock buffer").read(buf))
+    }
+}
+
+/// An HTTP client that sends requests with the HTTP client of the agent, for identity resolution.
+struct AgentHttpClient<T> {
+    client: Arc<T>,
+}
+
+impl<T> HttpClient for AgentHttpClient<T>
+where
+    T: XrpcClient + Send + Sync,
+{
+    async fn send_http(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        self.client.send_http(request).await
+    }
+}
+
+/// An XRPC client that sends requests to a fixed endpoint without the agent's session.
+///
+/// Returned by [`BskyAgent::for_actor()`], wrapped in an [`AtpServiceClient`].
+struct EndpointClient<T> {
+    client: Arc<T>,
+    endpoint: String,
+    sink: Option<DownloadSink>,
+}
+
+impl<T> HttpClient for EndpointClient<T>
+where
+    T: XrpcClient + Send + Sync,
+{
+    async fn send_http(
+        &self,
+        mut request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        if let Some(sink) = &self.sink {
+            request.extensions_mut().insert(sink.clone());
+        }
+        self.client.send_http(request).await
+    }
+}
+
+impl<T> XrpcClient for EndpointClient<T>
+where
+    T: XrpcClient + Send + Sync,
+{
+    fn base_uri(&self) -> String {
+        self.endpoint.clone()
+    }
 }
 
 impl<T, S> Deref for BskyAgent<T, S>
@@ -260,7 +561,106 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::Error;
+    use crate::tests::FAKE_CID;
     use atrium_api::agent::Session;
+    use atrium_api::xrpc::types::Header;
+    use futures::io::AsyncReadExt;
+
+    struct MockClient;
+
+    impl HttpClient for MockClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            assert!(request.headers().get("authorization").is_none());
+            let uri = request.uri().to_string();
+            let (content_type, body) = match uri.split_once('?').map_or(uri.as_str(), |(u, _)| u) {
+                "https://plc.directory/did:plc:blobowner"
+                | "https://plc.directory/did:plc:gone"
+                | "https://plc.directory/did:plc:deactivated"
+                | "https://plc.directory/did:plc:invalid"
+                | "https://example.com/user/did.json" => (
+                    "application/json",
+                    serde_json::to_vec(&serde_json::json!({
+                        "id": uri
+                            .strip_prefix("https://plc.directory/")
+                            .unwrap_or("did:web:example.com:user"),
+                        "alsoKnownAs": ["at://owner.example.com"],
+                        "service": [{
+                            "id": "#atproto_pds",
+                            "type": "AtprotoPersonalDataServer",
+                            "serviceEndpoint": "https://pds.example.com",
+                        }],
+                    }))?,
+                ),
+                "https://bsky.social/xrpc/com.atproto.identity.resolveHandle"
+                    if uri.ends_with("handle=owner.example.com")
+                        || uri.ends_with("handle=other.example.com") =>
+                {
+                    ("application/json", br#"{"did":"did:plc:blobowner"}"#.to_vec())
+                }
+                "https://pds.example.com/xrpc/com.atproto.repo.describeRepo" => {
+                    if !uri.ends_with("repo=did%3Aplc%3Ablobowner")
+                        && !uri.ends_with("repo=owner.example.com")
+                    {
+                        let error = match uri.rsplit_once("repo=did%3Aplc%3A") {
+                            Some((_, "deactivated")) => "RepoDeactivated",
+                            Some((_, "invalid")) => "InvalidRequest",
+                            _ => "RepoNotFound",
+                        };
+                        return Ok(Response::builder()
+                            .header(Header::ContentType, "application/json")
+                            .status(400)
+                            .body(serde_json::to_vec(&serde_json::json!({ "error": error }))?)?);
+                    }
+                    (
+                        "application/json",
+                        serde_json::to_vec(&serde_json::json!({
+                            "collections": ["app.bsky.feed.post"],
+                            "did": "did:plc:blobowner",
+                            "didDoc": {},
+                            "handle": "owner.example.com",
+                            "handleIsCorrect": true,
+                        }))?,
+                    )
+                }
+                "https://pds.example.com/xrpc/com.atproto.sync.getBlob" => {
+                    if !uri.contains("did=did%3Aplc%3Ablobowner") {
+                        return Ok(Response::builder()
+                            .header(Header::ContentType, "application/json")
+                            .status(400)
+                            .body(br#"{"error":"BlobNotFound"}"#.to_vec())?);
+                    }
+                    // Simulate an HTTP client streaming the body in two chunks.
+                    if let Some(sink) = request.extensions().get::<DownloadSink>() {
+                        sink.write(b"bl");
+                        sink.write(b"ob");
+                        return Ok(Response::builder()
+                            .header(Header::ContentType, "image/png")
+                            .status(200)
+                            .body(Vec::new())?);
+                    }
+                    ("image/png", b"blob".to_vec())
+                }
+                _ => return Ok(Response::builder().status(404).body(Vec::new())?),
+            };
+            Ok(Response::builder()
+                .header(Header::ContentType, content_type)
+                .status(200)
+                .body(body)?)
+        }
+    }
+
+    impl XrpcClient for MockClient {
+        fn base_uri(&self) -> String {
+            String::from("https://bsky.social")
+        }
+    }
 
     #[derive(Clone)]
     struct NoopStore;
@@ -287,4 +687,185 @@ mod tests {
         agent.configure_endpoint(String::from("https://example.com"));
         assert_eq!(cloned.get_endpoint().await, "https://example.com");
     }
+
+    #[tokio::test]
+    async fn get_blob() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient).build().await?;
+        let cid = FAKE_CID.parse::<Cid>().expect("invalid cid");
+        let did = "did:plc:blobowner".parse::<Did>().expect("invalid did");
+        assert_eq!(agent.get_blob(&did, &cid).await?, b"blob");
+
+        let mut buf = Vec::new();
+        agent.get_blob_reader(&did, &cid).await?.read_to_end(&mut buf).await.expect("read failed");
+        assert_eq!(buf, b"blob");
+        let other = "did:plc:gone".parse::<Did>().expect("invalid did");
+        assert!(matches!(agent.get_blob_reader(&other, &cid).await, Err(Error::Xrpc(_))));
+
+        let unknown = "did:plc:unknown".parse::<Did>().expect("invalid did");
+        assert!(matches!(agent.get_blob(&unknown, &cid).await, Err(Error::Identity(_))));
+        // `did:web` with a path is resolved to the PDS, which does not have the blob.
+        let web = "did:web:example.com:user".parse::<Did>().expect("invalid did");
+        assert!(matches!(agent.get_blob(&web, &cid).await, Err(Error::Xrpc(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn describe_repo() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient).build().await?;
+        let owner = "owner.example.com".parse::<AtIdentifier>().expect("invalid handle");
+        let output = agent.describe_repo(&owner).await?;
+        assert_eq!(output.did.as_str(), "did:plc:blobowner");
+        assert!(output.handle_is_correct);
+        assert_eq!(output.collections, ["app.bsky.feed.post".parse().expect("invalid nsid")]);
+
+        assert!(agent.repo_exists(&owner).await?);
+        let did = "did:plc:blobowner".parse().expect("invalid did");
+        assert!(agent.repo_exists(&did).await?);
+        // The PDS of this DID does not have the repo.
+        let gone = "did:plc:gone".parse().expect("invalid did");
+        assert!(!agent.repo_exists(&gone).await?);
+        let deactivated = "did:plc:deactivated".parse().expect("invalid did");
+        assert!(!agent.repo_exists(&deactivated).await?);
+        // Other client errors are not about the availability of the repo.
+        let invalid = "did:plc:invalid".parse().expect("invalid did");
+        assert!(matches!(agent.repo_exists(&invalid).await, Err(Error::Xrpc(_))));
+        let unknown_handle = "unknown.example.com".parse().expect("invalid handle");
+        assert!(matches!(agent.repo_exists(&unknown_handle).await, Err(Error::Identity(_))));
+        let unknown_did = "did:plc:unknown".parse().expect("invalid did");
+        assert!(matches!(agent.repo_exists(&unknown_did).await, Err(Error::Identity(_))));
+        Ok(())
+    }
+
+    #[cfg(feature = "upload-progress")]
+    #[tokio::test]
+    async fn upload_blob_with_progress() -> Result<()> {
+        use atrium_api::agent::store::MemorySessionStore;
+        use atrium_api::com::atproto::server::create_session::OutputData;
+
+        struct UploadClient;
+
+        impl HttpClient for UploadClient {
+            async fn send_http(
+                &self,
+                request: Request<Vec<u8>>,
+            ) -> core::result::Result<
+                Response<Vec<u8>>,
+                Box<dyn std::error::Error + Send + Sync + 'static>,
+            > {
+                let token = request.headers().get("authorization").and_then(|v| v.to_str().ok());
+                if request.uri().path() == "/xrpc/com.atproto.server.refreshSession" {
+                    assert_eq!(token, Some("Bearer refresh"));
+                    let output = serde_json::json!({
+                        "accessJwt": "access",
+                        "refreshJwt": "refresh",
+                        "did": "did:web:example.com",
+                        "handle": "example.com",
+                    });
+                    return Ok(Response::builder()
+                        .header(Header::ContentType, "application/json")
+                        .status(200)
+                        .body(serde_json::to_vec(&output)?)?);
+                }
+                assert_eq!(
+                    request.uri().to_string(),
+                    "https://bsky.social/xrpc/com.atproto.repo.uploadBlob"
+                );
+                if token == Some("Bearer expired") {
+                    return Ok(Response::builder()
+                        .header(Header::ContentType, "application/json")
+                        .status(400)
+                        .body(br#"{"error":"ExpiredToken"}"#.to_vec())?);
+                }
+                assert_eq!(token, Some("Bearer access"));
+                // Simulate an HTTP client reporting the progress in two steps.
+                let progress =
+                    request.extensions().get::<UploadProgress>().expect("no upload progress");
+                let total = request.body().len() as u64;
+                progress.report(total / 2, total);
+                progress.report(total, total);
+                let output = serde_json::json!({
+                    "blob": {
+                        "$type": "blob",
+                        "ref": { "$link": FAKE_CID },
+                        "mimeType": "image/png",
+                        "size": total,
+                    },
+                });
+                Ok(Response::builder()
+                    .header(Header::ContentType, "application/json")
+                    .status(200)
+                    .body(serde_json::to_vec(&output)?)?)
+            }
+        }
+
+        impl XrpcClient for UploadClient {
+            fn base_uri(&self) -> String {
+                String::from("https://bsky.social")
+            }
+        }
+
+        let store = MemorySessionStore::default();
+        store
+            .set_session(
+                OutputData {
+                    access_jwt: String::from("expired"),
+                    active: None,
+                    did: "did:web:example.com".parse().expect("invalid did"),
+                    did_doc: None,
+                    email: None,
+                    email_auth_factor: None,
+                    email_confirmed: None,
+                    handle: "example.com".parse().expect("invalid handle"),
+                    refresh_jwt: String::from("refresh"),
+                    status: None,
+                }
+                .into(),
+            )
+            .await;
+        let agent = BskyAgentBuilder::new(UploadClient).store(store).build().await?;
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let output = {
+            let reports = Arc::clone(&reports);
+            agent
+                .upload_blob_with_progress(vec![0; 10], move |sent, total| {
+                    reports.lock().unwrap().push((sent, total))
+                })
+                .await?
+        };
+        assert!(matches!(output.data.blob, atrium_api::types::BlobRef::Typed(_)));
+        // The upload is retried with the refreshed session.
+        assert_eq!(*reports.lock().unwrap(), [(5, 10), (10, 10)]);
+        assert_eq!(agent.get_session().await.map(|s| s.data.access_jwt).as_deref(), Some("access"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn for_actor() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient).build().await?;
+        let client = agent.for_actor(&"owner.example.com".parse().expect("invalid handle")).await?;
+        let output = client
+            .service
+            .com
+            .atproto
+            .sync
+            .get_blob(
+                get_blob::ParametersData {
+                    cid: FAKE_CID.parse().expect("invalid cid"),
+                    did: "did:plc:blobowner".parse().expect("invalid did"),
+                }
+                .into(),
+            )
+            .await?;
+        assert_eq!(output, b"blob");
+
+        let unknown = "unknown.example.com".parse().expect("invalid handle");
+        assert!(matches!(agent.for_actor(&unknown).await, Err(Error::Identity(_))));
+        // The DID document of this DID does not have the handle.
+        let mismatch = "other.example.com".parse().expect("invalid handle");
+        assert!(matches!(
+            agent.for_actor(&mismatch).await,
+            Err(Error::Identity(atrium_identity::Error::HandleMismatch(..)))
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,130 @@
+//! A blocking wrapper of [`BskyAgent`].
+use super::{BskyAgent, BskyAgentBuilder};
+use crate::error::{Error, Result};
+use crate::moderation::Moderator;
+use crate::preference::Preferences;
+use atrium_api::agent::store::{MemorySessionStore, SessionStore};
+use atrium_api::agent::Session;
+use atrium_api::xrpc::XrpcClient;
+#[cfg(feature = "default-client")]
+use atrium_xrpc_client::reqwest::ReqwestClient;
+use std::future::Future;
+use tokio::runtime::{Builder, Runtime};
+
+/// A blocking Bluesky agent.
+///
+/// This wraps a [`BskyAgent`] and runs its asynchronous methods to completion
+/// on an internal current-thread [`tokio`] runtime, so it can be used without an async runtime.
+/// Any other asynchronous call can be run with [`block_on()`](Self::block_on).
+///
+/// This must not be used from within an asynchronous context, as blocking on the runtime would panic.
+///
+/// # Example
+///
+/// ```no_run
+/// use bsky_sdk::agent::blocking::BlockingAgent;
+///
+/// fn main() -> bsky_sdk::Result<()> {
+///     let agent = BlockingAgent::new()?;
+///     agent.login("alice.bsky.social", "app-password")?;
+///     let profile = agent.block_on(agent.agent().api.app.bsky.actor.get_profile(
+///         bsky_sdk::api::app::bsky::actor::get_profile::ParametersData {
+///             actor: "alice.bsky.social".parse().expect("invalid actor"),
+///         }
+///         .into(),
+///     ))?;
+///     println!("{:?}", profile.display_name);
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "default-client")]
+pub struct BlockingAgent<T = ReqwestClient, S = MemorySessionStore>
+where
+    T: XrpcClient + Send + Sync,
+    S: SessionStore + Send + Sync,
+{
+    inner: BskyAgent<T, S>,
+    runtime: Runtime,
+}
+
+#[cfg(not(feature = "default-client"))]
+pub struct BlockingAgent<T, S = MemorySessionStore>
+where
+    T: XrpcClient + Send + Sync,
+    S: SessionStore + Send + Sync,
+{
+    inner: BskyAgent<T, S>,
+    runtime: Runtime,
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "default-client")))]
+#[cfg(feature = "default-client")]
+impl BlockingAgent {
+    /// Create a new agent with the default client and session store.
+    pub fn new() -> Result<Self> {
+        Self::from_builder(BskyAgent::builder())
+    }
+}
+
+impl<T, S> BlockingAgent<T, S>
+where
+    T: XrpcClient + Send + Sync,
+    S: SessionStore + Send + Sync,
+{
+    /// Create a new agent by building the given [`BskyAgentBuilder`] on the internal runtime.
+    pub fn from_builder(builder: BskyAgentBuilder<T, S>) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build().map_err(Error::Runtime)?;
+        let inner = runtime.block_on(builder.build())?;
+        Ok(Self { inner, runtime })
+    }
+    /// Returns the wrapped [`BskyAgent`], e.g. to make API calls with [`block_on()`](Self::block_on).
+    pub fn agent(&self) -> &BskyAgent<T, S> {
+        &self.inner
+    }
+    /// Run a future to completion on the internal runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+    /// Start a new session with this agent.
+    pub fn login(&self, identifier: impl AsRef<str>, password: impl AsRef<str>) -> Result<Session> {
+        Ok(self.block_on(self.inner.login(identifier, password))?)
+    }
+    /// Resume a pre-existing session with this agent.
+    pub fn resume_session(&self, session: Session) -> Result<()> {
+        Ok(self.block_on(self.inner.resume_session(session))?)
+    }
+    /// Get the current session.
+    pub fn get_session(&self) -> Option<Session> {
+        self.block_on(self.inner.get_session())
+    }
+    /// Get the logged-in user's [`Preferences`]. See [`BskyAgent::get_preferences()`].
+    pub fn get_preferences(&self, enable_bsky_labeler: bool) -> Result<Preferences> {
+        self.block_on(self.inner.get_preferences(enable_bsky_labeler))
+    }
+    /// Make a [`Moderator`] instance with the provided [`Preferences`].
+    pub fn moderator(&self, preferences: &Preferences) -> Result<Moderator> {
+        self.block_on(self.inner.moderator(preferences))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::MockClient;
+
+    #[test]
+    fn blocking_agent() -> Result<()> {
+        let agent = BlockingAgent::from_builder(BskyAgentBuilder::new(MockClient))?;
+        assert!(agent.get_session().is_none());
+        let did = agent.block_on(
+            agent.agent().api.com.atproto.identity.resolve_handle(
+                atrium_api::com::atproto::identity::resolve_handle::ParametersData {
+                    handle: "handle.test".parse().expect("invalid handle"),
+                }
+                .into(),
+            ),
+        )?;
+        assert_eq!(did.did.as_str(), "did:fake:handle.test");
+        Ok(())
+    }
+}
@@ -0,0 +1,330 @@
+use super::BskyAgent;
+use crate::error::{Error, Result};
+use atrium_api::agent::bluesky::AtprotoServiceType;
+use atrium_api::agent::store::SessionStore;
+use atrium_api::app::bsky::feed::defs::PostView;
+use atrium_api::app::bsky::feed::{generator, get_feed_skeleton, get_posts};
+use atrium_api::types::string::AtUri;
+use atrium_api::types::TryFromUnknown;
+use atrium_api::xrpc::XrpcClient;
+use futures::{stream, Stream, StreamExt};
+use std::collections::{HashMap, VecDeque};
+
+/// The maximum number of URIs in an `app.bsky.feed.getPosts` request.
+const GET_POSTS_MAX_URIS: usize = 25;
+/// The maximum number of `app.bsky.feed.getPosts` requests in flight in [`BskyAgent::get_posts()`].
+const GET_POSTS_CONCURRENCY: usize = 4;
+
+/// The posts fetched by [`BskyAgent::get_posts()`].
+#[derive(Debug)]
+pub struct Posts {
+    /// The hydrated posts, in the order of the requested URIs.
+    pub posts: Vec<PostView>,
+    /// The URIs that were requested but not returned, e.g. deleted posts.
+    pub not_found: Vec<AtUri>,
+    /// The URIs of the batches whose requests failed, with the errors.
+    pub failed: Vec<(Vec<AtUri>, Error)>,
+}
+
+struct FeedState<A> {
+    feed: AtUri,
+    generator: Option<A>,
+    posts: VecDeque<PostView>,
+    cursor: Option<String>,
+    done: bool,
+}
+
+impl<T, S> BskyAgent<T, S>
+where
+    T: XrpcClient + Send + Sync,
+    S: SessionStore + Send + Sync,
+{
+    /// Get the posts of a custom feed as a [`Stream`], directly from its feed generator,
+    /// fetching the following pages with the cursor as needed.
+    ///
+    /// The service DID of the generator is read from the `app.bsky.feed.generator` record of
+    /// `feed`. The skeleton is requested through the `atproto-proxy` header, and its posts are
+    /// hydrated with `app.bsky.feed.getPosts` in the order of the skeleton. Posts that cannot be
+    /// hydrated (e.g. deleted ones) are skipped.
+    ///
+    /// Pass the `cursor` returned by a feed generator to start from a later page.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields an error and ends if a request fails, or if the `feed` is not a
+    /// feed generator record.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bsky_sdk::{BskyAgent, Result};
+    /// use futures::{StreamExt, TryStreamExt};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let agent = BskyAgent::builder().build().await?;
+    ///     let feed = "at://did:plc:z72i7hdynmk6r22z27h6tvur/app.bsky.feed.generator/whats-hot"
+    ///         .parse()
+    ///         .expect("invalid at uri");
+    ///     let posts = agent.get_feed(feed, None).take(30).try_collect::<Vec<_>>().await?;
+    ///     for post in posts {
+    ///         println!("{}: {}", post.author.handle.as_str(), post.uri);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_feed(
+        &self,
+        feed: AtUri,
+        cursor: Option<String>,
+    ) -> impl Stream<Item = Result<PostView>> + '_ {
+        let state =
+            FeedState { feed, generator: None, posts: VecDeque::new(), cursor, done: false };
+        stream::try_unfold(state, move |mut state| async move {
+            loop {
+                if let Some(post) = state.posts.pop_front() {
+                    return Ok(Some((post, state)));
+                }
+                if state.done {
+                    return Ok(None);
+                }
+                let generator = match state.generator.take() {
+                    Some(generator) => generator,
+                    None => {
+                        let record = self.get_record_by_uri(&state.feed).await?;
+                        let did = generator::RecordData::try_from_unknown(record.data.value)?.did;
+                        self.api_with_proxy(did, AtprotoServiceType::BskyFeedGenerator)
+                    }
+                };
+                let output = generator
+                    .app
+                    .bsky
+                    .feed
+                    .get_feed_skeleton(
+                        get_feed_skeleton::ParametersData {
+                            cursor: state.cursor.take(),
+                            feed: state.feed.to_string(),
+                            limit: None,
+                        }
+                        .into(),
+                    )
+                    .await?;
+                state.generator = Some(generator);
+                state.done = output.data.cursor.is_none() || output.data.feed.is_empty();
+                state.cursor = output.data.cursor;
+                let uris = output
+                    .data
+                    .feed
+                    .into_iter()
+                    .map(|item| item.data.post.parse::<AtUri>().or(Err(Error::InvalidAtUri)))
+                    .collect::<Result<Vec<_>>>()?;
+                let posts = self.get_posts(&uris).await;
+                if let Some((_, err)) = posts.failed.into_iter().next() {
+                    return Err(err);
+                }
+                state.posts = posts.posts.into();
+            }
+        })
+    }
+    /// Get the hydrated views of posts with `app.bsky.feed.getPosts`.
+    ///
+    /// The URIs are split into batches of the maximum size of a request (25), which are
+    /// requested concurrently, up to 4 at a time. The posts are returned in the order of `uris`.
+    /// Posts are matched with their URIs, so the URIs should have DIDs rather than handles as
+    /// their authorities.
+    ///
+    /// This does not fail as a whole: the URIs that could not be fetched are returned in
+    /// [`Posts::not_found`] and [`Posts::failed`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bsky_sdk::{BskyAgent, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let agent = BskyAgent::builder().build().await?;
+    ///     let uris = ["3kxmfwtgfxl2w", "3kxmfwtgfxl3w"]
+    ///         .map(|rkey| format!("at://did:fake:handle.test/app.bsky.feed.post/{rkey}"))
+    ///         .map(|uri| uri.parse().expect("invalid at uri"));
+    ///     let posts = agent.get_posts(&uris).await;
+    ///     if let Some((_, err)) = posts.failed.into_iter().next() {
+    ///         return Err(err);
+    ///     }
+    ///     for post in posts.posts {
+    ///         println!("{}: {}", post.author.handle.as_str(), post.uri);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_posts(&self, uris: &[AtUri]) -> Posts {
+        let results = stream::iter(uris.chunks(GET_POSTS_MAX_URIS).map(|uris| async move {
+            let result = self
+                .api
+                .app
+                .bsky
+                .feed
+                .get_posts(
+                    get_posts::ParametersData { uris: uris.iter().map(AtUri::to_string).collect() }
+                        .into(),
+                )
+                .await;
+            (uris, result)
+        }))
+        .buffered(GET_POSTS_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+        let mut output = Posts { posts: Vec::new(), not_found: Vec::new(), failed: Vec::new() };
+        for (uris, result) in results {
+            let posts = match result {
+                Ok(fetched) => fetched
+                    .data
+                    .posts
+                    .into_iter()
+                    .map(|post| (post.uri.clone(), post))
+                    .collect::<HashMap<_, _>>(),
+                Err(err) => {
+                    output.failed.push((uris.to_vec(), err.into()));
+                    continue;
+                }
+            };
+            for uri in uris {
+                match posts.get(&uri.to_string()) {
+                    Some(post) => output.posts.push(post.clone()),
+                    None => output.not_found.push(uri.clone()),
+                }
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::BskyAgentBuilder;
+    use crate::tests::FAKE_CID;
+    use atrium_api::xrpc::http::{Request, Response};
+    use atrium_api::xrpc::types::Header;
+    use atrium_api::xrpc::HttpClient;
+    use futures::TryStreamExt;
+
+    const FEED_URI: &str = "at://did:fake:handle.test/app.bsky.feed.generator/feed";
+
+    fn post_uri(rkey: &str) -> String {
+        format!("at://did:fake:handle.test/app.bsky.feed.post/{rkey}")
+    }
+
+    fn post_view(rkey: &str) -> serde_json::Value {
+        serde_json::json!({
+            "uri": post_uri(rkey),
+            "cid": FAKE_CID,
+            "author": {"did": "did:fake:handle.test", "handle": "handle.test"},
+            "record": {},
+            "indexedAt": "2024-10-01T00:00:00.000Z",
+        })
+    }
+
+    struct MockClient;
+
+    impl HttpClient for MockClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            let query = request.uri().query().unwrap_or_default();
+            let body = match request.uri().path() {
+                "/xrpc/com.atproto.repo.getRecord" => serde_json::json!({
+                    "uri": FEED_URI,
+                    "value": {
+                        "$type": "app.bsky.feed.generator",
+                        "did": "did:web:feed.test",
+                        "displayName": "Feed",
+                        "createdAt": "2024-10-01T00:00:00.000Z",
+                    },
+                }),
+                "/xrpc/app.bsky.feed.getFeedSkeleton" => {
+                    assert_eq!(
+                        request.headers().get("atproto-proxy").map(|value| value.as_bytes()),
+                        Some(b"did:web:feed.test#bsky_fg".as_slice())
+                    );
+                    let (posts, cursor) = if query.contains("cursor=2") {
+                        (vec!["4"], None)
+                    } else {
+                        (vec!["1", "2", "3"], Some("2"))
+                    };
+                    serde_json::json!({
+                        "feed": posts
+                            .into_iter()
+                            .map(|rkey| serde_json::json!({ "post": post_uri(rkey) }))
+                            .collect::<Vec<_>>(),
+                        "cursor": cursor,
+                    })
+                }
+                "/xrpc/app.bsky.feed.getPosts" => {
+                    assert_eq!(request.headers().get("atproto-proxy"), None);
+                    assert!(query.split('&').count() <= GET_POSTS_MAX_URIS);
+                    if query.contains("%2Ffail") {
+                        return Ok(Response::builder().status(500).body(Vec::new())?);
+                    }
+                    // returned in reverse order, without the deleted post "2"
+                    let mut posts = query
+                        .split('&')
+                        .filter_map(|param| param.rsplit("%2F").next())
+                        .filter(|rkey| *rkey != "2")
+                        .map(post_view)
+                        .collect::<Vec<_>>();
+                    posts.reverse();
+                    serde_json::json!({ "posts": posts })
+                }
+                _ => return Ok(Response::builder().status(404).body(Vec::new())?),
+            };
+            Ok(Response::builder()
+                .header(Header::ContentType, "application/json")
+                .status(200)
+                .body(serde_json::to_vec(&body)?)?)
+        }
+    }
+
+    impl XrpcClient for MockClient {
+        fn base_uri(&self) -> String {
+            String::from("https://bsky.social")
+        }
+    }
+
+    #[tokio::test]
+    async fn get_feed() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient).build().await?;
+        let feed = FEED_URI.parse::<AtUri>().expect("invalid at uri");
+        let uris =
+            |posts: Vec<PostView>| posts.into_iter().map(|post| post.data.uri).collect::<Vec<_>>();
+        let posts = agent.get_feed(feed.clone(), None).try_collect::<Vec<_>>().await?;
+        assert_eq!(uris(posts), ["1", "3", "4"].map(post_uri));
+        let posts = agent.get_feed(feed, Some(String::from("2"))).try_collect::<Vec<_>>().await?;
+        assert_eq!(uris(posts), [post_uri("4")]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_posts() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient).build().await?;
+        let uris = (1..=30)
+            .map(|i| i.to_string())
+            .chain([String::from("fail")])
+            .map(|rkey| post_uri(&rkey).parse::<AtUri>().expect("invalid at uri"))
+            .collect::<Vec<_>>();
+        let posts = agent.get_posts(&uris).await;
+        assert_eq!(
+            posts.posts.into_iter().map(|post| post.data.uri).collect::<Vec<_>>(),
+            (1..=25).filter(|i| *i != 2).map(|i| post_uri(&i.to_string())).collect::<Vec<_>>()
+        );
+        assert_eq!(posts.not_found, [uris[1].clone()]);
+        assert_eq!(posts.failed.len(), 1);
+        assert_eq!(posts.failed[0].0, uris[25..]);
+        assert!(matches!(posts.failed[0].1, Error::Xrpc(_)));
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use super::BskyAgent;
+use crate::error::Result;
+use atrium_api::agent::store::SessionStore;
+use atrium_api::app::bsky::notification::{get_unread_count, list_notifications, update_seen};
+use atrium_api::types::string::Datetime;
+use atrium_api::xrpc::XrpcClient;
+use chrono::{DateTime, FixedOffset};
+
+/// A helper for the `app.bsky.notification.*` endpoints, returned by
+/// [`BskyAgent::notifications()`].
+pub struct Notifications<'a, T, S>
+where
+    T: XrpcClient + Send + Sync,
+    S: SessionStore + Send + Sync,
+{
+    agent: &'a BskyAgent<T, S>,
+}
+
+impl<T, S> BskyAgent<T, S>
+where
+    T: XrpcClient + Send + Sync,
+    S: SessionStore + Send + Sync,
+{
+    /// Get a helper to poll and manage the notifications of the logged-in user.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bsky_sdk::BskyAgent;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> bsky_sdk::Result<()> {
+    ///     let agent = BskyAgent::builder().build().await?;
+    ///     agent.login("alice.bsky.social", "app-password").await?;
+    ///     let notifications = agent.notifications();
+    ///     if notifications.unread_count().await? > 0 {
+    ///         let output = notifications.list(None).await?;
+    ///         for notification in &output.notifications {
+    ///             println!("{}: {}", notification.reason, notification.uri);
+    ///         }
+    ///         notifications.mark_seen(chrono::Utc::now()).await?;
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn notifications(&self) -> Notifications<'_, T, S> {
+        Notifications { agent: self }
+    }
+}
+
+impl<'a, T, S> Notifications<'a, T, S>
+where
+    T: XrpcClient + Send + Sync,
+    S: SessionStore + Send + Sync,
+{
+    /// Get the number of unread notifications.
+    pub async fn unread_count(&self) -> Result<i64> {
+        Ok(self
+            .agent
+            .api
+            .app
+            .bsky
+            .notification
+            .get_unread_count(
+                get_unread_count::ParametersData { priority: None, seen_at: None }.into(),
+            )
+            .await?
+            .data
+            .count)
+    }
+    /// List the notifications, from the newest one.
+    ///
+    /// To get the next page, pass the `cursor` of the previous output.
+    pub async fn list(&self, cursor: Option<String>) -> Result<list_notifications::Output> {
+        Ok(self
+            .agent
+            .api
+            .app
+            .bsky
+            .notification
+            .list_notifications(
+                list_notifications::ParametersData {
+                    cursor,
+                    limit: None,
+                    priority: None,
+                    seen_at: None,
+                }
+                .into(),
+            )
+            .await?)
+    }
+    /// Mark the notifications up to the given time as seen.
+    ///
+    /// Any [`chrono::DateTime`] can be used, e.g. `chrono::Utc::now()` to mark all of them.
+    pub async fn mark_seen(&self, at: impl Into<DateTime<FixedOffset>>) -> Result<()> {
+        Ok(self
+            .agent
+            .api
+            .app
+            .bsky
+            .notification
+            .update_seen(update_seen::InputData { seen_at: Datetime::new(at.into()) }.into())
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::BskyAgentBuilder;
+    use atrium_api::xrpc::http::{Request, Response};
+    use atrium_api::xrpc::types::Header;
+    use atrium_api::xrpc::HttpClient;
+    use chrono::TimeZone;
+
+    struct MockClient;
+
+    impl HttpClient for MockClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            let body = match request.uri().path() {
+                "/xrpc/app.bsky.notification.getUnreadCount" => br#"{"count":3}"#.to_vec(),
+                "/xrpc/app.bsky.notification.listNotifications" => {
+                    let cursor = (request.uri().query() != Some("cursor=next")).then_some("next");
+                    serde_json::to_vec(&serde_json::json!({"notifications": [], "cursor": cursor}))?
+                }
+                "/xrpc/app.bsky.notification.updateSeen" => {
+                    let input = serde_json::from_slice::<serde_json::Value>(request.body())?;
+                    assert_eq!(input, serde_json::json!({"seenAt": "2024-10-01T12:00:00.000000Z"}));
+                    return Ok(Response::builder().status(200).body(Vec::new())?);
+                }
+                _ => return Ok(Response::builder().status(404).body(Vec::new())?),
+            };
+            Ok(Response::builder()
+                .header(Header::ContentType, "application/json")
+                .status(200)
+                .body(body)?)
+        }
+    }
+
+    impl XrpcClient for MockClient {
+        fn base_uri(&self) -> String {
+            String::from("https://bsky.social")
+        }
+    }
+
+    #[tokio::test]
+    async fn notifications() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient).build().await?;
+        let notifications = agent.notifications();
+        assert_eq!(notifications.unread_count().await?, 3);
+        let output = notifications.list(None).await?;
+        assert!(output.notifications.is_empty());
+        assert_eq!(output.cursor.as_deref(), Some("next"));
+        assert_eq!(notifications.list(output.data.cursor).await?.cursor, None);
+        notifications
+            .mark_seen(chrono::Utc.with_ymd_and_hms(2024, 10, 1, 12, 0, 0).unwrap())
+            .await?;
+        Ok(())
+    }
+}
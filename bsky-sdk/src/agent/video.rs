@@ -0,0 +1,356 @@
+//! Uploading videos to the Bluesky video service.
+use super::{BskyAgent, EndpointClient};
+use crate::blob::BlobValidator;
+use crate::error::{Error, Result};
+use atrium_api::agent::store::SessionStore;
+use atrium_api::app::bsky::embed::video;
+use atrium_api::app::bsky::video::{get_job_status, get_upload_limits};
+use atrium_api::client::AtpServiceClient;
+use atrium_api::com::atproto::repo::upload_blob;
+use atrium_api::types::string::Did;
+use atrium_api::xrpc::http::header::CONTENT_TYPE;
+use atrium_api::xrpc::http::{uri::Builder, Request, Response};
+use atrium_api::xrpc::types::AuthorizationToken;
+use atrium_api::xrpc::{HttpClient, XrpcClient};
+use atrium_common::backoff::Backoff;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+const VIDEO_SERVICE: &str = "https://video.bsky.app";
+const VIDEO_SERVICE_DID: &str = "did:web:video.bsky.app";
+const UPLOAD_VIDEO_PATH: &str = "/xrpc/app.bsky.video.uploadVideo";
+/// The validity of the service auth token for uploading, which must cover the whole upload.
+const UPLOAD_TOKEN_EXPIRATION_SECS: i64 = 30 * 60;
+const POLL_INTERVAL_MIN: Duration = Duration::from_millis(250);
+const POLL_INTERVAL_MAX: Duration = Duration::from_secs(5);
+const POLL_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+impl<T, S> BskyAgent<T, S>
+where
+    T: XrpcClient + Send + Sync,
+    S: SessionStore + Send + Sync,
+{
+    /// Upload a video to the video service, and wait until it has been processed.
+    ///
+    /// This checks the upload limits of the account first, and polls the status of the
+    /// processing job with an exponential backoff, for up to 10 minutes.
+    /// The returned embed can be set to the `embed` of a post with `.into()`.
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors of [`BlobValidator::validate()`] and of the requests,
+    /// returns an [`Error::VideoUpload`] if the upload is not allowed, or processing fails or
+    /// times out.
+    pub async fn upload_video(
+        &self,
+        data: Vec<u8>,
+        filename: impl Into<String>,
+        alt: Option<String>,
+    ) -> Result<video::Main> {
+        let mime_type = BlobValidator::video().validate(&data)?.mime_type;
+        let did = self.get_session().await.ok_or(Error::NotLoggedIn)?.data.did;
+
+        let token = self
+            .service_auth(
+                &VIDEO_SERVICE_DID.parse().expect("invalid DID"),
+                &get_upload_limits::NSID.parse().expect("invalid NSID"),
+                None,
+            )
+            .await?;
+        let limits = AtpServiceClient::new(self.video_client(token, None))
+            .service
+            .app
+            .bsky
+            .video
+            .get_upload_limits()
+            .await?
+            .data;
+        if !limits.can_upload
+            || limits.remaining_daily_bytes.is_some_and(|remain| remain < data.len() as i64)
+            || limits.remaining_daily_videos.is_some_and(|remain| remain <= 0)
+        {
+            return Err(Error::VideoUpload(
+                limits.message.or(limits.error).unwrap_or_else(|| "upload limit exceeded".into()),
+            ));
+        }
+
+        // The video service uploads the blob to the PDS on behalf of the account.
+        let token = self
+            .service_auth_for_endpoint(
+                &upload_blob::NSID.parse().expect("invalid NSID"),
+                Some(chrono::Utc::now().timestamp() + UPLOAD_TOKEN_EXPIRATION_SECS),
+            )
+            .await?;
+        let params = UploadParams { did, name: filename.into(), mime_type };
+        let mut status = AtpServiceClient::new(self.video_client(token, Some(params)))
+            .service
+            .app
+            .bsky
+            .video
+            .upload_video(data)
+            .await?
+            .data
+            .job_status
+            .data;
+
+        let client = AtpServiceClient::new(EndpointClient {
+            client: self.inner.xrpc_client(),
+            endpoint: VIDEO_SERVICE.into(),
+            sink: None,
+        });
+        let mut backoff = Backoff::new(POLL_INTERVAL_MIN, POLL_INTERVAL_MAX);
+        let started = Instant::now();
+        let blob = loop {
+            if let Some(blob) = status.blob {
+                break blob;
+            }
+            match status.state.as_str() {
+                "JOB_STATE_FAILED" => {
+                    return Err(Error::VideoUpload(
+                        status.message.or(status.error).unwrap_or(status.state),
+                    ))
+                }
+                "JOB_STATE_COMPLETED" => {
+                    return Err(Error::VideoUpload(String::from("completed without a blob")))
+                }
+                _ => {}
+            }
+            if started.elapsed() >= POLL_TIMEOUT {
+                return Err(Error::VideoUpload(format!("timed out in {}", status.state)));
+            }
+            sleep(backoff.next_delay()).await;
+            status = client
+                .service
+                .app
+                .bsky
+                .video
+                .get_job_status(get_job_status::ParametersData { job_id: status.job_id }.into())
+                .await?
+                .data
+                .job_status
+                .data;
+        };
+        Ok(video::MainData { alt, aspect_ratio: None, captions: None, video: blob }.into())
+    }
+    fn video_client(&self, token: String, params: Option<UploadParams>) -> VideoClient<T> {
+        VideoClient { client: self.inner.xrpc_client(), token, params }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+#[derive(Serialize)]
+struct UploadParams {
+    did: Did,
+    name: String,
+    #[serde(skip)]
+    mime_type: &'static str,
+}
+
+/// An XRPC client for the video service, authorized with a service auth token.
+///
+/// It also works around differences between the service and its lexicon: `uploadVideo` needs
+/// the DID and the file name as query parameters, and responds with the job status itself
+/// instead of an object containing it.
+struct VideoClient<T> {
+    client: Arc<T>,
+    token: String,
+    params: Option<UploadParams>,
+}
+
+impl<T> HttpClient for VideoClient<T>
+where
+    T: XrpcClient + Send + Sync,
+{
+    async fn send_http(
+        &self,
+        mut request: Request<Vec<u8>>,
+    ) -> core::result::Result<Response<Vec<u8>>, Box<dyn std::error::Error + Send + Sync + 'static>>
+    {
+        let is_upload_video = request.uri().path() == UPLOAD_VIDEO_PATH;
+        if is_upload_video {
+            if let Some(params) = &self.params {
+                *request.uri_mut() = Builder::from(request.uri().clone())
+                    .path_and_query(format!(
+                        "{UPLOAD_VIDEO_PATH}?{}",
+                        serde_html_form::to_string(params)?
+                    ))
+                    .build()?;
+                // The generated method always sends `video/mp4`
+                request.headers_mut().insert(CONTENT_TYPE, params.mime_type.parse()?);
+            }
+        }
+        let mut response = self.client.send_http(request).await?;
+        if is_upload_video && response.status().is_success() {
+            *response.body_mut() = [b"{\"jobStatus\":", response.body().as_slice(), b"}"].concat();
+        }
+        Ok(response)
+    }
+}
+
+impl<T> XrpcClient for VideoClient<T>
+where
+    T: XrpcClient + Send + Sync,
+{
+    fn base_uri(&self) -> String {
+        VIDEO_SERVICE.into()
+    }
+    async fn authorization_token(&self, _: bool) -> Option<AuthorizationToken> {
+        Some(AuthorizationToken::Bearer(self.token.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::BskyAgentBuilder;
+    use crate::tests::FAKE_CID;
+    use atrium_api::agent::Session;
+    use atrium_api::com::atproto::server::create_session::OutputData;
+    use atrium_api::types::{Blob, BlobRef, CidLink, TypedBlobRef};
+    use atrium_api::xrpc::types::Header;
+
+    const WEBM: &[u8] = b"\x1a\x45\xdf\xa3webm";
+
+    struct MockClient {
+        can_upload: bool,
+    }
+
+    impl HttpClient for MockClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            let authorization =
+                request.headers().get("authorization").and_then(|value| value.to_str().ok());
+            let query = request.uri().query().unwrap_or_default();
+            let body = match (request.uri().host(), request.uri().path()) {
+                (Some("bsky.social"), "/xrpc/com.atproto.server.getServiceAuth") => {
+                    assert_eq!(authorization, Some("Bearer access"));
+                    // Return the parameters as the token, to check them later
+                    serde_json::json!({ "token": query })
+                }
+                (Some("video.bsky.app"), "/xrpc/app.bsky.video.getUploadLimits") => {
+                    assert_eq!(
+                        authorization,
+                        Some("Bearer aud=did%3Aweb%3Avideo.bsky.app&lxm=app.bsky.video.getUploadLimits")
+                    );
+                    serde_json::json!({ "canUpload": self.can_upload, "message": "limit reached" })
+                }
+                (Some("video.bsky.app"), "/xrpc/app.bsky.video.uploadVideo") => {
+                    assert!(authorization.is_some_and(|auth| auth
+                        .starts_with("Bearer aud=did%3Aweb%3Absky.social&exp=")
+                        && auth.ends_with("&lxm=com.atproto.repo.uploadBlob")));
+                    assert_eq!(query, "did=did%3Afake%3Ahandle.test&name=video.webm");
+                    assert_eq!(
+                        request.headers().get(CONTENT_TYPE).and_then(|v| v.to_str().ok()),
+                        Some("video/webm")
+                    );
+                    assert_eq!(request.body(), WEBM);
+                    // Not wrapped in `jobStatus`
+                    serde_json::json!({
+                        "did": "did:fake:handle.test",
+                        "jobId": "job",
+                        "state": "JOB_STATE_CREATED",
+                    })
+                }
+                (Some("video.bsky.app"), "/xrpc/app.bsky.video.getJobStatus") => {
+                    assert_eq!(authorization, None);
+                    assert_eq!(query, "jobId=job");
+                    serde_json::json!({
+                        "jobStatus": {
+                            "did": "did:fake:handle.test",
+                            "jobId": "job",
+                            "state": "JOB_STATE_COMPLETED",
+                            "blob": blob(),
+                        },
+                    })
+                }
+                _ => return Ok(Response::builder().status(404).body(Vec::new())?),
+            };
+            Ok(Response::builder()
+                .header(Header::ContentType, "application/json")
+                .status(200)
+                .body(serde_json::to_vec(&body)?)?)
+        }
+    }
+
+    impl XrpcClient for MockClient {
+        fn base_uri(&self) -> String {
+            String::from("https://bsky.social")
+        }
+    }
+
+    struct MockSessionStore;
+
+    impl SessionStore for MockSessionStore {
+        async fn get_session(&self) -> Option<Session> {
+            Some(
+                OutputData {
+                    access_jwt: String::from("access"),
+                    active: None,
+                    did: "did:fake:handle.test".parse().expect("invalid did"),
+                    did_doc: None,
+                    email: None,
+                    email_auth_factor: None,
+                    email_confirmed: None,
+                    handle: "handle.test".parse().expect("invalid handle"),
+                    refresh_jwt: String::from("refresh"),
+                    status: None,
+                }
+                .into(),
+            )
+        }
+        async fn set_session(&self, _: Session) {}
+        async fn clear_session(&self) {}
+    }
+
+    fn blob() -> BlobRef {
+        BlobRef::Typed(TypedBlobRef::Blob(Blob {
+            r#ref: CidLink(FAKE_CID.parse().expect("invalid cid")),
+            mime_type: String::from("video/webm"),
+            size: WEBM.len(),
+        }))
+    }
+
+    #[tokio::test]
+    async fn upload_video() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient { can_upload: true })
+            .store(MockSessionStore)
+            .build()
+            .await?;
+        let embed = agent.upload_video(WEBM.to_vec(), "video.webm", Some("alt".into())).await?;
+        assert_eq!(embed.alt.as_deref(), Some("alt"));
+        assert_eq!(embed.video, blob());
+
+        let agent = BskyAgentBuilder::new(MockClient { can_upload: false })
+            .store(MockSessionStore)
+            .build()
+            .await?;
+        assert!(matches!(
+            agent.upload_video(WEBM.to_vec(), "video.webm", None).await,
+            Err(Error::VideoUpload(message)) if message == "limit reached"
+        ));
+        assert!(matches!(
+            agent.upload_video(b"not a video".to_vec(), "video.webm", None).await,
+            Err(Error::UnsupportedBlobType(_))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+//! Pre-flight validation of blobs before uploading them.
+use crate::error::{Error, Result};
+use atrium_api::app::bsky::embed::defs::{AspectRatio, AspectRatioData};
+use atrium_api::types::{accepts_mime_type, BlobConstraints};
+use std::num::NonZeroU64;
+
+/// Information about a blob that passed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobInfo {
+    /// The sniffed MIME type.
+    pub mime_type: &'static str,
+    /// The size in bytes.
+    pub size: usize,
+    /// The aspect ratio, if the blob is an image whose dimensions could be read.
+    pub aspect_ratio: Option<AspectRatio>,
+}
+
+/// Validates blobs against an allowlist of MIME types and a maximum size,
+/// so that they can be rejected before `com.atproto.repo.uploadBlob` is called.
+///
+/// # Example
+///
+/// ```
+/// use bsky_sdk::blob::BlobValidator;
+///
+/// let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\x02\x80\0\0\x01\xe0";
+/// let info = BlobValidator::image().validate(png).expect("blob should be valid");
+/// assert_eq!(info.mime_type, "image/png");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlobValidator {
+    mime_types: Vec<String>,
+    max_size: Option<usize>,
+}
+
+impl BlobValidator {
+    /// Create a new validator which accepts the given MIME types up to `max_size` bytes.
+    ///
+    /// MIME types may be glob patterns such as `image/*`, as in [`BlobConstraints::accept`].
+    pub fn new(mime_types: impl IntoIterator<Item = impl Into<String>>, max_size: usize) -> Self {
+        Self {
+            mime_types: mime_types.into_iter().map(Into::into).collect(),
+            max_size: Some(max_size),
+        }
+    }
+    /// A validator with the limits of `app.bsky.embed.images`.
+    pub fn image() -> Self {
+        Self::new(["image/png", "image/jpeg", "image/webp", "image/gif"], 1_000_000)
+    }
+    /// A validator with the limits of `app.bsky.embed.video`.
+    pub fn video() -> Self {
+        Self::new(["video/mp4", "video/quicktime", "video/webm"], 50_000_000)
+    }
+    /// Set the maximum size in bytes.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+    /// Validate the given bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::UnsupportedBlobType`] if the MIME type cannot be detected or is not allowed,
+    /// or an [`Error::BlobTooLarge`] if the blob exceeds the maximum size.
+    pub fn validate(&self, bytes: &[u8]) -> Result<BlobInfo> {
+        let mime_type = sniff_mime_type(bytes)
+            .ok_or_else(|| Error::UnsupportedBlobType(String::from("unknown")))?;
+        if !accepts_mime_type(&self.mime_types, mime_type) {
+            return Err(Error::UnsupportedBlobType(mime_type.into()));
+        }
+        if let Some(max_size) = self.max_size.filter(|max_size| bytes.len() > *max_size) {
+            return Err(Error::BlobTooLarge { size: bytes.len(), max_size });
+        }
+        Ok(BlobInfo { mime_type, size: bytes.len(), aspect_ratio: aspect_ratio(bytes) })
+    }
+}
+
+impl From<BlobConstraints> for BlobValidator {
+    fn from(constraints: BlobConstraints) -> Self {
+        Self {
+            mime_types: constraints.accept.iter().map(|s| s.to_string()).collect(),
+            max_size: constraints.max_size,
+        }
+    }
+}
+
+/// Returns the aspect ratio of an encoded PNG, JPEG, GIF or WebP image,
+/// read from the dimensions in its header.
+///
+/// Returns `None` if the format is not supported or the dimensions cannot be read.
+pub fn aspect_ratio(bytes: &[u8]) -> Option<AspectRatio> {
+    let (width, height) = image_dimensions(sniff_mime_type(bytes)?, bytes)?;
+    Some(
+        AspectRatioData {
+            height: NonZeroU64::new(height.into())?,
+            width: NonZeroU64::new(width.into())?,
+        }
+        .into(),
+    )
+}
+
+fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        Some("image/webp")
+    } else if bytes.starts_with(b"\x1a\x45\xdf\xa3") {
+        Some("video/webm")
+    } else if bytes.get(4..8) == Some(b"ftyp") {
+        if bytes.get(8..12) == Some(b"qt  ") {
+            Some("video/quicktime")
+        } else {
+            Some("video/mp4")
+        }
+    } else {
+        None
+    }
+}
+
+fn image_dimensions(mime_type: &str, bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| bytes.get(i..i + 2).map(|b| u32::from(u16::from_be_bytes([b[0], b[1]])));
+    let le16 = |i: usize| bytes.get(i..i + 2).map(|b| u32::from(u16::from_le_bytes([b[0], b[1]])));
+    let le24 = |i: usize| bytes.get(i..i + 3).map(|b| u32::from_le_bytes([b[0], b[1], b[2], 0]));
+    let be32 = |i: usize| bytes.get(i..i + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+    match mime_type {
+        "image/png" => Some((be32(16)?, be32(20)?)),
+        "image/gif" => Some((le16(6)?, le16(8)?)),
+        "image/jpeg" => {
+            // scan the segments for a start-of-frame marker
+            let mut i = 2;
+            while bytes.get(i) == Some(&0xff) {
+                let marker = *bytes.get(i + 1)?;
+                if (0xc0..=0xcf).contains(&marker) && ![0xc4, 0xc8, 0xcc].contains(&marker) {
+                    return Some((be16(i + 7)?, be16(i + 5)?));
+                }
+                i += 2 + usize::try_from(be16(i + 2)?).ok()?;
+            }
+            None
+        }
+        "image/webp" => match bytes.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff)),
+            b"VP8L" => {
+                let b = bytes.get(21..25)?.iter().map(|&b| u32::from(b)).collect::<Vec<_>>();
+                Some((
+                    1 + (b[0] | (b[1] & 0x3f) << 8),
+                    1 + (b[1] >> 6 | b[2] << 2 | (b[3] & 0x0f) << 10),
+                ))
+            }
+            b"VP8X" => Some((1 + le24(24)?, 1 + le24(27)?)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aspect_ratio(width: u64, height: u64) -> Option<AspectRatio> {
+        Some(
+            AspectRatioData {
+                height: NonZeroU64::new(height).expect("height should be non-zero"),
+                width: NonZeroU64::new(width).expect("width should be non-zero"),
+            }
+            .into(),
+        )
+    }
+
+    #[test]
+    fn validate_images() {
+        let validator = BlobValidator::image();
+        // PNG
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\x02\x80\0\0\x01\xe0";
+        let info = validator.validate(png).expect("png should be valid");
+        assert_eq!(info.mime_type, "image/png");
+        assert_eq!(info.size, png.len());
+        assert_eq!(info.aspect_ratio, aspect_ratio(640, 480));
+        // GIF
+        let info = validator.validate(b"GIF89a\x10\0\x20\0").expect("gif should be valid");
+        assert_eq!(info.mime_type, "image/gif");
+        assert_eq!(info.aspect_ratio, aspect_ratio(16, 32));
+        // JPEG (APP0 segment followed by SOF0)
+        let jpeg = b"\xff\xd8\xff\xe0\0\x04\0\0\xff\xc0\0\x11\x08\0\x64\0\xc8";
+        let info = validator.validate(jpeg).expect("jpeg should be valid");
+        assert_eq!(info.mime_type, "image/jpeg");
+        assert_eq!(info.aspect_ratio, aspect_ratio(200, 100));
+        // WebP (extended format)
+        let webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0\x1f\0\0\x0f\0\0";
+        let info = validator.validate(webp).expect("webp should be valid");
+        assert_eq!(info.mime_type, "image/webp");
+        assert_eq!(info.aspect_ratio, aspect_ratio(32, 16));
+    }
+
+    #[test]
+    fn validate_video() {
+        let mp4 = b"\0\0\0\x20ftypisom\0\0\x02\0";
+        let info = BlobValidator::video().validate(mp4).expect("mp4 should be valid");
+        assert_eq!(info.mime_type, "video/mp4");
+        assert_eq!(info.aspect_ratio, None);
+    }
+
+    #[test]
+    fn image_aspect_ratio() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x03\0\0\0\x02";
+        assert_eq!(super::aspect_ratio(png), aspect_ratio(3, 2));
+        assert_eq!(super::aspect_ratio(b"\0\0\0\x20ftypisom\0\0\x02\0"), None);
+        assert_eq!(super::aspect_ratio(b"plain text"), None);
+    }
+
+    #[test]
+    fn validate_with_constraints() {
+        use atrium_api::app::bsky::actor::profile::RecordData;
+
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\x02\x80\0\0\x01\xe0";
+        let validator = BlobValidator::from(RecordData::AVATAR_CONSTRAINTS);
+        assert!(validator.validate(png).is_ok());
+        assert!(matches!(
+            validator.validate(b"GIF89a\x10\0\x20\0"),
+            Err(Error::UnsupportedBlobType(mime_type)) if mime_type == "image/gif"
+        ));
+        let validator =
+            BlobValidator::from(atrium_api::app::bsky::embed::images::ImageData::IMAGE_CONSTRAINTS);
+        assert!(validator.validate(b"GIF89a\x10\0\x20\0").is_ok());
+        let validator = BlobValidator::new(["*/*"], 16);
+        assert!(validator.validate(b"GIF89a\x10\0\x20\0").is_ok());
+        assert!(matches!(validator.validate(png), Err(Error::BlobTooLarge { .. })));
+    }
+
+    #[test]
+    fn validate_with_runtime_mime_types() {
+        // e.g. read from a configuration file
+        let config = String::from("image/png,video/*");
+        let validator = BlobValidator::new(config.split(',').map(String::from), 1_000);
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\x02\x80\0\0\x01\xe0";
+        assert!(validator.validate(png).is_ok());
+        assert!(validator.validate(b"\x1a\x45\xdf\xa3webm").is_ok());
+        assert!(matches!(
+            validator.validate(b"GIF89a\x10\0\x20\0"),
+            Err(Error::UnsupportedBlobType(mime_type)) if mime_type == "image/gif"
+        ));
+    }
+
+    #[test]
+    fn validate_invalid() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\x02\x80\0\0\x01\xe0";
+        assert!(matches!(
+            BlobValidator::video().validate(png),
+            Err(Error::UnsupportedBlobType(mime_type)) if mime_type == "image/png"
+        ));
+        assert!(matches!(
+            BlobValidator::image().validate(b"plain text"),
+            Err(Error::UnsupportedBlobType(_))
+        ));
+        assert!(matches!(
+            BlobValidator::image().max_size(8).validate(png),
+            Err(Error::BlobTooLarge { size: 24, max_size: 8 })
+        ));
+    }
+}
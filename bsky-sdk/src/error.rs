@@ -12,15 +12,35 @@ pub enum Error {
     NotLoggedIn,
     #[error("invalid AT URI")]
     InvalidAtUri,
+    #[error("record has no CID")]
+    MissingCid,
+    #[error("record not found: {0}")]
+    RecordNotFound(String),
+    #[error("record has been changed or deleted: {0}")]
+    InvalidSwap(String),
+    #[error("images and an external link cannot be embedded together")]
+    IncompatibleEmbeds,
+    #[error("too many images")]
+    TooManyImages,
+    #[error("unsupported blob type: {0}")]
+    UnsupportedBlobType(String),
+    #[error("blob too large: {size} bytes (max {max_size})")]
+    BlobTooLarge { size: usize, max_size: usize },
+    #[error("video upload failed: {0}")]
+    VideoUpload(String),
     #[error("xrpc response error: {0}")]
     Xrpc(Box<GenericXrpcError>),
     #[error("loading config error: {0}")]
     ConfigLoad(Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error("saving config error: {0}")]
     ConfigSave(Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("runtime error: {0}")]
+    Runtime(std::io::Error),
     #[error(transparent)]
     ApiType(#[from] atrium_api::error::Error),
     #[error(transparent)]
+    Identity(#[from] atrium_identity::Error),
+    #[error(transparent)]
     Moderation(#[from] crate::moderation::Error),
 }
 
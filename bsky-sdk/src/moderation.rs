@@ -10,11 +10,15 @@ pub mod util;
 
 use self::decision::ModerationDecision;
 pub use self::error::{Error, Result};
+use self::labels::KnownLabelValue;
 pub use self::types::*;
 use atrium_api::types::string::Did;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// The language used when the requested one is not available in a label definition.
+pub const DEFAULT_LANG: &str = "en";
+
 /// A moderator for the different kinds of content on the Bluesky network.
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -22,6 +26,8 @@ pub struct Moderator {
     user_did: Option<Did>,
     prefs: ModerationPrefs,
     label_defs: HashMap<Did, Vec<InterpretedLabelValueDefinition>>,
+    #[serde(default)]
+    muted_threads: Vec<String>,
 }
 
 impl Moderator {
@@ -31,7 +37,15 @@ impl Moderator {
         prefs: ModerationPrefs,
         label_defs: HashMap<Did, Vec<InterpretedLabelValueDefinition>>,
     ) -> Self {
-        Self { user_did, prefs, label_defs }
+        Self { user_did, prefs, label_defs, muted_threads: Vec::new() }
+    }
+    /// Set the URIs of the root posts of muted threads.
+    ///
+    /// Posts in these threads are filtered from content lists, as are posts whose viewer state
+    /// has `threadMuted` set.
+    pub fn with_muted_threads(mut self, muted_threads: Vec<String>) -> Self {
+        self.muted_threads = muted_threads;
+        self
     }
     /// Calculate the moderation decision for an account profile.
     pub fn moderate_profile(&self, profile: &SubjectProfile) -> ModerationDecision {
@@ -56,6 +70,41 @@ impl Moderator {
     pub fn moderate_user_list(&self, user_list: &SubjectUserList) -> ModerationDecision {
         self.decide_user_list(user_list)
     }
+    /// Returns the localized name and description of a label value.
+    ///
+    /// The strings are resolved from the configured label value definitions in `lang`,
+    /// falling back to [`DEFAULT_LANG`], and then to the built-in strings of the known labels.
+    ///
+    /// If several labelers define the label value, their definitions are used in the order of
+    /// the labelers in [`ModerationPrefs::labelers`], and then of the other labelers sorted by DID.
+    pub fn label_strings(&self, label_value: &str, lang: &str) -> Option<LabelStrings> {
+        let mut dids = self.label_defs.keys().collect::<Vec<_>>();
+        dids.sort_by_key(|did| {
+            (
+                self.prefs
+                    .labelers
+                    .iter()
+                    .position(|labeler| labeler.did == **did)
+                    .unwrap_or(usize::MAX),
+                did.as_str(),
+            )
+        });
+        let locales = dids
+            .into_iter()
+            .flat_map(|did| &self.label_defs[did])
+            .filter(|def| def.identifier == label_value)
+            .flat_map(|def| &def.locales)
+            .collect::<Vec<_>>();
+        [lang, DEFAULT_LANG]
+            .iter()
+            .find_map(|lang| {
+                locales
+                    .iter()
+                    .find(|strings| strings.lang.as_ref().as_str().eq_ignore_ascii_case(lang))
+            })
+            .map(|strings| LabelStrings::from(*strings))
+            .or_else(|| label_value.parse::<KnownLabelValue>().ok().map(|known| known.strings()))
+    }
 }
 
 #[cfg(test)]
@@ -74,7 +74,7 @@ impl AsRef<u8> for Priority {
 }
 
 /// A moderation decision.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ModerationDecision {
     did: Option<Did>,
     is_me: bool,
@@ -198,6 +198,11 @@ impl ModerationDecision {
                         }
                     }
                 }
+                ModerationCause::MutedThread(_) => {
+                    if matches!(context, DecisionContext::ContentList) {
+                        ui.filters.push(cause.clone())
+                    }
+                }
                 ModerationCause::Hidden(hidden) => {
                     if matches!(context, DecisionContext::ProfileList | DecisionContext::ContentList) {
                         ui.filters.push(cause.clone())
@@ -241,14 +246,26 @@ impl ModerationDecision {
     pub(crate) fn new() -> Self {
         Self { did: None, is_me: false, causes: Vec::new() }
     }
-    pub(crate) fn merge(decisions: &[Self]) -> Self {
-        assert!(!decisions.is_empty());
-        // assert!(decisions
-        //     .windows(2)
-        //     .all(|w| w[0].did == w[1].did && w[0].is_me == w[1].is_me));
+    /// Merge multiple decisions into one.
+    ///
+    /// The subject (`did` and whether it is the current user) is taken from the first decision,
+    /// and the causes of all decisions are combined.
+    /// Precedence between the causes is resolved when calculating the [`ModerationUi`]:
+    ///
+    /// - If any blurring cause cannot be overridden (e.g. `!hide`), the merged result has `no_override` set.
+    /// - `filters` and `blurs` are ordered by the priority of their causes,
+    ///   so imperative labels come before labels the user has chosen to hide, which come before other blurring labels.
+    /// - Each cause independently contributes to filter, blur, alert and inform, so the merged result
+    ///   is at least as strict as every decision that was merged.
+    ///
+    /// Merging no decisions returns an empty decision, with no subject and no causes.
+    pub fn merge(decisions: &[Self]) -> Self {
+        let Some(first) = decisions.first() else {
+            return Self::new();
+        };
         Self {
-            did: decisions[0].did.clone(),
-            is_me: decisions[0].is_me,
+            did: first.did.clone(),
+            is_me: first.is_me,
             causes: decisions.iter().flat_map(|d| d.causes.iter().cloned()).collect(),
         }
     }
@@ -382,6 +399,12 @@ impl ModerationDecision {
             downgraded: false,
         })));
     }
+    pub(crate) fn add_muted_thread(&mut self) {
+        self.causes.push(ModerationCause::MutedThread(Box::new(ModerationCauseOther {
+            source: ModerationCauseSource::User,
+            downgraded: false,
+        })));
+    }
     pub(crate) fn downgrade(&mut self) {
         for cause in self.causes.iter_mut() {
             cause.downgrade()
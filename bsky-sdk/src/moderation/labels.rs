@@ -31,6 +31,25 @@ impl FromStr for KnownLabelValue {
 }
 
 impl KnownLabelValue {
+    pub fn strings(&self) -> LabelStrings {
+        let (name, description) = match self {
+            Self::ReservedHide => {
+                ("Hidden by Moderators", "This content has been hidden by the moderators.")
+            }
+            Self::ReservedWarn => {
+                ("Content Warning", "This content has received a general warning from moderators.")
+            }
+            Self::ReservedNoUnauthenticated => (
+                "Sign-in Required",
+                "This user has requested that their content only be shown to signed-in users.",
+            ),
+            Self::Porn => ("Adult Content", "Explicit sexual images."),
+            Self::Sexual => ("Sexually Suggestive", "Does not include nudity."),
+            Self::Nudity => ("Non-sexual Nudity", "E.g. artistic nudes."),
+            Self::GraphicMedia => ("Graphic Media", "Explicit or potentially disturbing media."),
+        };
+        LabelStrings { name: name.into(), description: description.into() }
+    }
     pub fn definition(&self) -> InterpretedLabelValueDefinition {
         match self {
             Self::ReservedHide => InterpretedLabelValueDefinition {
@@ -23,6 +23,9 @@ impl Moderator {
         if check_hidden_post(subject, &self.prefs.hidden_posts) {
             acc.add_hidden();
         }
+        if check_muted_thread(subject, &self.muted_threads) {
+            acc.add_muted_thread();
+        }
         if !is_me && check_muted_words(subject, &self.prefs.muted_words) {
             acc.add_muted_word();
         }
@@ -135,6 +138,22 @@ fn check_hidden_post(subject: &SubjectPost, hidden_posts: &[String]) -> bool {
     false
 }
 
+fn check_muted_thread(subject: &SubjectPost, muted_threads: &[String]) -> bool {
+    if subject.viewer.as_ref().and_then(|viewer| viewer.thread_muted).unwrap_or_default() {
+        return true;
+    }
+    if muted_threads.is_empty() {
+        return false;
+    }
+    if muted_threads.contains(&subject.uri) {
+        return true;
+    }
+    post::Record::try_from_unknown(subject.record.clone())
+        .ok()
+        .and_then(|post| post.data.reply)
+        .map_or(false, |reply| muted_threads.contains(&reply.root.uri))
+}
+
 fn check_muted_words(subject: &SubjectPost, muted_words: &[MutedWord]) -> bool {
     if muted_words.is_empty() {
         return false;
@@ -8,8 +8,11 @@ use crate::moderation::types::*;
 use crate::moderation::util::interpret_label_value_definition;
 use crate::moderation::Moderator;
 use crate::tests::FAKE_CID;
-use atrium_api::app::bsky::actor::defs::{ProfileViewBasic, ProfileViewBasicData};
-use atrium_api::app::bsky::feed::defs::{PostView, PostViewData};
+use atrium_api::app::bsky::actor::defs::{
+    ProfileView, ProfileViewBasic, ProfileViewBasicData, ProfileViewData,
+};
+use atrium_api::app::bsky::feed::defs::{GeneratorViewData, PostView, PostViewData};
+use atrium_api::app::bsky::graph::defs::{ListView, ListViewData};
 use atrium_api::com::atproto::label::defs::{Label, LabelData, LabelValueDefinitionData};
 use atrium_api::types::string::Datetime;
 use atrium_api::types::TryIntoUnknown;
@@ -652,3 +655,351 @@ fn adult_content_disabled_forces_hide() {
         assert_ui(&result, &expected, context);
     }
 }
+
+fn profile_view(
+    handle: &str,
+    display_name: Option<&str>,
+    labels: Option<Vec<Label>>,
+) -> ProfileView {
+    ProfileViewData {
+        associated: None,
+        avatar: None,
+        created_at: None,
+        description: None,
+        did: format!("did:web:{handle}").parse().expect("invalid did"),
+        display_name: display_name.map(String::from),
+        handle: handle.parse().expect("invalid handle"),
+        indexed_at: None,
+        labels,
+        viewer: None,
+    }
+    .into()
+}
+
+fn imperative_label_moderator() -> Moderator {
+    Moderator::new(
+        Some("did:web:alice.test".parse().expect("invalid did")),
+        ModerationPrefs {
+            adult_content_enabled: true,
+            labelers: vec![ModerationPrefsLabeler {
+                did: "did:web:labeler.test".parse().expect("invalid did"),
+                labels: HashMap::new(),
+                is_default_labeler: false,
+            }],
+            ..Default::default()
+        },
+        HashMap::new(),
+    )
+}
+
+#[test]
+fn moderate_labeled_feed_generator() {
+    let moderator = imperative_label_moderator();
+    let result = moderator.moderate_feed_generator(
+        &GeneratorViewData {
+            accepts_interactions: None,
+            avatar: None,
+            cid: FAKE_CID.parse().expect("invalid cid"),
+            creator: profile_view("bob.test", Some("Bob"), None),
+            description: None,
+            description_facets: None,
+            did: "did:web:feed.test".parse().expect("invalid did"),
+            display_name: String::from("Feed"),
+            indexed_at: Datetime::now(),
+            labels: Some(vec![label(
+                "did:web:labeler.test",
+                "at://did:web:bob.test/app.bsky.feed.generator/fake",
+                "!hide",
+            )]),
+            like_count: None,
+            uri: String::from("at://did:web:bob.test/app.bsky.feed.generator/fake"),
+            viewer: None,
+        }
+        .into(),
+    );
+    for context in DecisionContext::ALL {
+        let expected = match context {
+            DecisionContext::ContentList => {
+                vec![ResultFlag::Filter, ResultFlag::Blur, ResultFlag::NoOverride]
+            }
+            DecisionContext::ContentView => vec![ResultFlag::Blur, ResultFlag::NoOverride],
+            _ => vec![],
+        };
+        assert_ui(&result, &expected, context);
+    }
+}
+
+#[test]
+fn moderate_labeled_user_list() {
+    let moderator = imperative_label_moderator();
+    let result = moderator.moderate_user_list(
+        &ListView::from(ListViewData {
+            avatar: None,
+            cid: FAKE_CID.parse().expect("invalid cid"),
+            creator: profile_view("bob.test", Some("Bob"), None),
+            description: None,
+            description_facets: None,
+            indexed_at: Datetime::now(),
+            labels: Some(vec![label(
+                "did:web:labeler.test",
+                "at://did:web:bob.test/app.bsky.graph.list/fake",
+                "!hide",
+            )]),
+            list_item_count: None,
+            name: String::from("List"),
+            purpose: String::from("app.bsky.graph.defs#curatelist"),
+            uri: String::from("at://did:web:bob.test/app.bsky.graph.list/fake"),
+            viewer: None,
+        })
+        .into(),
+    );
+    for context in DecisionContext::ALL {
+        let expected = match context {
+            DecisionContext::ContentList => {
+                vec![ResultFlag::Filter, ResultFlag::Blur, ResultFlag::NoOverride]
+            }
+            DecisionContext::ContentView => vec![ResultFlag::Blur, ResultFlag::NoOverride],
+            _ => vec![],
+        };
+        assert_ui(&result, &expected, context);
+    }
+}
+
+#[test]
+fn merge_decisions() {
+    let post = post_view(
+        &profile_view_basic("bob.test", Some("Bob"), None),
+        "Hello",
+        Some(vec![
+            label("did:web:labeler.test", "at://did:web:bob.test/app.bsky.post/fake", "porn"),
+            label("did:web:custom.test", "at://did:web:bob.test/app.bsky.post/fake", "!hide"),
+        ]),
+    );
+    let moderator = |labeler: &str| {
+        Moderator::new(
+            Some("did:web:alice.test".parse().expect("invalid did")),
+            ModerationPrefs {
+                adult_content_enabled: true,
+                labels: HashMap::from_iter([(String::from("porn"), LabelPreference::Warn)]),
+                labelers: vec![ModerationPrefsLabeler {
+                    did: labeler.parse().expect("invalid did"),
+                    labels: HashMap::new(),
+                    is_default_labeler: false,
+                }],
+                ..Default::default()
+            },
+            HashMap::new(),
+        )
+    };
+    let result = ModerationDecision::merge(&[
+        moderator("did:web:labeler.test").moderate_post(&post),
+        moderator("did:web:custom.test").moderate_post(&post),
+    ]);
+    for context in DecisionContext::ALL {
+        let expected = match context {
+            DecisionContext::ContentList => {
+                vec![ResultFlag::Filter, ResultFlag::Blur, ResultFlag::NoOverride]
+            }
+            DecisionContext::ContentView => vec![ResultFlag::Blur, ResultFlag::NoOverride],
+            DecisionContext::ContentMedia => vec![ResultFlag::Blur],
+            _ => vec![],
+        };
+        assert_ui(&result, &expected, context);
+    }
+    let empty = ModerationDecision::merge(&[]);
+    for context in DecisionContext::ALL {
+        assert_ui(&empty, &[], context);
+    }
+}
+
+#[test]
+fn label_strings() {
+    use atrium_api::com::atproto::label::defs::LabelValueDefinitionStringsData;
+
+    let strings = |lang: &str, name: &str| {
+        LabelValueDefinitionStringsData {
+            description: format!("{name} description"),
+            lang: lang.parse().expect("invalid language"),
+            name: name.into(),
+        }
+        .into()
+    };
+    let label_defs = |did: &str, locales| {
+        (
+            did.parse().expect("invalid did"),
+            vec![interpret_label_value_definition(
+                &LabelValueDefinitionData {
+                    identifier: String::from("custom"),
+                    default_setting: None,
+                    severity: String::from("inform"),
+                    blurs: String::from("none"),
+                    adult_only: None,
+                    locales,
+                }
+                .into(),
+                Some(did.parse().expect("invalid did")),
+            )
+            .expect("invalid label value definition")],
+        )
+    };
+    let moderator = Moderator::new(
+        None,
+        ModerationPrefs::default(),
+        HashMap::from_iter([
+            label_defs(
+                "did:web:labeler.test",
+                vec![strings("en", "Custom"), strings("ja", "カスタム")],
+            ),
+            label_defs("did:web:other.test", vec![strings("en", "Other")]),
+        ]),
+    );
+    let name = |moderator: &Moderator, label_value: &str, lang: &str| {
+        moderator.label_strings(label_value, lang).map(|strings| strings.name)
+    };
+    assert_eq!(name(&moderator, "custom", "ja"), Some(String::from("カスタム")));
+    // labelers not in the preferences are sorted by DID
+    assert_eq!(name(&moderator, "custom", "fr"), Some(String::from("Custom")));
+    assert_eq!(name(&moderator, "porn", "ja"), Some(String::from("Adult Content")));
+    assert_eq!(name(&moderator, "unknown", "en"), None);
+    // labelers in the preferences come first, in their order
+    let moderator = Moderator::new(
+        None,
+        ModerationPrefs {
+            labelers: vec![ModerationPrefsLabeler {
+                did: "did:web:other.test".parse().expect("invalid did"),
+                labels: HashMap::new(),
+                is_default_labeler: false,
+            }],
+            ..Default::default()
+        },
+        moderator.label_defs.clone(),
+    );
+    assert_eq!(name(&moderator, "custom", "en"), Some(String::from("Other")));
+    assert_eq!(name(&moderator, "custom", "ja"), Some(String::from("カスタム")));
+}
+
+#[test]
+fn moderate_muted_thread() {
+    use atrium_api::app::bsky::feed::defs::ViewerStateData;
+    use atrium_api::app::bsky::feed::post::{RecordData, ReplyRefData};
+    use atrium_api::com::atproto::repo::strong_ref;
+
+    let root_uri = "at://did:web:bob.test/app.bsky.feed.post/root";
+    let strong_ref = |uri: &str| -> strong_ref::Main {
+        strong_ref::MainData { cid: FAKE_CID.parse().expect("invalid cid"), uri: uri.into() }.into()
+    };
+    let moderator = Moderator::new(
+        Some("did:web:alice.test".parse().expect("invalid did")),
+        ModerationPrefs::default(),
+        HashMap::new(),
+    )
+    .with_muted_threads(vec![String::from(root_uri)]);
+    let author = profile_view_basic("bob.test", Some("Bob"), None);
+    // reply in a muted thread
+    let mut reply = post_view(&author, "Hello", None);
+    reply.record = atrium_api::app::bsky::feed::post::Record::from(RecordData {
+        created_at: Datetime::now(),
+        embed: None,
+        entities: None,
+        facets: None,
+        labels: None,
+        langs: None,
+        reply: Some(
+            ReplyRefData { parent: strong_ref(root_uri), root: strong_ref(root_uri) }.into(),
+        ),
+        tags: None,
+        text: String::from("Hello"),
+    })
+    .try_into_unknown()
+    .expect("failed to convert record to unknown");
+    // post muted through the viewer state
+    let mut viewer_muted = post_view(&author, "Hello", None);
+    viewer_muted.viewer = Some(
+        ViewerStateData {
+            embedding_disabled: None,
+            like: None,
+            pinned: None,
+            reply_disabled: None,
+            repost: None,
+            thread_muted: Some(true),
+        }
+        .into(),
+    );
+    for post in [reply, viewer_muted] {
+        let result = moderator.moderate_post(&post);
+        for context in DecisionContext::ALL {
+            let expected = match context {
+                DecisionContext::ContentList => vec![ResultFlag::Filter],
+                _ => vec![],
+            };
+            assert_ui(&result, &expected, context);
+        }
+    }
+    // unrelated post
+    let result = moderator.moderate_post(&post_view(&author, "Hello", None));
+    for context in DecisionContext::ALL {
+        assert_ui(&result, &[], context);
+    }
+}
+
+#[test]
+fn moderation_prefs_from_preferences() {
+    use atrium_api::agent::bluesky::BSKY_LABELER_DID;
+    use atrium_api::app::bsky::actor::defs::Preferences;
+
+    let preferences = serde_json::from_str::<Preferences>(
+        r#"[
+            {"$type": "app.bsky.actor.defs#adultContentPref", "enabled": true},
+            {"$type": "app.bsky.actor.defs#contentLabelPref", "label": "porn", "visibility": "warn"},
+            {"$type": "app.bsky.actor.defs#contentLabelPref", "label": "gore", "visibility": "invalid"},
+            {
+                "$type": "app.bsky.actor.defs#contentLabelPref",
+                "labelerDid": "did:web:labeler.test",
+                "label": "custom",
+                "visibility": "hide"
+            },
+            {
+                "$type": "app.bsky.actor.defs#contentLabelPref",
+                "labelerDid": "did:web:labeler.test",
+                "label": "undefined",
+                "visibility": "hide"
+            },
+            {"$type": "app.bsky.actor.defs#labelersPref", "labelers": [{"did": "did:web:labeler.test"}]},
+            {"$type": "app.bsky.actor.defs#hiddenPostsPref", "items": ["at://did:web:bob.test/app.bsky.feed.post/fake"]},
+            {"$type": "app.bsky.actor.defs#unknownPref", "value": 42}
+        ]"#,
+    )
+    .expect("deserializing preferences should succeed");
+    let prefs = ModerationPrefs::from_preferences(&preferences, &HashMap::new());
+    assert!(prefs.adult_content_enabled);
+    assert_eq!(prefs.labels.get("porn"), Some(&LabelPreference::Warn));
+    assert_eq!(prefs.labels.get("gore"), None);
+    assert_eq!(
+        prefs.labelers.iter().map(|l| l.did.as_str()).collect::<Vec<_>>(),
+        vec![BSKY_LABELER_DID, "did:web:labeler.test"]
+    );
+    assert_eq!(prefs.labelers[1].labels.get("custom"), Some(&LabelPreference::Hide));
+    assert_eq!(prefs.labelers[1].labels.get("undefined"), Some(&LabelPreference::Hide));
+    assert_eq!(prefs.hidden_posts.len(), 1);
+    assert!(prefs.muted_words.is_empty());
+    // with the label value definitions, only the configurable ones are kept
+    let labeler_defs = HashMap::from_iter([(
+        "did:web:labeler.test".parse().expect("invalid did"),
+        vec![interpret_label_value_definition(
+            &LabelValueDefinitionData {
+                identifier: String::from("custom"),
+                default_setting: None,
+                severity: String::from("inform"),
+                blurs: String::from("none"),
+                adult_only: None,
+                locales: Vec::new(),
+            }
+            .into(),
+            Some("did:web:labeler.test".parse().expect("invalid did")),
+        )
+        .expect("invalid label value definition")],
+    )]);
+    let prefs = ModerationPrefs::from_preferences(&preferences, &labeler_defs);
+    assert_eq!(prefs.labelers[1].labels.get("custom"), Some(&LabelPreference::Hide));
+    assert_eq!(prefs.labelers[1].labels.get("undefined"), None);
+}
@@ -2,11 +2,12 @@ use super::decision::{DecisionContext, Priority};
 use super::error::Error;
 use atrium_api::agent::bluesky::BSKY_LABELER_DID;
 use atrium_api::app::bsky::actor::defs::{
-    MutedWord, ProfileView, ProfileViewBasic, ProfileViewDetailed, ViewerState,
+    MutedWord, PreferencesItem, ProfileView, ProfileViewBasic, ProfileViewDetailed, ViewerState,
 };
 use atrium_api::app::bsky::graph::defs::{ListView, ListViewBasic};
 use atrium_api::com::atproto::label::defs::{Label, LabelValueDefinitionStrings};
 use atrium_api::types::string::Did;
+use atrium_api::types::Union;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{collections::HashMap, str::FromStr};
 
@@ -480,6 +481,19 @@ impl InterpretedLabelValueDefinitionBehaviors {
     }
 }
 
+/// The localized name and description of a label.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelStrings {
+    pub name: String,
+    pub description: String,
+}
+
+impl From<&LabelValueDefinitionStrings> for LabelStrings {
+    fn from(strings: &LabelValueDefinitionStrings) -> Self {
+        Self { name: strings.name.clone(), description: strings.description.clone() }
+    }
+}
+
 // subjects
 
 /// A subject profile.
@@ -563,6 +577,7 @@ impl From<ListViewBasic> for SubjectUserList {
 
 /// A cause for moderation decisions.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum ModerationCause {
     Blocking(Box<ModerationCauseOther>),
     BlockedBy(Box<ModerationCauseOther>),
@@ -571,6 +586,7 @@ pub enum ModerationCause {
     Muted(Box<ModerationCauseOther>),
     MuteWord(Box<ModerationCauseOther>),
     Hidden(Box<ModerationCauseOther>),
+    MutedThread(Box<ModerationCauseOther>),
 }
 
 impl ModerationCause {
@@ -582,6 +598,7 @@ impl ModerationCause {
             Self::Muted(_) => *Priority::Priority6.as_ref(),
             Self::MuteWord(_) => *Priority::Priority6.as_ref(),
             Self::Hidden(_) => *Priority::Priority6.as_ref(),
+            Self::MutedThread(_) => *Priority::Priority6.as_ref(),
         }
     }
     pub fn downgrade(&mut self) {
@@ -592,6 +609,7 @@ impl ModerationCause {
             Self::Muted(muted) => muted.downgraded = true,
             Self::MuteWord(mute_word) => mute_word.downgraded = true,
             Self::Hidden(hidden) => hidden.downgraded = true,
+            Self::MutedThread(muted_thread) => muted_thread.downgraded = true,
         }
     }
 }
@@ -687,3 +705,67 @@ impl Default for ModerationPrefs {
         }
     }
 }
+
+impl ModerationPrefs {
+    /// Extract the moderation preferences from the items of `app.bsky.actor.getPreferences`.
+    ///
+    /// Adult content, label visibilities, labelers, muted words and hidden posts are read,
+    /// and other or unknown items are ignored, as are label visibilities which cannot be parsed.
+    /// The [Bluesky's moderation labeler](BSKY_LABELER_DID) is always the first labeler.
+    ///
+    /// `labeler_defs` are the label value definitions of the labelers, as passed to
+    /// [`Moderator::new()`](crate::moderation::Moderator::new). The label visibilities set for a
+    /// labeler with definitions are kept only for the label values it defines as configurable.
+    /// Labelers without definitions keep all of their label visibilities.
+    pub fn from_preferences(
+        preferences: &[Union<PreferencesItem>],
+        labeler_defs: &HashMap<Did, Vec<InterpretedLabelValueDefinition>>,
+    ) -> Self {
+        let mut prefs = Self::default();
+        prefs.labelers.push(ModerationPrefsLabeler::default());
+        let mut label_prefs = Vec::new();
+        for pref in preferences {
+            match pref {
+                Union::Refs(PreferencesItem::AdultContentPref(p)) => {
+                    prefs.adult_content_enabled = p.enabled;
+                }
+                Union::Refs(PreferencesItem::ContentLabelPref(p)) => {
+                    label_prefs.push(p);
+                }
+                Union::Refs(PreferencesItem::MutedWordsPref(p)) => {
+                    prefs.muted_words.clone_from(&p.items);
+                }
+                Union::Refs(PreferencesItem::HiddenPostsPref(p)) => {
+                    prefs.hidden_posts.clone_from(&p.items);
+                }
+                Union::Refs(PreferencesItem::LabelersPref(p)) => {
+                    prefs.labelers.extend(p.labelers.iter().map(|item| ModerationPrefsLabeler {
+                        did: item.did.clone(),
+                        labels: HashMap::default(),
+                        is_default_labeler: false,
+                    }));
+                }
+                _ => {}
+            }
+        }
+        for pref in label_prefs {
+            let Ok(visibility) = pref.visibility.parse() else {
+                continue;
+            };
+            if let Some(did) = &pref.labeler_did {
+                let is_configurable = labeler_defs.get(did).map_or(true, |defs| {
+                    defs.iter().any(|def| def.identifier == pref.label && def.configurable)
+                });
+                if !is_configurable {
+                    continue;
+                }
+                if let Some(l) = prefs.labelers.iter_mut().find(|l| &l.did == did) {
+                    l.labels.insert(pref.label.clone(), visibility);
+                }
+            } else {
+                prefs.labels.insert(pref.label.clone(), visibility);
+            }
+        }
+        prefs
+    }
+}
@@ -1,7 +1,11 @@
 //! Preferences for Bluesky application.
 use crate::moderation::ModerationPrefs;
-use atrium_api::app::bsky::actor::defs::SavedFeed;
-use atrium_api::types::Object;
+use atrium_api::app::bsky::actor::defs::{
+    self, AdultContentPrefData, LabelerPrefItem, LabelerPrefItemData, LabelersPrefData, MutedWord,
+    MutedWordsPrefData, PreferencesItem, SavedFeed, SavedFeedsPrefV2Data,
+};
+use atrium_api::types::string::Did;
+use atrium_api::types::{Object, Union};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -74,6 +78,143 @@ impl Default for Preferences {
     }
 }
 
+/// The raw `app.bsky.actor.defs#preferences` of an account, with typed accessors.
+///
+/// Unlike [`Preferences`], this keeps every item as it was received, so it can be written back
+/// with `app.bsky.actor.putPreferences` without dropping preferences unknown to this crate.
+///
+/// # Example
+///
+/// ```
+/// use bsky_sdk::preference::RawPreferences;
+///
+/// let mut prefs = RawPreferences::default();
+/// assert!(!prefs.get_adult_content_enabled());
+/// prefs.set_adult_content_enabled(true);
+/// assert!(prefs.get_adult_content_enabled());
+/// assert_eq!(prefs.into_inner().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawPreferences(defs::Preferences);
+
+impl RawPreferences {
+    /// Create a new instance from the items of `app.bsky.actor.getPreferences`.
+    pub fn new(items: defs::Preferences) -> Self {
+        Self(items)
+    }
+    /// Returns the items, to be sent with `app.bsky.actor.putPreferences`.
+    pub fn into_inner(self) -> defs::Preferences {
+        self.0
+    }
+    /// Returns whether adult content is enabled. Defaults to `false`.
+    pub fn get_adult_content_enabled(&self) -> bool {
+        self.0
+            .iter()
+            .find_map(|item| match item {
+                Union::Refs(PreferencesItem::AdultContentPref(p)) => Some(p.enabled),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+    /// Set whether adult content is enabled.
+    pub fn set_adult_content_enabled(&mut self, enabled: bool) {
+        for item in self.0.iter_mut() {
+            if let Union::Refs(PreferencesItem::AdultContentPref(p)) = item {
+                p.enabled = enabled;
+                return;
+            }
+        }
+        self.0.push(Union::Refs(PreferencesItem::AdultContentPref(Box::new(
+            AdultContentPrefData { enabled }.into(),
+        ))));
+    }
+    /// Returns the saved feeds, from `savedFeedsPrefV2`.
+    pub fn get_saved_feeds(&self) -> Vec<SavedFeed> {
+        self.0
+            .iter()
+            .find_map(|item| match item {
+                Union::Refs(PreferencesItem::SavedFeedsPrefV2(p)) => Some(p.items.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+    /// Set the saved feeds, in `savedFeedsPrefV2`.
+    pub fn set_saved_feeds(&mut self, items: Vec<SavedFeed>) {
+        for item in self.0.iter_mut() {
+            if let Union::Refs(PreferencesItem::SavedFeedsPrefV2(p)) = item {
+                p.items = items;
+                return;
+            }
+        }
+        self.0.push(Union::Refs(PreferencesItem::SavedFeedsPrefV2(Box::new(
+            SavedFeedsPrefV2Data { items }.into(),
+        ))));
+    }
+    /// Returns the muted words.
+    pub fn get_muted_words(&self) -> Vec<MutedWord> {
+        self.0
+            .iter()
+            .find_map(|item| match item {
+                Union::Refs(PreferencesItem::MutedWordsPref(p)) => Some(p.items.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+    /// Set the muted words.
+    pub fn set_muted_words(&mut self, items: Vec<MutedWord>) {
+        for item in self.0.iter_mut() {
+            if let Union::Refs(PreferencesItem::MutedWordsPref(p)) = item {
+                p.items = items;
+                return;
+            }
+        }
+        self.0.push(Union::Refs(PreferencesItem::MutedWordsPref(Box::new(
+            MutedWordsPrefData { items }.into(),
+        ))));
+    }
+    /// Returns the DIDs of the subscribed labelers.
+    pub fn get_labelers(&self) -> Vec<Did> {
+        self.0
+            .iter()
+            .find_map(|item| match item {
+                Union::Refs(PreferencesItem::LabelersPref(p)) => {
+                    Some(p.labelers.iter().map(|labeler| labeler.did.clone()).collect())
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+    /// Subscribe to a labeler. Does nothing if it is already subscribed.
+    pub fn add_labeler(&mut self, did: Did) {
+        let labeler: LabelerPrefItem = LabelerPrefItemData { did }.into();
+        for item in self.0.iter_mut() {
+            if let Union::Refs(PreferencesItem::LabelersPref(p)) = item {
+                if !p.labelers.iter().any(|l| l.did == labeler.did) {
+                    p.labelers.push(labeler);
+                }
+                return;
+            }
+        }
+        self.0.push(Union::Refs(PreferencesItem::LabelersPref(Box::new(
+            LabelersPrefData { labelers: vec![labeler] }.into(),
+        ))));
+    }
+    /// Unsubscribe from a labeler.
+    pub fn remove_labeler(&mut self, did: &Did) {
+        for item in self.0.iter_mut() {
+            if let Union::Refs(PreferencesItem::LabelersPref(p)) = item {
+                p.labelers.retain(|l| &l.did != did);
+            }
+        }
+    }
+}
+
+impl From<defs::Preferences> for RawPreferences {
+    fn from(items: defs::Preferences) -> Self {
+        Self::new(items)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,6 +264,38 @@ mod tests {
         assert_eq!(deserialized1, deserialized2);
     }
 
+    #[test]
+    fn raw_preferences() {
+        let json = r#"[
+            {"$type": "app.bsky.actor.defs#adultContentPref", "enabled": false},
+            {"$type": "app.bsky.actor.defs#unknownPref", "value": 42},
+            {"$type": "app.bsky.actor.defs#labelersPref", "labelers": [], "extra": true}
+        ]"#;
+        let mut prefs = RawPreferences::new(from_str(json).expect("deserializing should succeed"));
+        prefs.set_adult_content_enabled(true);
+        assert!(prefs.get_adult_content_enabled());
+        let labeler = "did:fake:labeler.test".parse::<Did>().expect("invalid did");
+        prefs.add_labeler(labeler.clone());
+        prefs.add_labeler(labeler.clone());
+        assert_eq!(prefs.get_labelers(), vec![labeler.clone()]);
+        assert!(prefs.get_saved_feeds().is_empty());
+        prefs.set_muted_words(Vec::new());
+        let items = prefs.clone().into_inner();
+        assert_eq!(items.len(), 4);
+        assert_eq!(
+            from_str::<Value>(&to_string(&items).expect("serializing should succeed"))
+                .expect("deserializing to value should succeed")[1],
+            serde_json::json!({"$type": "app.bsky.actor.defs#unknownPref", "value": 42})
+        );
+        assert_eq!(
+            from_str::<Value>(&to_string(&items[2]).expect("serializing should succeed"))
+                .expect("deserializing to value should succeed")["extra"],
+            Value::Bool(true)
+        );
+        prefs.remove_labeler(&labeler);
+        assert!(prefs.get_labelers().is_empty());
+    }
+
     #[test]
     fn sdk_preferences_json() {
         let preferences = Preferences {
@@ -1,5 +1,6 @@
 //! Record operations.
 mod agent;
+mod post;
 
 use std::future::Future;
 
@@ -9,9 +10,52 @@ use atrium_api::agent::store::SessionStore;
 use atrium_api::com::atproto::repo::{
     create_record, delete_record, get_record, list_records, put_record,
 };
+use atrium_api::types::string::{AtUri, Cid};
 use atrium_api::types::{Collection, LimitedNonZeroU8, TryIntoUnknown};
 use atrium_api::xrpc::XrpcClient;
 
+pub use self::post::PostBuilder;
+
+/// Options for [`BskyAgent::list_records()`].
+#[derive(Debug, Clone, Default)]
+pub struct ListRecordsOptions {
+    /// The number of records to fetch per request.
+    pub limit: Option<LimitedNonZeroU8<100u8>>,
+    /// List the records in reverse order.
+    pub reverse: bool,
+    /// The lowest record key to start from (exclusive).
+    pub rkey_start: Option<String>,
+    /// The highest record key to stop at (exclusive).
+    pub rkey_end: Option<String>,
+}
+
+/// A write operation on a record of the collection `C`, for [`BskyAgent::apply_writes()`].
+#[derive(Debug, Clone)]
+pub enum Write<C: Collection> {
+    /// Create a record, with a record key generated by the server if `rkey` is `None`.
+    Create { rkey: Option<String>, record: C::Record },
+    /// Update the record with the record key `rkey`.
+    Update { rkey: String, record: C::Record },
+    /// Delete the record with the record key `rkey`.
+    Delete { rkey: String },
+}
+
+/// The result of a [`Write`] applied by [`BskyAgent::apply_writes()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteResult {
+    /// The AT URI and CID of the created record.
+    Create {
+        uri: AtUri,
+        cid: Cid,
+    },
+    /// The AT URI and CID of the new version of the updated record.
+    Update {
+        uri: AtUri,
+        cid: Cid,
+    },
+    Delete,
+}
+
 #[cfg_attr(not(target_arch = "wasm32"), trait_variant::make(Send))]
 pub trait Record<T, S>
 where
@@ -276,6 +320,7 @@ mod tests {
     use crate::agent::BskyAgentBuilder;
     use crate::tests::FAKE_CID;
     use atrium_api::agent::Session;
+    use atrium_api::com::atproto::repo::apply_writes;
     use atrium_api::com::atproto::server::create_session::OutputData;
     use atrium_api::types::string::Datetime;
     use atrium_api::xrpc::http::{Request, Response};
@@ -301,6 +346,131 @@ mod tests {
                         validation_status: None,
                     })?
                 }
+                "/xrpc/com.atproto.repo.putRecord" => {
+                    let input = serde_json::from_slice::<put_record::Input>(request.body())?;
+                    if input
+                        .swap_record
+                        .as_ref()
+                        .is_some_and(|cid| cid.as_ref().to_string() != FAKE_CID)
+                    {
+                        return Ok(Response::builder()
+                            .header(Header::ContentType, "application/json")
+                            .status(400)
+                            .body(br#"{"error":"InvalidSwap"}"#.to_vec())?);
+                    }
+                    serde_json::to_vec(&put_record::OutputData {
+                        cid: FAKE_CID.parse().expect("invalid cid"),
+                        commit: None,
+                        uri: String::from("at://did:fake:handle.test/app.bsky.actor.profile/self"),
+                        validation_status: None,
+                    })?
+                }
+                "/xrpc/com.atproto.repo.getRecord" => {
+                    let rkey = request
+                        .uri()
+                        .query()
+                        .and_then(|query| query.split('&').find_map(|s| s.strip_prefix("rkey=")))
+                        .unwrap_or_default();
+                    if rkey == "notfound" {
+                        return Ok(Response::builder()
+                            .header(Header::ContentType, "application/json")
+                            .status(400)
+                            .body(br#"{"error":"RecordNotFound"}"#.to_vec())?);
+                    }
+                    let reply = (rkey == "reply").then(|| {
+                        let root = serde_json::json!({
+                            "cid": FAKE_CID,
+                            "uri": "at://did:fake:handle.test/app.bsky.feed.post/root",
+                        });
+                        serde_json::json!({ "parent": root, "root": root })
+                    });
+                    serde_json::to_vec(&get_record::OutputData {
+                        cid: (rkey != "nocid").then(|| FAKE_CID.parse().expect("invalid cid")),
+                        uri: format!("at://did:fake:handle.test/app.bsky.feed.post/{rkey}"),
+                        value: serde_json::from_value(serde_json::json!({
+                            "$type": "app.bsky.feed.post",
+                            "createdAt": "2024-01-01T00:00:00.000Z",
+                            "reply": reply,
+                            "text": "text",
+                        }))?,
+                    })?
+                }
+                "/xrpc/com.atproto.repo.listRecords" => {
+                    let query = request.uri().query().unwrap_or_default();
+                    let (rkeys, cursor) = if query.contains("cursor=next") {
+                        (vec!["3"], None)
+                    } else {
+                        (vec!["1", "2"], Some(String::from("next")))
+                    };
+                    serde_json::to_vec(&list_records::OutputData {
+                        cursor,
+                        records: rkeys
+                            .into_iter()
+                            .map(|rkey| {
+                                Ok(list_records::RecordData {
+                                    cid: FAKE_CID.parse().expect("invalid cid"),
+                                    uri: format!(
+                                        "at://did:fake:handle.test/app.bsky.graph.follow/{rkey}"
+                                    ),
+                                    value: serde_json::from_value(serde_json::json!({
+                                        "$type": "app.bsky.graph.follow",
+                                        "createdAt": "2024-01-01T00:00:00.000Z",
+                                        "subject": format!("did:fake:{rkey}.test"),
+                                    }))?,
+                                }
+                                .into())
+                            })
+                            .collect::<serde_json::Result<_>>()?,
+                    })?
+                }
+                "/xrpc/com.atproto.repo.applyWrites" => {
+                    let input = serde_json::from_slice::<apply_writes::Input>(request.body())?;
+                    if input.swap_commit.is_some() {
+                        return Ok(Response::builder()
+                            .header(Header::ContentType, "application/json")
+                            .status(400)
+                            .body(br#"{"error":"InvalidSwap"}"#.to_vec())?);
+                    }
+                    let results = input
+                        .data
+                        .writes
+                        .into_iter()
+                        .map(|write| match write {
+                            apply_writes::InputWritesItem::Create(create) => {
+                                apply_writes::CreateResult::from(apply_writes::CreateResultData {
+                                    cid: FAKE_CID.parse().expect("invalid cid"),
+                                    uri: format!(
+                                        "at://did:fake:handle.test/{}/{}",
+                                        create.collection.as_str(),
+                                        create.rkey.clone().unwrap_or(String::from("generated"))
+                                    ),
+                                    validation_status: None,
+                                })
+                                .into()
+                            }
+                            apply_writes::InputWritesItem::Update(update) => {
+                                apply_writes::UpdateResult::from(apply_writes::UpdateResultData {
+                                    cid: FAKE_CID.parse().expect("invalid cid"),
+                                    uri: format!(
+                                        "at://did:fake:handle.test/{}/{}",
+                                        update.collection.as_str(),
+                                        update.rkey
+                                    ),
+                                    validation_status: None,
+                                })
+                                .into()
+                            }
+                            apply_writes::InputWritesItem::Delete(_) => {
+                                apply_writes::DeleteResult::from(apply_writes::DeleteResultData {})
+                                    .into()
+                            }
+                        })
+                        .collect();
+                    serde_json::to_vec(&apply_writes::OutputData {
+                        commit: None,
+                        results: Some(results),
+                    })?
+                }
                 "/xrpc/com.atproto.repo.deleteRecord" => {
                     serde_json::to_vec(&delete_record::OutputData { commit: None })?
                 }
@@ -369,12 +539,119 @@ mod tests {
             }
             .into()
         );
+        // put
+        let output = agent
+            .put_record(
+                atrium_api::app::bsky::actor::profile::RecordData {
+                    avatar: None,
+                    banner: None,
+                    created_at: None,
+                    description: None,
+                    display_name: Some(String::from("name")),
+                    joined_via_starter_pack: None,
+                    labels: None,
+                    pinned_post: None,
+                },
+                "self",
+            )
+            .await?;
+        assert_eq!(
+            output,
+            put_record::OutputData {
+                cid: FAKE_CID.parse().expect("invalid cid"),
+                commit: None,
+                uri: String::from("at://did:fake:handle.test/app.bsky.actor.profile/self"),
+                validation_status: None,
+            }
+            .into()
+        );
         // delete
         atrium_api::app::bsky::actor::profile::Record::delete(&agent, String::from("somerkey"))
             .await?;
         Ok(())
     }
 
+    #[tokio::test]
+    async fn put_record_if() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient).store(MockSessionStore).build().await?;
+        let uri = "at://did:fake:handle.test/app.bsky.actor.profile/self"
+            .parse()
+            .expect("invalid at uri");
+        let record = atrium_api::app::bsky::actor::profile::RecordData {
+            avatar: None,
+            banner: None,
+            created_at: None,
+            description: None,
+            display_name: Some(String::from("name")),
+            joined_via_starter_pack: None,
+            labels: None,
+            pinned_post: None,
+        };
+        let output = agent
+            .put_record_if(&uri, record.clone(), &FAKE_CID.parse().expect("invalid cid"))
+            .await?;
+        assert_eq!(output.uri, uri.to_string());
+
+        let other_cid = "bafyreie5737gdxlw5i64vzichcalba3z2v5n6icifvx5xytvske7mr3hpm"
+            .parse()
+            .expect("invalid cid");
+        assert!(matches!(
+            agent.put_record_if(&uri, record, &other_cid).await,
+            Err(Error::InvalidSwap(u)) if u == uri.to_string()
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn apply_writes() -> Result<()> {
+        use atrium_api::app::bsky::graph::Follow;
+
+        let agent = BskyAgentBuilder::new(MockClient).store(MockSessionStore).build().await?;
+        let follow = |subject: &str| -> atrium_api::app::bsky::graph::follow::Record {
+            atrium_api::app::bsky::graph::follow::RecordData {
+                created_at: Datetime::now(),
+                subject: subject.parse().expect("invalid did"),
+            }
+            .into()
+        };
+        let results = agent
+            .apply_writes::<Follow>(
+                vec![
+                    Write::Create { rkey: None, record: follow("did:fake:1.test") },
+                    Write::Update { rkey: String::from("2"), record: follow("did:fake:2.test") },
+                    Write::Delete { rkey: String::from("3") },
+                ],
+                None,
+            )
+            .await?;
+        let cid = FAKE_CID.parse::<Cid>().expect("invalid cid");
+        assert_eq!(
+            results,
+            [
+                WriteResult::Create {
+                    uri: "at://did:fake:handle.test/app.bsky.graph.follow/generated"
+                        .parse()
+                        .expect("invalid at uri"),
+                    cid: cid.clone(),
+                },
+                WriteResult::Update {
+                    uri: "at://did:fake:handle.test/app.bsky.graph.follow/2"
+                        .parse()
+                        .expect("invalid at uri"),
+                    cid: cid.clone(),
+                },
+                WriteResult::Delete,
+            ]
+        );
+        assert!(matches!(
+            agent
+                .apply_writes::<Follow>(vec![Write::Delete { rkey: String::from("3") }], Some(cid))
+                .await,
+            Err(Error::InvalidSwap(_))
+        ));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn feed_post() -> Result<()> {
         let agent = BskyAgentBuilder::new(MockClient).store(MockSessionStore).build().await?;
@@ -432,4 +709,87 @@ mod tests {
             .await?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn strong_ref() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient).store(MockSessionStore).build().await?;
+        let uri = "at://handle.test/app.bsky.feed.post/somerkey".parse().expect("invalid at uri");
+        assert_eq!(
+            agent.strong_ref(&uri).await?,
+            atrium_api::com::atproto::repo::strong_ref::MainData {
+                cid: FAKE_CID.parse().expect("invalid cid"),
+                uri: String::from("at://did:fake:handle.test/app.bsky.feed.post/somerkey"),
+            }
+            .into()
+        );
+        let uri = "at://handle.test/app.bsky.feed.post/nocid".parse().expect("invalid at uri");
+        assert!(matches!(agent.strong_ref(&uri).await, Err(Error::MissingCid)));
+        let uri = "at://handle.test".parse().expect("invalid at uri");
+        assert!(matches!(agent.strong_ref(&uri).await, Err(Error::InvalidAtUri)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resolve_reply() -> Result<()> {
+        let agent = BskyAgentBuilder::new(MockClient).store(MockSessionStore).build().await?;
+        let strong_ref = |rkey: &str| -> atrium_api::com::atproto::repo::strong_ref::Main {
+            atrium_api::com::atproto::repo::strong_ref::MainData {
+                cid: FAKE_CID.parse().expect("invalid cid"),
+                uri: format!("at://did:fake:handle.test/app.bsky.feed.post/{rkey}"),
+            }
+            .into()
+        };
+        // reply to a root post
+        let uri = "at://handle.test/app.bsky.feed.post/somerkey".parse().expect("invalid at uri");
+        let reply = agent.resolve_reply(&uri).await?;
+        assert_eq!(reply.parent, strong_ref("somerkey"));
+        assert_eq!(reply.root, strong_ref("somerkey"));
+        // reply to a reply
+        let uri = "at://handle.test/app.bsky.feed.post/reply".parse().expect("invalid at uri");
+        let reply = agent.resolve_reply(&uri).await?;
+        assert_eq!(reply.parent, strong_ref("reply"));
+        assert_eq!(reply.root, strong_ref("root"));
+        // missing record
+        let uri = "at://handle.test/app.bsky.feed.post/notfound".parse().expect("invalid at uri");
+        assert!(matches!(
+            agent.resolve_reply(&uri).await,
+            Err(Error::RecordNotFound(uri)) if uri == "at://handle.test/app.bsky.feed.post/notfound"
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_records() -> Result<()> {
+        use futures::TryStreamExt;
+
+        let agent = BskyAgentBuilder::new(MockClient).store(MockSessionStore).build().await?;
+        let records = agent
+            .list_records::<atrium_api::app::bsky::graph::Follow>(
+                "did:fake:handle.test".parse().expect("invalid at identifier"),
+                ListRecordsOptions::default(),
+            )
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(
+            records
+                .iter()
+                .map(|(uri, record)| (uri.to_string(), record.subject.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    String::from("at://did:fake:handle.test/app.bsky.graph.follow/1"),
+                    "did:fake:1.test"
+                ),
+                (
+                    String::from("at://did:fake:handle.test/app.bsky.graph.follow/2"),
+                    "did:fake:2.test"
+                ),
+                (
+                    String::from("at://did:fake:handle.test/app.bsky.graph.follow/3"),
+                    "did:fake:3.test"
+                ),
+            ]
+        );
+        Ok(())
+    }
 }
@@ -1,11 +1,26 @@
-use super::Record;
+use super::{ListRecordsOptions, Record, Write, WriteResult};
 use crate::error::{Error, Result};
 use crate::BskyAgent;
 use atrium_api::agent::store::SessionStore;
-use atrium_api::com::atproto::repo::{create_record, delete_record};
+use atrium_api::app::bsky::feed::post::{self, ReplyRef, ReplyRefData};
+use atrium_api::app::bsky::feed::Post;
+use atrium_api::com::atproto::repo::{
+    apply_writes, create_record, delete_record, get_record, list_records, put_record, strong_ref,
+};
 use atrium_api::record::KnownRecord;
-use atrium_api::types::string::RecordKey;
+use atrium_api::types::string::{AtIdentifier, AtUri, Cid, RecordKey};
+use atrium_api::types::{Collection, TryFromUnknown, TryIntoUnknown};
 use atrium_api::xrpc::XrpcClient;
+use futures::{stream, Stream};
+use std::collections::VecDeque;
+
+struct ListRecordsState {
+    repo: AtIdentifier,
+    options: ListRecordsOptions,
+    records: VecDeque<list_records::Record>,
+    cursor: Option<String>,
+    done: bool,
+}
 
 impl<T, S> BskyAgent<T, S>
 where
@@ -52,6 +67,317 @@ where
             KnownRecord::ChatBskyActorDeclaration(record) => record.data.create(self).await,
         }
     }
+    /// Create or update a record with the given record key.
+    /// For example, the Record families defined in [`KnownRecord`](atrium_api::record::KnownRecord) are supported.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bsky_sdk::{BskyAgent, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let agent = BskyAgent::builder().build().await?;
+    ///     let output = agent.put_record(atrium_api::app::bsky::actor::profile::RecordData {
+    ///         avatar: None,
+    ///         banner: None,
+    ///         created_at: None,
+    ///         description: None,
+    ///         display_name: Some(String::from("Alice")),
+    ///         joined_via_starter_pack: None,
+    ///         labels: None,
+    ///         pinned_post: None,
+    ///     }, "self").await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn put_record(
+        &self,
+        subject: impl Into<KnownRecord>,
+        rkey: impl Into<String>,
+    ) -> Result<put_record::Output> {
+        let rkey = rkey.into();
+        match subject.into() {
+            KnownRecord::AppBskyActorProfile(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyFeedGenerator(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyFeedLike(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyFeedPost(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyFeedPostgate(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyFeedRepost(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyFeedThreadgate(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyGraphBlock(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyGraphFollow(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyGraphList(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyGraphListblock(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyGraphListitem(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyGraphStarterpack(record) => record.data.put(self, rkey).await,
+            KnownRecord::AppBskyLabelerService(record) => record.data.put(self, rkey).await,
+            KnownRecord::ChatBskyActorDeclaration(record) => record.data.put(self, rkey).await,
+        }
+    }
+    /// Update the record at the given AT URI, only if the CID of its current version is
+    /// `expected_cid`.
+    ///
+    /// This sets `swapRecord` of `com.atproto.repo.putRecord`, so that a change made since the
+    /// record was read is not overwritten (optimistic concurrency control).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidSwap`] if the record has been changed or deleted,
+    /// or an [`Error::InvalidAtUri`] if the `uri` has no collection or record key.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bsky_sdk::{BskyAgent, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let agent = BskyAgent::builder().build().await?;
+    ///     let uri = "at://did:fake:handle.test/app.bsky.actor.profile/self"
+    ///         .parse()
+    ///         .expect("invalid at uri");
+    ///     let current = agent.strong_ref(&uri).await?;
+    ///     let record = atrium_api::app::bsky::actor::profile::RecordData {
+    ///         avatar: None,
+    ///         banner: None,
+    ///         created_at: None,
+    ///         description: None,
+    ///         display_name: Some(String::from("Alice")),
+    ///         joined_via_starter_pack: None,
+    ///         labels: None,
+    ///         pinned_post: None,
+    ///     };
+    ///     match agent.put_record_if(&uri, record, &current.cid).await {
+    ///         Err(Error::InvalidSwap(_)) => println!("the profile has been changed, retry"),
+    ///         result => println!("{:?}", result?),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn put_record_if(
+        &self,
+        uri: &AtUri,
+        subject: impl Into<KnownRecord>,
+        expected_cid: &Cid,
+    ) -> Result<put_record::Output> {
+        let (Some(collection), Some(rkey)) = (uri.collection(), uri.rkey()) else {
+            return Err(Error::InvalidAtUri);
+        };
+        self.api
+            .com
+            .atproto
+            .repo
+            .put_record(
+                put_record::InputData {
+                    collection: collection.clone(),
+                    record: subject.into().try_into_unknown()?,
+                    repo: uri.authority().clone(),
+                    rkey: rkey.as_str().into(),
+                    swap_commit: None,
+                    swap_record: Some(expected_cid.clone()),
+                    validate: None,
+                }
+                .into(),
+            )
+            .await
+            .map_err(|err| match err.as_api_error() {
+                Some(put_record::Error::InvalidSwap(_)) => Error::InvalidSwap(uri.to_string()),
+                None => err.into(),
+            })
+    }
+    /// Apply the writes to the records of the collection `C` in the repo of the session,
+    /// in a single `com.atproto.repo.applyWrites` request.
+    ///
+    /// The writes are applied atomically: if any of them fails, none of them is applied and an
+    /// error is returned. If `swap_commit` is given, the writes are only applied if it is the CID
+    /// of the current commit of the repo. The server limits the number of writes in a request
+    /// (200 for the reference implementation).
+    ///
+    /// Returns the results in the order of the writes. This is empty if the server does not
+    /// return them, as older versions of the reference implementation do.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidSwap`] if `swap_commit` is not the current commit.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use atrium_api::app::bsky::feed::Post;
+    /// use atrium_api::types::string::Datetime;
+    /// use bsky_sdk::record::Write;
+    /// use bsky_sdk::{BskyAgent, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let agent = BskyAgent::builder().build().await?;
+    ///     let writes = ["first", "second"]
+    ///         .into_iter()
+    ///         .map(|text| Write::<Post>::Create {
+    ///             rkey: None,
+    ///             record: atrium_api::app::bsky::feed::post::RecordData {
+    ///                 created_at: Datetime::now(),
+    ///                 embed: None,
+    ///                 entities: None,
+    ///                 facets: None,
+    ///                 labels: None,
+    ///                 langs: None,
+    ///                 reply: None,
+    ///                 tags: None,
+    ///                 text: text.into(),
+    ///             }
+    ///             .into(),
+    ///         })
+    ///         .collect();
+    ///     let results = agent.apply_writes(writes, None).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn apply_writes<C: Collection>(
+        &self,
+        writes: Vec<Write<C>>,
+        swap_commit: Option<Cid>,
+    ) -> Result<Vec<WriteResult>> {
+        let session = self.get_session().await.ok_or(Error::NotLoggedIn)?;
+        let writes = writes
+            .into_iter()
+            .map(|write| {
+                Ok(match write {
+                    Write::Create { rkey, record } => {
+                        apply_writes::Create::from(apply_writes::CreateData {
+                            collection: C::nsid(),
+                            rkey,
+                            value: record.try_into_unknown()?,
+                        })
+                        .into()
+                    }
+                    Write::Update { rkey, record } => {
+                        apply_writes::Update::from(apply_writes::UpdateData {
+                            collection: C::nsid(),
+                            rkey,
+                            value: record.try_into_unknown()?,
+                        })
+                        .into()
+                    }
+                    Write::Delete { rkey } => {
+                        apply_writes::Delete::from(apply_writes::DeleteData {
+                            collection: C::nsid(),
+                            rkey,
+                        })
+                        .into()
+                    }
+                })
+            })
+            .collect::<Result<Vec<apply_writes::InputWritesItem>>>()?;
+        let repo = AtIdentifier::from(session.data.did);
+        let output = self
+            .api
+            .com
+            .atproto
+            .repo
+            .apply_writes(
+                apply_writes::InputData { repo: repo.clone(), swap_commit, validate: None, writes }
+                    .into(),
+            )
+            .await
+            .map_err(|err| match err.as_api_error() {
+                Some(apply_writes::Error::InvalidSwap(_)) => {
+                    Error::InvalidSwap(format!("at://{repo}"))
+                }
+                None => err.into(),
+            })?;
+        output
+            .data
+            .results
+            .unwrap_or_default()
+            .into_iter()
+            .map(|result| {
+                Ok(match result {
+                    apply_writes::OutputResultsItem::CreateResult(result) => WriteResult::Create {
+                        uri: result.uri.parse().or(Err(Error::InvalidAtUri))?,
+                        cid: result.data.cid,
+                    },
+                    apply_writes::OutputResultsItem::UpdateResult(result) => WriteResult::Update {
+                        uri: result.uri.parse().or(Err(Error::InvalidAtUri))?,
+                        cid: result.data.cid,
+                    },
+                    apply_writes::OutputResultsItem::DeleteResult(_) => WriteResult::Delete,
+                })
+            })
+            .collect()
+    }
+    /// List the records of the collection `C` in the given repo as a [`Stream`],
+    /// fetching the following pages with the cursor as needed.
+    ///
+    /// Each record is returned with its AT URI and deserialized into the collection's record type.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields an error and ends if a request fails,
+    /// or if a record cannot be deserialized into `C::Record`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use atrium_api::app::bsky::feed::Post;
+    /// use bsky_sdk::record::ListRecordsOptions;
+    /// use bsky_sdk::{BskyAgent, Result};
+    /// use futures::TryStreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let agent = BskyAgent::builder().build().await?;
+    ///     let repo = "did:fake:handle.test".parse().expect("invalid at identifier");
+    ///     let mut records =
+    ///         std::pin::pin!(agent.list_records::<Post>(repo, ListRecordsOptions::default()));
+    ///     while let Some((uri, record)) = records.try_next().await? {
+    ///         println!("{uri}: {}", record.text);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list_records<C: Collection>(
+        &self,
+        repo: AtIdentifier,
+        options: ListRecordsOptions,
+    ) -> impl Stream<Item = Result<(AtUri, C::Record)>> + '_ {
+        let state =
+            ListRecordsState { repo, options, records: VecDeque::new(), cursor: None, done: false };
+        stream::try_unfold(state, move |mut state| async move {
+            loop {
+                if let Some(record) = state.records.pop_front() {
+                    let uri = record.data.uri.parse::<AtUri>().or(Err(Error::InvalidAtUri))?;
+                    let value = C::Record::try_from_unknown(record.data.value)?;
+                    return Ok(Some(((uri, value), state)));
+                }
+                if state.done {
+                    return Ok(None);
+                }
+                let output = self
+                    .api
+                    .com
+                    .atproto
+                    .repo
+                    .list_records(
+                        list_records::ParametersData {
+                            collection: C::nsid(),
+                            cursor: state.cursor.take(),
+                            limit: state.options.limit,
+                            repo: state.repo.clone(),
+                            reverse: state.options.reverse.then_some(true),
+                            rkey_end: state.options.rkey_end.clone(),
+                            rkey_start: state.options.rkey_start.clone(),
+                        }
+                        .into(),
+                    )
+                    .await?;
+                state.done = output.data.cursor.is_none() || output.data.records.is_empty();
+                state.cursor = output.data.cursor;
+                state.records = output.data.records.into();
+            }
+        })
+    }
     /// Delete a record with AT URI.
     ///
     /// # Errors
@@ -97,4 +423,105 @@ where
             )
             .await?)
     }
+    /// Get a [strong reference](strong_ref::Main) to the current version of the record at the given AT URI,
+    /// e.g. to like, repost or reply to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::InvalidAtUri`] if the `uri` has no collection or record key,
+    /// or an [`Error::MissingCid`] if the record is returned without a CID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bsky_sdk::{BskyAgent, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let agent = BskyAgent::builder().build().await?;
+    ///     let uri = "at://did:fake:handle.test/app.bsky.feed.post/3kxmfwtgfxl2w"
+    ///         .parse()
+    ///         .expect("invalid at uri");
+    ///     let subject = agent.strong_ref(&uri).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn strong_ref(&self, uri: &AtUri) -> Result<strong_ref::Main> {
+        let output = self.get_record_by_uri(uri).await?;
+        let cid = output.data.cid.ok_or(Error::MissingCid)?;
+        Ok(strong_ref::MainData { cid, uri: output.data.uri }.into())
+    }
+    /// Resolve the [`ReplyRef`] for a reply to the record at the given AT URI.
+    ///
+    /// The `parent` is the record itself, and the `root` is the root of the parent's thread
+    /// if the parent is a reply, or the parent itself otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::RecordNotFound`] if the parent record does not exist,
+    /// an [`Error::InvalidAtUri`] if the `parent_uri` has no collection or record key,
+    /// or an [`Error::MissingCid`] if the record is returned without a CID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use bsky_sdk::record::PostBuilder;
+    /// use bsky_sdk::{BskyAgent, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let agent = BskyAgent::builder().build().await?;
+    ///     let parent_uri = "at://did:fake:handle.test/app.bsky.feed.post/3kxmfwtgfxl2w"
+    ///         .parse()
+    ///         .expect("invalid at uri");
+    ///     let reply = agent.resolve_reply(&parent_uri).await?;
+    ///     let record = PostBuilder::new()
+    ///         .text("Hello!")
+    ///         .reply_to(reply.data.parent, reply.data.root)
+    ///         .build()?;
+    ///     agent.create_record(record).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn resolve_reply(&self, parent_uri: &AtUri) -> Result<ReplyRef> {
+        let output = self.get_record_by_uri(parent_uri).await?;
+        let root = if parent_uri.collection().map(|nsid| nsid.as_str()) == Some(Post::NSID) {
+            post::RecordData::try_from_unknown(output.data.value)?
+                .reply
+                .map(|reply| reply.data.root)
+        } else {
+            None
+        };
+        let parent: strong_ref::Main = strong_ref::MainData {
+            cid: output.data.cid.ok_or(Error::MissingCid)?,
+            uri: output.data.uri,
+        }
+        .into();
+        Ok(ReplyRefData { root: root.unwrap_or_else(|| parent.clone()), parent }.into())
+    }
+    pub(crate) async fn get_record_by_uri(&self, uri: &AtUri) -> Result<get_record::Output> {
+        let (Some(collection), Some(rkey)) = (uri.collection(), uri.rkey()) else {
+            return Err(Error::InvalidAtUri);
+        };
+        self.api
+            .com
+            .atproto
+            .repo
+            .get_record(
+                get_record::ParametersData {
+                    cid: None,
+                    collection: collection.clone(),
+                    repo: uri.authority().clone(),
+                    rkey: rkey.as_str().into(),
+                }
+                .into(),
+            )
+            .await
+            .map_err(|err| match err.as_api_error() {
+                Some(get_record::Error::RecordNotFound(_)) => {
+                    Error::RecordNotFound(uri.to_string())
+                }
+                None => err.into(),
+            })
+    }
 }
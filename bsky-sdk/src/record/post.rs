@@ -0,0 +1,232 @@
+use crate::error::{Error, Result};
+#[cfg(feature = "rich-text")]
+use crate::rich_text::RichText;
+use atrium_api::app::bsky::embed::defs::AspectRatio;
+use atrium_api::app::bsky::embed::{external, images};
+use atrium_api::app::bsky::feed::post::{RecordData, ReplyRefData};
+use atrium_api::app::bsky::richtext::facet;
+use atrium_api::com::atproto::repo::strong_ref;
+use atrium_api::types::string::{Datetime, Language};
+use atrium_api::types::BlobRef;
+
+const MAX_IMAGES: usize = 4;
+
+/// A builder for creating an [`app.bsky.feed.post`](atrium_api::app::bsky::feed::post) record.
+///
+/// # Example
+///
+/// ```
+/// use bsky_sdk::record::PostBuilder;
+///
+/// let record = PostBuilder::new()
+///     .text("Hello, world!")
+///     .langs(vec!["en".parse().expect("invalid language")])
+///     .build()
+///     .expect("post should be valid");
+/// assert_eq!(record.text, "Hello, world!");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PostBuilder {
+    text: String,
+    facets: Option<Vec<facet::Main>>,
+    images: Vec<images::Image>,
+    external: Option<external::External>,
+    reply: Option<(strong_ref::Main, strong_ref::Main)>,
+    langs: Option<Vec<Language>>,
+    created_at: Option<Datetime>,
+}
+
+impl PostBuilder {
+    /// Create a new builder for an empty post.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Set the text of the post, without facets.
+    pub fn text(mut self, text: impl AsRef<str>) -> Self {
+        self.text = text.as_ref().into();
+        self.facets = None;
+        self
+    }
+    /// Set the text and facets of the post from a [`RichText`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "rich-text")))]
+    #[cfg(feature = "rich-text")]
+    pub fn rich_text(mut self, rich_text: RichText) -> Self {
+        self.text = rich_text.text;
+        self.facets = rich_text.facets;
+        self
+    }
+    /// Add an image to the post.
+    ///
+    /// Up to 4 images can be added, and they cannot be combined with an external link.
+    pub fn image(self, blob: BlobRef, alt: impl AsRef<str>) -> Self {
+        self.image_with_aspect_ratio(blob, alt, None)
+    }
+    /// Add an image with its aspect ratio to the post.
+    ///
+    /// The aspect ratio can be computed with [`blob::aspect_ratio()`](crate::blob::aspect_ratio).
+    pub fn image_with_aspect_ratio(
+        mut self,
+        blob: BlobRef,
+        alt: impl AsRef<str>,
+        aspect_ratio: Option<AspectRatio>,
+    ) -> Self {
+        self.images
+            .push(images::ImageData { alt: alt.as_ref().into(), aspect_ratio, image: blob }.into());
+        self
+    }
+    /// Set an external link card for the post.
+    ///
+    /// This cannot be combined with images.
+    pub fn external(
+        mut self,
+        uri: impl AsRef<str>,
+        title: impl AsRef<str>,
+        description: impl AsRef<str>,
+        thumb: Option<BlobRef>,
+    ) -> Self {
+        self.external = Some(
+            external::ExternalData {
+                description: description.as_ref().into(),
+                thumb,
+                title: title.as_ref().into(),
+                uri: uri.as_ref().into(),
+            }
+            .into(),
+        );
+        self
+    }
+    /// Make the post a reply to `parent`, in the thread starting at `root`.
+    pub fn reply_to(mut self, parent: strong_ref::Main, root: strong_ref::Main) -> Self {
+        self.reply = Some((parent, root));
+        self
+    }
+    /// Set the languages of the post.
+    pub fn langs(mut self, langs: Vec<Language>) -> Self {
+        self.langs = Some(langs);
+        self
+    }
+    /// Set the creation time of the post. Defaults to the time of [`build()`](Self::build).
+    pub fn created_at(mut self, created_at: Datetime) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+    /// Build the post record.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::IncompatibleEmbeds`] if both images and an external link are set,
+    /// or an [`Error::TooManyImages`] if more than 4 images are added.
+    pub fn build(self) -> Result<RecordData> {
+        let embed = match (self.images.is_empty(), self.external) {
+            (false, Some(_)) => return Err(Error::IncompatibleEmbeds),
+            (false, None) if self.images.len() > MAX_IMAGES => return Err(Error::TooManyImages),
+            (false, None) => {
+                Some(images::Main::from(images::MainData { images: self.images }).into())
+            }
+            (true, Some(external)) => {
+                Some(external::Main::from(external::MainData { external }).into())
+            }
+            (true, None) => None,
+        };
+        Ok(RecordData {
+            created_at: self.created_at.unwrap_or_else(Datetime::now),
+            embed,
+            entities: None,
+            facets: self.facets,
+            labels: None,
+            langs: self.langs,
+            reply: self.reply.map(|(parent, root)| ReplyRefData { parent, root }.into()),
+            tags: None,
+            text: self.text,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::FAKE_CID;
+    use atrium_api::app::bsky::feed::post::RecordEmbedRefs;
+    use atrium_api::types::{Blob, CidLink, TypedBlobRef, Union};
+
+    fn blob() -> BlobRef {
+        BlobRef::Typed(TypedBlobRef::Blob(Blob {
+            r#ref: CidLink(FAKE_CID.parse().expect("invalid cid")),
+            mime_type: String::from("image/png"),
+            size: 1024,
+        }))
+    }
+
+    fn strong_ref(rkey: &str) -> strong_ref::Main {
+        strong_ref::MainData {
+            cid: FAKE_CID.parse().expect("invalid cid"),
+            uri: format!("at://did:fake:handle.test/app.bsky.feed.post/{rkey}"),
+        }
+        .into()
+    }
+
+    #[test]
+    fn build_with_images() -> Result<()> {
+        let record = PostBuilder::new()
+            .text("images")
+            .image(blob(), "first")
+            .image(blob(), "second")
+            .reply_to(strong_ref("parent"), strong_ref("root"))
+            .build()?;
+        let Some(Union::Refs(RecordEmbedRefs::AppBskyEmbedImagesMain(embed))) = record.embed else {
+            panic!("embed should be images");
+        };
+        assert_eq!(
+            embed.images.iter().map(|image| image.alt.as_str()).collect::<Vec<_>>(),
+            vec!["first", "second"]
+        );
+        let reply = record.reply.expect("reply should be set");
+        assert_eq!(reply.parent, strong_ref("parent"));
+        assert_eq!(reply.root, strong_ref("root"));
+        Ok(())
+    }
+
+    #[test]
+    fn build_with_external() -> Result<()> {
+        let record = PostBuilder::new()
+            .text("external")
+            .external("https://example.com", "title", "description", None)
+            .build()?;
+        let Some(Union::Refs(RecordEmbedRefs::AppBskyEmbedExternalMain(embed))) = record.embed
+        else {
+            panic!("embed should be external");
+        };
+        assert_eq!(embed.external.uri, "https://example.com");
+        Ok(())
+    }
+
+    #[test]
+    fn build_invalid() {
+        assert!(matches!(
+            PostBuilder::new()
+                .image(blob(), "image")
+                .external("https://example.com", "title", "description", None)
+                .build(),
+            Err(Error::IncompatibleEmbeds)
+        ));
+        assert!(matches!(
+            (0..5).fold(PostBuilder::new(), |builder, _| builder.image(blob(), "image")).build(),
+            Err(Error::TooManyImages)
+        ));
+    }
+
+    #[cfg(feature = "rich-text")]
+    #[test]
+    fn build_with_rich_text() -> Result<()> {
+        let facets = vec![facet::MainData {
+            features: Vec::new(),
+            index: facet::ByteSliceData { byte_end: 5, byte_start: 0 }.into(),
+        }
+        .into()];
+        let record =
+            PostBuilder::new().rich_text(RichText::new("hello", Some(facets.clone()))).build()?;
+        assert_eq!(record.text, "hello");
+        assert_eq!(record.facets, Some(facets));
+        Ok(())
+    }
+}
@@ -5,7 +5,7 @@ use atrium_api::types::{CidLink, Collection};
 use chrono::Local;
 use firehose::cid_compat::CidOld;
 use firehose::stream::frames::Frame;
-use firehose::subscription::{CommitHandler, Subscription};
+use firehose::subscription::{handle_commit_message, CommitHandler, Subscription};
 use futures::StreamExt;
 use tokio::net::TcpStream;
 use tokio_tungstenite::tungstenite::Message;
@@ -24,8 +24,7 @@ impl RepoSubscription {
         while let Some(result) = self.next().await {
             if let Ok(Frame::Message(Some(t), message)) = result {
                 if t.as_str() == "#commit" {
-                    let commit = serde_ipld_dagcbor::from_reader(message.body.as_slice())?;
-                    if let Err(err) = handler.handle_commit(&commit).await {
+                    if let Err(err) = handle_commit_message(&handler, &message.body).await {
                         eprintln!("FAILED: {err:?}");
                     }
                 }
@@ -45,16 +44,29 @@ impl Subscription for RepoSubscription {
     }
 }
 
+const COLLECTIONS: &[&str] = &[atrium_api::app::bsky::feed::Post::NSID];
+
 struct Firehose;
 
 impl CommitHandler for Firehose {
+    fn collections(&self) -> Option<&[&str]> {
+        Some(COLLECTIONS)
+    }
     async fn handle_commit(&self, commit: &Commit) -> Result<()> {
-        for op in &commit.ops {
-            let collection = op.path.split('/').next().expect("op.path is empty");
-            if op.action != "create" || collection != atrium_api::app::bsky::feed::Post::NSID {
-                continue;
-            }
-            let (items, _) = rs_car::car_read_all(&mut commit.blocks.as_slice(), true).await?;
+        let posts = commit
+            .ops
+            .iter()
+            .filter(|op| {
+                op.action == "create"
+                    && op.path.split('/').next() == Some(atrium_api::app::bsky::feed::Post::NSID)
+            })
+            .collect::<Vec<_>>();
+        // the blocks are read only if the commit creates posts
+        if posts.is_empty() {
+            return Ok(());
+        }
+        let (items, _) = rs_car::car_read_all(&mut commit.blocks.as_slice(), true).await?;
+        for op in posts {
             if let Some((_, item)) = items.iter().find(|(cid, _)| {
                 //
                 // convert cid from v0.10.1 to v0.11.1
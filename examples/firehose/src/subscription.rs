@@ -9,5 +9,31 @@ pub trait Subscription {
 }
 
 pub trait CommitHandler {
+    /// The collections this handler is interested in, or `None` for all collections.
+    ///
+    /// Commits whose ops touch none of these collections are skipped
+    /// before their blocks are read.
+    fn collections(&self) -> Option<&[&str]> {
+        None
+    }
     fn handle_commit(&self, commit: &Commit) -> impl Future<Output = Result<()>>;
 }
+
+/// Pass a `#commit` message to the handler, unless none of its ops touch the handler's
+/// collections.
+pub async fn handle_commit_message(handler: &impl CommitHandler, body: &[u8]) -> Result<()> {
+    let commit = serde_ipld_dagcbor::from_reader::<Commit, _>(body)?;
+    if let Some(collections) = handler.collections() {
+        if !touches_collections(&commit, collections) {
+            return Ok(());
+        }
+    }
+    handler.handle_commit(&commit).await
+}
+
+/// Returns `true` if any op of the commit touches one of the given collections.
+pub fn touches_collections(commit: &Commit, collections: &[&str]) -> bool {
+    commit.ops.iter().any(|op| {
+        op.path.split('/').next().is_some_and(|collection| collections.contains(&collection))
+    })
+}
@@ -3,6 +3,7 @@ use crate::schema::find_ref_unions;
 use crate::token_stream::{
     client, collection, enum_common, impl_into_record, modules, ref_unions, user_type,
 };
+use crate::GenOptions;
 use atrium_lex::lexicon::LexUserType;
 use atrium_lex::LexiconDoc;
 use heck::ToSnakeCase;
@@ -20,6 +21,7 @@ const HEADER: &str = "// @generated - This file is generated by atrium-codegen.
 pub(crate) fn generate_schemas(
     schema: &LexiconDoc,
     outdir: &Path,
+    options: &GenOptions,
 ) -> Result<Vec<PathBuf>, Box<dyn Error>> {
     let mut results = Vec::new();
     let mut paths = schema.id.split('.').collect::<Vec<_>>();
@@ -51,7 +53,11 @@ pub(crate) fn generate_schemas(
             tokens.push(user_type(&schema.defs[name], &schema.id, name, false)?);
         }
         // ref unions
-        tokens.push(ref_unions(&schema.id, &find_ref_unions(&schema.defs))?);
+        tokens.push(ref_unions(
+            &schema.id,
+            &find_ref_unions(&schema.defs),
+            options.non_exhaustive_unions,
+        )?);
 
         let documentation = {
             let doc = format!("Definitions for the `{}` namespace.", schema.id);
@@ -11,10 +11,23 @@ use std::error::Error;
 use std::fs::File;
 use std::path::{Path, PathBuf};
 
+/// Options that control the shape of the generated code.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GenOptions {
+    /// Mark the enums of open unions as `#[non_exhaustive]`.
+    ///
+    /// Open unions are already wrapped in `Union<T>`, whose `Unknown` variant catches
+    /// `$type`s that are not known at generation time. With this option, downstream code
+    /// matching on the enum itself must also have a wildcard arm, so adding a variant in a
+    /// later lexicon update is not a breaking change.
+    pub non_exhaustive_unions: bool,
+}
+
 pub fn genapi(
     lexdir: impl AsRef<Path>,
     outdir: impl AsRef<Path>,
     namespaces: &[(&str, Option<&str>)],
+    options: &GenOptions,
 ) -> Result<Vec<impl AsRef<Path>>, Box<dyn Error>> {
     let lexdir = lexdir.as_ref().canonicalize()?;
     let outdir = outdir.as_ref().canonicalize()?;
@@ -29,7 +42,7 @@ pub fn genapi(
             .iter()
             .filter(|schema| schema.id.starts_with(prefix))
             .collect_vec();
-        results.extend(gen(&outdir, &targets)?);
+        results.extend(gen(&outdir, &targets, options)?);
     }
     results.push(generate_records(&outdir, &schemas, namespaces)?);
     results.push(generate_client(&outdir, &schemas, namespaces)?);
@@ -37,10 +50,14 @@ pub fn genapi(
     Ok(results)
 }
 
-fn gen(outdir: &Path, schemas: &[&LexiconDoc]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+fn gen(
+    outdir: &Path,
+    schemas: &[&LexiconDoc],
+    options: &GenOptions,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
     let mut results = Vec::new();
     for &schema in schemas {
-        results.extend(generate_schemas(schema, outdir)?);
+        results.extend(generate_schemas(schema, outdir, options)?);
     }
     Ok(results)
 }
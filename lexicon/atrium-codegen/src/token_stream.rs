@@ -36,10 +36,18 @@ pub fn user_type(
     })
 }
 
-pub fn ref_unions(schema_id: &str, ref_unions: &[(String, LexRefUnion)]) -> Result<TokenStream> {
+pub fn ref_unions(
+    schema_id: &str,
+    ref_unions: &[(String, LexRefUnion)],
+    non_exhaustive: bool,
+) -> Result<TokenStream> {
     let mut enums = Vec::new();
     for (name, ref_union) in ref_unions {
+        if non_exhaustive && !ref_union.closed.unwrap_or_default() {
+            enums.push(quote!(#[non_exhaustive]));
+        }
         enums.push(refs_enum(&ref_union.refs, name, Some(schema_id))?);
+        enums.push(refs_enum_from_impls(&ref_union.refs, name)?);
     }
     Ok(quote!(#(#enums)*))
 }
@@ -265,6 +273,7 @@ fn lex_object(object: &LexObject, name: &str) -> Result<TokenStream> {
         }
     }
     let mut fields = Vec::new();
+    let mut blob_constraints = Vec::new();
     for key in object.properties.keys().sorted() {
         fields.push(lex_object_property(
             &object.properties[key],
@@ -272,7 +281,19 @@ fn lex_object(object: &LexObject, name: &str) -> Result<TokenStream> {
             required.contains(key),
             name,
         )?);
+        if let LexObjectProperty::Blob(blob) = &object.properties[key] {
+            blob_constraints.extend(blob_constraints_const(blob, key));
+        }
     }
+    let blob_constraints = if blob_constraints.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            impl #struct_name {
+                #(#blob_constraints)*
+            }
+        }
+    };
     Ok(quote! {
         #description
         #derives
@@ -282,6 +303,30 @@ fn lex_object(object: &LexObject, name: &str) -> Result<TokenStream> {
         }
 
         pub type #object_name = crate::types::Object<#struct_name>;
+        #blob_constraints
+    })
+}
+
+fn blob_constraints_const(blob: &LexBlob, name: &str) -> Option<TokenStream> {
+    if blob.accept.is_none() && blob.max_size.is_none() {
+        return None;
+    }
+    let doc = format!("Constraints on the `{}` blob.", name.to_snake_case());
+    let const_name = format_ident!("{}_CONSTRAINTS", name.to_shouty_snake_case());
+    let accept = blob.accept.clone().unwrap_or_default();
+    let max_size = match blob.max_size {
+        Some(max_size) => {
+            let max_size = proc_macro2::Literal::usize_unsuffixed(max_size);
+            quote!(Some(#max_size))
+        }
+        None => quote!(None),
+    };
+    Some(quote! {
+        #[doc = #doc]
+        pub const #const_name: crate::types::BlobConstraints = crate::types::BlobConstraints {
+            accept: &[#(#accept),*],
+            max_size: #max_size,
+        };
     })
 }
 
@@ -556,6 +601,32 @@ fn refs_enum(refs: &[String], name: &str, schema_id: Option<&str>) -> Result<Tok
     enum_common(refs, name, schema_id, &[])
 }
 
+fn refs_enum_from_impls(refs: &[String], name: &str) -> Result<TokenStream> {
+    let enum_name = format_ident!("{name}");
+    let mut impls = Vec::new();
+    for r#ref in refs {
+        let path = resolve_path(r#ref, "main")?;
+        let s = path.to_string().replace(' ', "");
+        let variant_name = format_ident!(
+            "{}",
+            s.strip_prefix("crate::").unwrap_or(&s).split("::").map(str::to_pascal_case).join("")
+        );
+        impls.push(quote! {
+            impl From<#path> for #enum_name {
+                fn from(value: #path) -> Self {
+                    #enum_name::#variant_name(Box::new(value))
+                }
+            }
+            impl From<#path> for crate::types::Union<#enum_name> {
+                fn from(value: #path) -> Self {
+                    crate::types::Union::Refs(value.into())
+                }
+            }
+        });
+    }
+    Ok(quote!(#(#impls)*))
+}
+
 pub fn enum_common(
     refs: &[String],
     name: &str,
@@ -1,4 +1,8 @@
 pub mod lexicon;
+#[cfg(feature = "resolver")]
+pub mod resolver;
+#[cfg(feature = "validation")]
+pub mod validation;
 
 use lexicon::LexUserType;
 use serde::{Deserialize, Serialize};
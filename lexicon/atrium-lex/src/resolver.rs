@@ -0,0 +1,246 @@
+//! Resolution of lexicon schemas published in atproto repositories.
+use crate::LexiconDoc;
+use atrium_api::types::string::{Did, Nsid};
+use atrium_common::resolver::Resolver;
+use atrium_identity::did::DidResolver;
+use atrium_identity::handle::DnsTxtResolver;
+use atrium_xrpc::http::{Request, StatusCode};
+use atrium_xrpc::HttpClient;
+use serde::Deserialize;
+use std::sync::Arc;
+use thiserror::Error;
+
+const SUBDOMAIN: &str = "_lexicon";
+const PREFIX: &str = "did=";
+/// The collection of the records that publish lexicon schemas, keyed by their NSIDs.
+pub const SCHEMA_COLLECTION: &str = "com.atproto.lexicon.schema";
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("no lexicon authority found for `{0}`")]
+    AuthorityNotFound(String),
+    #[error("invalid did: {0}")]
+    Did(String),
+    #[error("did document of `{}` has no pds endpoint", .0.as_str())]
+    PdsNotFound(Did),
+    #[error("invalid lexicon schema record: {0}")]
+    InvalidSchema(String),
+    #[error(transparent)]
+    Identity(#[from] atrium_identity::Error),
+    #[error("dns resolver error: {0}")]
+    DnsResolver(Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    Http(#[from] atrium_xrpc::http::Error),
+    #[error("http client error: {0}")]
+    HttpClient(Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("http status: {0:?}")]
+    HttpStatus(StatusCode),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    SerdeHtmlForm(#[from] serde_html_form::ser::Error),
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Deserialize)]
+struct GetRecordOutput {
+    value: serde_json::Value,
+}
+
+#[derive(Clone, Debug)]
+pub struct LexiconResolverConfig<D, R, T> {
+    pub did_resolver: D,
+    pub dns_txt_resolver: R,
+    pub http_client: Arc<T>,
+}
+
+/// A [`Resolver`] that fetches the lexicon schema of an NSID at runtime.
+///
+/// The DID of the authority is read from the `_lexicon` TXT record of the NSID's domain authority
+/// (e.g. `_lexicon.example.com` for `com.example.fooBar`). The schema is then fetched as the
+/// `com.atproto.lexicon.schema` record with the NSID as its key, from the PDS of the DID.
+pub struct LexiconResolver<D, R, T> {
+    did_resolver: D,
+    dns_txt_resolver: R,
+    http_client: Arc<T>,
+}
+
+impl<D, R, T> LexiconResolver<D, R, T> {
+    pub fn new(config: LexiconResolverConfig<D, R, T>) -> Self {
+        Self {
+            did_resolver: config.did_resolver,
+            dns_txt_resolver: config.dns_txt_resolver,
+            http_client: config.http_client,
+        }
+    }
+}
+
+impl<D, R, T> LexiconResolver<D, R, T>
+where
+    R: DnsTxtResolver + Send + Sync + 'static,
+{
+    /// Resolve the DID of the authority that publishes the schema of the NSID.
+    pub async fn resolve_authority(&self, nsid: &Nsid) -> Result<Did> {
+        let domain = nsid.domain_authority().rsplit('.').collect::<Vec<_>>().join(".");
+        for result in self
+            .dns_txt_resolver
+            .resolve(&format!("{SUBDOMAIN}.{domain}"))
+            .await
+            .map_err(Error::DnsResolver)?
+        {
+            if let Some(did) = result.strip_prefix(PREFIX) {
+                return did.parse::<Did>().map_err(|e| Error::Did(e.to_string()));
+            }
+        }
+        Err(Error::AuthorityNotFound(domain))
+    }
+}
+
+impl<D, R, T> Resolver for LexiconResolver<D, R, T>
+where
+    D: DidResolver + Send + Sync + 'static,
+    R: DnsTxtResolver + Send + Sync + 'static,
+    T: HttpClient + Send + Sync + 'static,
+{
+    type Input = Nsid;
+    type Output = LexiconDoc;
+    type Error = Error;
+
+    async fn resolve(&self, nsid: &Self::Input) -> Result<Self::Output> {
+        let did = self.resolve_authority(nsid).await?;
+        let document = self.did_resolver.resolve(&did).await?;
+        let Some(pds) = document.get_pds_endpoint() else {
+            return Err(Error::PdsNotFound(did));
+        };
+        let uri = format!(
+            "{pds}/xrpc/com.atproto.repo.getRecord?{}",
+            serde_html_form::to_string([
+                ("repo", did.as_str()),
+                ("collection", SCHEMA_COLLECTION),
+                ("rkey", nsid.as_str()),
+            ])?
+        );
+        let res = self
+            .http_client
+            .send_http(Request::builder().uri(uri).body(Vec::new())?)
+            .await
+            .map_err(Error::HttpClient)?;
+        if !res.status().is_success() {
+            return Err(Error::HttpStatus(res.status()));
+        }
+        let value = serde_json::from_slice::<GetRecordOutput>(res.body())?.value;
+        if value.get("$type").and_then(|t| t.as_str()) != Some(SCHEMA_COLLECTION) {
+            return Err(Error::InvalidSchema(format!("record is not a `{SCHEMA_COLLECTION}`")));
+        }
+        let doc = serde_json::from_value::<LexiconDoc>(value)?;
+        if doc.id != nsid.as_str() {
+            return Err(Error::InvalidSchema(format!("schema is for `{}`", doc.id)));
+        }
+        Ok(doc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use atrium_api::did_doc::{DidDocument, Service};
+    use atrium_identity::did::StaticDidResolver;
+    use atrium_xrpc::http::Response;
+
+    struct MockDnsTxtResolver;
+
+    impl DnsTxtResolver for MockDnsTxtResolver {
+        async fn resolve(
+            &self,
+            query: &str,
+        ) -> core::result::Result<Vec<String>, Box<dyn std::error::Error + Send + Sync + 'static>>
+        {
+            Ok(match query {
+                "_lexicon.example.com" => vec![String::from("did=did:plc:lexicon")],
+                _ => Vec::new(),
+            })
+        }
+    }
+
+    const GET_RECORD_URI: &str = "https://pds.test/xrpc/com.atproto.repo.getRecord?repo=did%3Aplc%3Alexicon&collection=com.atproto.lexicon.schema&rkey=";
+
+    struct MockHttpClient;
+
+    impl HttpClient for MockHttpClient {
+        async fn send_http(
+            &self,
+            request: Request<Vec<u8>>,
+        ) -> core::result::Result<
+            Response<Vec<u8>>,
+            Box<dyn std::error::Error + Send + Sync + 'static>,
+        > {
+            let uri = request.uri().to_string();
+            let id = match uri.strip_prefix(GET_RECORD_URI) {
+                Some("com.example.fooBar" | "com.example.other") => "com.example.fooBar",
+                _ => return Ok(Response::builder().status(400).body(Vec::new())?),
+            };
+            let body = serde_json::json!({
+                "uri": format!("at://did:plc:lexicon/{SCHEMA_COLLECTION}/{id}"),
+                "value": {
+                    "$type": SCHEMA_COLLECTION,
+                    "lexicon": 1,
+                    "id": id,
+                    "defs": {
+                        "main": {"type": "token", "description": "A token"},
+                    },
+                },
+            });
+            Ok(Response::builder().status(200).body(serde_json::to_vec(&body)?)?)
+        }
+    }
+
+    fn resolver() -> LexiconResolver<StaticDidResolver, MockDnsTxtResolver, MockHttpClient> {
+        LexiconResolver::new(LexiconResolverConfig {
+            did_resolver: StaticDidResolver::new().with_document(
+                "did:plc:lexicon".parse().expect("did should be valid"),
+                DidDocument {
+                    context: None,
+                    id: String::from("did:plc:lexicon"),
+                    also_known_as: None,
+                    verification_method: None,
+                    service: Some(vec![Service {
+                        id: String::from("#atproto_pds"),
+                        r#type: String::from("AtprotoPersonalDataServer"),
+                        service_endpoint: String::from("https://pds.test"),
+                    }]),
+                },
+            ),
+            dns_txt_resolver: MockDnsTxtResolver,
+            http_client: Arc::new(MockHttpClient),
+        })
+    }
+
+    fn nsid(s: &str) -> Nsid {
+        s.parse().expect("nsid should be valid")
+    }
+
+    #[tokio::test]
+    async fn resolve() {
+        let doc =
+            resolver().resolve(&nsid("com.example.fooBar")).await.expect("resolve should succeed");
+        assert_eq!(doc.id, "com.example.fooBar");
+        assert_eq!(doc.defs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn resolve_invalid() {
+        assert!(matches!(
+            resolver().resolve(&nsid("com.example.other")).await,
+            Err(Error::InvalidSchema(reason)) if reason == "schema is for `com.example.fooBar`"
+        ));
+        assert!(matches!(
+            resolver().resolve(&nsid("com.example.unknown")).await,
+            Err(Error::HttpStatus(StatusCode::BAD_REQUEST))
+        ));
+        assert!(matches!(
+            resolver().resolve(&nsid("org.example.fooBar")).await,
+            Err(Error::AuthorityNotFound(domain)) if domain == "example.org"
+        ));
+    }
+}
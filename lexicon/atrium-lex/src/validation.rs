@@ -0,0 +1,578 @@
+//! Validation of records against lexicon schemas at runtime.
+use crate::lexicon::{
+    LexArray, LexArrayItem, LexBlob, LexBoolean, LexBytes, LexInteger, LexObject,
+    LexObjectProperty, LexRecordRecord, LexRefUnion, LexString, LexStringFormat, LexUserType,
+};
+use crate::LexiconDoc;
+use atrium_api::types::string::{
+    AtIdentifier, AtUri, Cid, Datetime, Did, Handle, Language, Nsid, RecordKey, Tid,
+};
+use atrium_api::types::BlobConstraints;
+use base64::alphabet::STANDARD;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// `$bytes` are encoded with the standard alphabet, usually without padding.
+const BYTES_ENGINE: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// An invalid value found by a [`Validator`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{path}: {message}")]
+pub struct ValidationError {
+    /// The location of the value in the validated data, e.g. `$.embed.images[0].alt`.
+    pub path: String,
+    pub message: String,
+}
+
+/// A validator of records (and other data) against lexicon schemas, for the types that have no
+/// generated code.
+///
+/// Values are in the JSON representation of the atproto data model, e.g. `{"$link": "..."}` for
+/// CID links. All the schemas that are referenced must be registered with
+/// [`with_doc()`](Self::with_doc), or the referencing values are reported as invalid.
+///
+/// # Example
+///
+/// ```
+/// use atrium_lex::validation::Validator;
+/// use atrium_lex::LexiconDoc;
+///
+/// let doc = serde_json::from_str::<LexiconDoc>(
+///     r#"{
+///         "lexicon": 1,
+///         "id": "com.example.status",
+///         "defs": {
+///             "main": {
+///                 "type": "record",
+///                 "key": "tid",
+///                 "record": {
+///                     "type": "object",
+///                     "required": ["text"],
+///                     "properties": {"text": {"type": "string", "maxLength": 8}}
+///                 }
+///             }
+///         }
+///     }"#,
+/// )
+/// .expect("invalid lexicon");
+/// let validator = Validator::new().with_doc(doc);
+/// let record = serde_json::json!({"$type": "com.example.status", "text": "too long text"});
+/// let errors = validator.validate_record("com.example.status", &record).unwrap_err();
+/// assert_eq!(errors[0].to_string(), "$.text: must be at most 8 bytes long");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Validator {
+    docs: HashMap<String, LexiconDoc>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Register a schema, replacing any existing one with the same id.
+    pub fn with_doc(mut self, doc: LexiconDoc) -> Self {
+        self.docs.insert(doc.id.clone(), doc);
+        self
+    }
+    /// Validate a record of the collection `nsid`, whose `main` definition must be a record.
+    ///
+    /// Returns all the errors found in the record.
+    pub fn validate_record(&self, nsid: &str, record: &Value) -> Result<(), Vec<ValidationError>> {
+        let mut context = Context { validator: self, errors: Vec::new() };
+        match self.docs.get(nsid).and_then(|doc| doc.defs.get("main")) {
+            Some(LexUserType::Record(def)) => {
+                let LexRecordRecord::Object(object) = &def.record;
+                if record.is_object() && record.get("$type").and_then(Value::as_str) != Some(nsid) {
+                    context.error("$", format!("`$type` must be `{nsid}`"));
+                }
+                context.object(nsid, object, record, "$");
+            }
+            Some(_) => context.error("$", format!("`{nsid}` is not a record type")),
+            None => context.error("$", format!("unknown record type `{nsid}`")),
+        }
+        context.finish()
+    }
+    /// Validate a value against a definition, e.g. `com.example.defs#view`.
+    ///
+    /// Returns all the errors found in the value.
+    pub fn validate(&self, r#ref: &str, value: &Value) -> Result<(), Vec<ValidationError>> {
+        let mut context = Context { validator: self, errors: Vec::new() };
+        context.r#ref("", r#ref, value, "$");
+        context.finish()
+    }
+}
+
+/// Normalize a reference to the `<nsid>#<name>` form, relative to the schema `doc_id`.
+fn full_ref(doc_id: &str, r#ref: &str) -> String {
+    if r#ref.starts_with('#') {
+        format!("{doc_id}{}", r#ref)
+    } else if r#ref.contains('#') {
+        r#ref.to_string()
+    } else {
+        format!("{}#main", r#ref)
+    }
+}
+
+struct Context<'a> {
+    validator: &'a Validator,
+    errors: Vec<ValidationError>,
+}
+
+impl Context<'_> {
+    fn finish(self) -> Result<(), Vec<ValidationError>> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+    fn error(&mut self, path: &str, message: impl Into<String>) {
+        self.errors.push(ValidationError { path: path.to_string(), message: message.into() });
+    }
+    fn r#ref(&mut self, doc_id: &str, r#ref: &str, value: &Value, path: &str) {
+        let full = full_ref(doc_id, r#ref);
+        let (id, name) = full.split_once('#').expect("full reference should have a name");
+        let validator = self.validator;
+        let Some(def) = validator.docs.get(id).and_then(|doc| doc.defs.get(name)) else {
+            return self.error(path, format!("unresolved reference `{full}`"));
+        };
+        match def {
+            LexUserType::Record(def) => {
+                let LexRecordRecord::Object(object) = &def.record;
+                self.object(id, object, value, path);
+            }
+            LexUserType::Object(object) => self.object(id, object, value, path),
+            LexUserType::Array(array) => self.array(id, array, value, path),
+            LexUserType::Blob(blob) => self.blob(blob, value, path),
+            LexUserType::Boolean(boolean) => self.boolean(boolean, value, path),
+            LexUserType::Integer(integer) => self.integer(integer, value, path),
+            LexUserType::String(string) => self.string(string, value, path),
+            LexUserType::Bytes(bytes) => self.bytes(bytes, value, path),
+            LexUserType::CidLink(_) => self.cid_link(value, path),
+            LexUserType::Unknown(_) => self.unknown(value, path),
+            LexUserType::Token(_)
+            | LexUserType::XrpcQuery(_)
+            | LexUserType::XrpcProcedure(_)
+            | LexUserType::XrpcSubscription(_) => {
+                self.error(path, format!("`{full}` is not a data type"))
+            }
+        }
+    }
+    fn union(&mut self, doc_id: &str, union: &LexRefUnion, value: &Value, path: &str) {
+        let Some(value_type) = value.get("$type").and_then(Value::as_str) else {
+            return self.error(path, "must be an object with `$type`");
+        };
+        let full = full_ref("", value_type);
+        if let Some(r#ref) = union.refs.iter().find(|r#ref| full_ref(doc_id, r#ref) == full) {
+            self.r#ref(doc_id, r#ref, value, path);
+        } else if union.closed.unwrap_or_default() {
+            self.error(path, format!("`{value_type}` is not one of the union types"));
+        }
+    }
+    fn object(&mut self, doc_id: &str, object: &LexObject, value: &Value, path: &str) {
+        let Value::Object(map) = value else {
+            return self.error(path, "must be an object");
+        };
+        let nullable = object.nullable.as_deref().unwrap_or_default();
+        for name in object.required.as_deref().unwrap_or_default() {
+            if !map.contains_key(name) {
+                self.error(path, format!("missing required property `{name}`"));
+            }
+        }
+        for (name, property) in &object.properties {
+            let path = format!("{path}.{name}");
+            match map.get(name) {
+                None => {}
+                Some(Value::Null) if nullable.contains(name) => {}
+                Some(Value::Null) => self.error(&path, "must not be null"),
+                Some(value) => self.property(doc_id, property, value, &path),
+            }
+        }
+    }
+    fn property(&mut self, doc_id: &str, property: &LexObjectProperty, value: &Value, path: &str) {
+        match property {
+            LexObjectProperty::Ref(r#ref) => self.r#ref(doc_id, &r#ref.r#ref, value, path),
+            LexObjectProperty::Union(union) => self.union(doc_id, union, value, path),
+            LexObjectProperty::Bytes(bytes) => self.bytes(bytes, value, path),
+            LexObjectProperty::CidLink(_) => self.cid_link(value, path),
+            LexObjectProperty::Array(array) => self.array(doc_id, array, value, path),
+            LexObjectProperty::Blob(blob) => self.blob(blob, value, path),
+            LexObjectProperty::Boolean(boolean) => self.boolean(boolean, value, path),
+            LexObjectProperty::Integer(integer) => self.integer(integer, value, path),
+            LexObjectProperty::String(string) => self.string(string, value, path),
+            LexObjectProperty::Unknown(_) => self.unknown(value, path),
+        }
+    }
+    fn array(&mut self, doc_id: &str, array: &LexArray, value: &Value, path: &str) {
+        let Value::Array(items) = value else {
+            return self.error(path, "must be an array");
+        };
+        self.length(path, items.len(), array.min_length, array.max_length, "items");
+        for (i, item) in items.iter().enumerate() {
+            let path = format!("{path}[{i}]");
+            match &array.items {
+                LexArrayItem::Boolean(boolean) => self.boolean(boolean, item, &path),
+                LexArrayItem::Integer(integer) => self.integer(integer, item, &path),
+                LexArrayItem::String(string) => self.string(string, item, &path),
+                LexArrayItem::Unknown(_) => self.unknown(item, &path),
+                LexArrayItem::Bytes(bytes) => self.bytes(bytes, item, &path),
+                LexArrayItem::CidLink(_) => self.cid_link(item, &path),
+                LexArrayItem::Blob(blob) => self.blob(blob, item, &path),
+                LexArrayItem::Ref(r#ref) => self.r#ref(doc_id, &r#ref.r#ref, item, &path),
+                LexArrayItem::Union(union) => self.union(doc_id, union, item, &path),
+            }
+        }
+    }
+    fn boolean(&mut self, boolean: &LexBoolean, value: &Value, path: &str) {
+        let Some(value) = value.as_bool() else {
+            return self.error(path, "must be a boolean");
+        };
+        if let Some(c) = boolean.r#const.filter(|c| *c != value) {
+            self.error(path, format!("must be {c}"));
+        }
+    }
+    fn integer(&mut self, integer: &LexInteger, value: &Value, path: &str) {
+        let Some(value) = value.as_i64() else {
+            return self.error(path, "must be an integer");
+        };
+        if let Some(c) = integer.r#const.filter(|c| *c != value) {
+            self.error(path, format!("must be {c}"));
+        }
+        if let Some(e) = integer.r#enum.as_deref().filter(|e| !e.contains(&value)) {
+            self.error(path, format!("must be one of {e:?}"));
+        }
+        if let Some(minimum) = integer.minimum.filter(|minimum| value < *minimum) {
+            self.error(path, format!("must be at least {minimum}"));
+        }
+        if let Some(maximum) = integer.maximum.filter(|maximum| value > *maximum) {
+            self.error(path, format!("must be at most {maximum}"));
+        }
+    }
+    fn string(&mut self, string: &LexString, value: &Value, path: &str) {
+        let Some(value) = value.as_str() else {
+            return self.error(path, "must be a string");
+        };
+        if let Some(c) = string.r#const.as_deref().filter(|c| *c != value) {
+            self.error(path, format!("must be `{c}`"));
+        }
+        if let Some(e) = string.r#enum.as_deref().filter(|e| !e.iter().any(|s| s == value)) {
+            self.error(path, format!("must be one of {e:?}"));
+        }
+        self.length(path, value.len(), string.min_length, string.max_length, "bytes");
+        if string.min_graphemes.is_some() || string.max_graphemes.is_some() {
+            let graphemes = value.graphemes(true).count();
+            self.length(path, graphemes, string.min_graphemes, string.max_graphemes, "graphemes");
+        }
+        if let Some(format) = string.format {
+            if !is_valid_format(format, value) {
+                let name = serde_json::to_string(&format).unwrap_or_default();
+                self.error(path, format!("must be in the `{}` format", name.trim_matches('"')));
+            }
+        }
+    }
+    fn bytes(&mut self, bytes: &LexBytes, value: &Value, path: &str) {
+        let Some(encoded) = value.get("$bytes").and_then(Value::as_str) else {
+            return self.error(path, "must be an object with `$bytes`");
+        };
+        match BYTES_ENGINE.decode(encoded) {
+            Ok(decoded) => {
+                self.length(path, decoded.len(), bytes.min_length, bytes.max_length, "bytes")
+            }
+            Err(e) => self.error(path, format!("invalid base64 of `$bytes`: {e}")),
+        }
+    }
+    fn cid_link(&mut self, value: &Value, path: &str) {
+        match value.get("$link").and_then(Value::as_str) {
+            Some(link) if Cid::from_str(link).is_ok() => {}
+            _ => self.error(path, "must be an object with a CID as `$link`"),
+        }
+    }
+    fn blob(&mut self, blob: &LexBlob, value: &Value, path: &str) {
+        let Value::Object(map) = value else {
+            return self.error(path, "must be a blob");
+        };
+        let Some(mime_type) = map.get("mimeType").and_then(Value::as_str) else {
+            return self.error(path, "must be a blob with `mimeType`");
+        };
+        if let Some(accept) = blob.accept.as_deref() {
+            if !accept.iter().any(|pattern| BlobConstraints::matches(pattern, mime_type)) {
+                self.error(path, format!("mime type `{mime_type}` is not accepted"));
+            }
+        }
+        if let (Some(max_size), Some(size)) = (blob.max_size, blob_size(map)) {
+            if size > max_size as u64 {
+                self.error(path, format!("must be at most {max_size} bytes in size"));
+            }
+        }
+    }
+    fn unknown(&mut self, value: &Value, path: &str) {
+        if !value.is_object() {
+            self.error(path, "must be an object");
+        }
+    }
+    fn length(
+        &mut self,
+        path: &str,
+        length: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+        unit: &str,
+    ) {
+        if let Some(min) = min.filter(|min| length < *min) {
+            self.error(path, format!("must be at least {min} {unit} long"));
+        }
+        if let Some(max) = max.filter(|max| length > *max) {
+            self.error(path, format!("must be at most {max} {unit} long"));
+        }
+    }
+}
+
+/// The size of a blob, or `None` for the legacy format (`{"cid": ..., "mimeType": ...}`).
+fn blob_size(map: &Map<String, Value>) -> Option<u64> {
+    if map.get("$type").and_then(Value::as_str) == Some("blob") {
+        map.get("size").and_then(Value::as_u64)
+    } else {
+        None
+    }
+}
+
+fn is_valid_format(format: LexStringFormat, value: &str) -> bool {
+    match format {
+        LexStringFormat::Datetime => Datetime::from_str(value).is_ok(),
+        LexStringFormat::Uri => value.split_once(':').is_some_and(|(scheme, rest)| {
+            !rest.is_empty()
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }),
+        LexStringFormat::AtUri => AtUri::from_str(value).is_ok(),
+        LexStringFormat::Did => Did::from_str(value).is_ok(),
+        LexStringFormat::Handle => Handle::from_str(value).is_ok(),
+        LexStringFormat::AtIdentifier => AtIdentifier::from_str(value).is_ok(),
+        LexStringFormat::Nsid => Nsid::from_str(value).is_ok(),
+        LexStringFormat::Cid => Cid::from_str(value).is_ok(),
+        LexStringFormat::Language => Language::from_str(value).is_ok(),
+        LexStringFormat::Tid => Tid::from_str(value).is_ok(),
+        LexStringFormat::RecordKey => RecordKey::from_str(value).is_ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CID: &str = "bafyreiclp443lavogvhj3d2ob2cxbfuscni2k5jk7bebjzg7khl3esabwq";
+
+    fn validator() -> Validator {
+        let post = serde_json::from_value::<LexiconDoc>(json!({
+            "lexicon": 1,
+            "id": "com.example.post",
+            "defs": {
+                "main": {
+                    "type": "record",
+                    "key": "tid",
+                    "record": {
+                        "type": "object",
+                        "required": ["text", "createdAt"],
+                        "nullable": ["subject"],
+                        "properties": {
+                            "text": {"type": "string", "maxLength": 30, "maxGraphemes": 5},
+                            "createdAt": {"type": "string", "format": "datetime"},
+                            "langs": {
+                                "type": "array",
+                                "maxLength": 2,
+                                "items": {"type": "string", "format": "language"}
+                            },
+                            "priority": {"type": "integer", "minimum": 1, "maximum": 3},
+                            "subject": {"type": "ref", "ref": "#subject"},
+                            "embed": {
+                                "type": "union",
+                                "refs": ["com.example.embed#images"],
+                                "closed": true
+                            }
+                        }
+                    }
+                },
+                "subject": {
+                    "type": "object",
+                    "required": ["uri", "cid"],
+                    "properties": {
+                        "uri": {"type": "string", "format": "at-uri"},
+                        "cid": {"type": "cid-link"}
+                    }
+                }
+            }
+        }))
+        .expect("invalid lexicon");
+        let embed = serde_json::from_value::<LexiconDoc>(json!({
+            "lexicon": 1,
+            "id": "com.example.embed",
+            "defs": {
+                "images": {
+                    "type": "object",
+                    "required": ["images"],
+                    "properties": {
+                        "images": {
+                            "type": "array",
+                            "items": {"type": "blob", "accept": ["image/*"], "maxSize": 1000}
+                        },
+                        "hash": {"type": "bytes", "maxLength": 4}
+                    }
+                }
+            }
+        }))
+        .expect("invalid lexicon");
+        Validator::new().with_doc(post).with_doc(embed)
+    }
+
+    fn blob(mime_type: &str, size: u64) -> Value {
+        json!({"$type": "blob", "ref": {"$link": CID}, "mimeType": mime_type, "size": size})
+    }
+
+    fn errors(result: Result<(), Vec<ValidationError>>) -> Vec<String> {
+        result.expect_err("validation should fail").iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn valid() {
+        let validator = validator();
+        let record = json!({
+            "$type": "com.example.post",
+            "text": "héllo",
+            "createdAt": "2024-10-01T00:00:00.000Z",
+            "langs": ["en", "ja"],
+            "priority": 2,
+            "subject": {
+                "uri": "at://did:plc:alice/com.example.post/3kxmfwtgfxl2w",
+                "cid": {"$link": CID}
+            },
+            "embed": {
+                "$type": "com.example.embed#images",
+                "images": [blob("image/png", 1000), {"cid": CID, "mimeType": "image/jpeg"}],
+                "hash": {"$bytes": "AQID"}
+            }
+        });
+        assert_eq!(validator.validate_record("com.example.post", &record), Ok(()));
+        let record = json!({
+            "$type": "com.example.post",
+            "text": "",
+            "createdAt": "2024-10-01T00:00:00Z",
+            "subject": null
+        });
+        assert_eq!(validator.validate_record("com.example.post", &record), Ok(()));
+    }
+
+    #[test]
+    fn invalid() {
+        let validator = validator();
+        let record = json!({
+            "$type": "com.example.post",
+            "text": "hello, world",
+            "langs": ["en", "not a language", "ja"],
+            "priority": 0,
+            "subject": {"uri": "https://example.com", "cid": CID},
+            "embed": {
+                "$type": "com.example.embed#images",
+                "images": [blob("video/mp4", 1001)],
+                "hash": {"$bytes": "AQIDBAU"}
+            }
+        });
+        let mut found = errors(validator.validate_record("com.example.post", &record));
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                "$.embed.hash: must be at most 4 bytes long",
+                "$.embed.images[0]: mime type `video/mp4` is not accepted",
+                "$.embed.images[0]: must be at most 1000 bytes in size",
+                "$.langs: must be at most 2 items long",
+                "$.langs[1]: must be in the `language` format",
+                "$.priority: must be at least 1",
+                "$.subject.cid: must be an object with a CID as `$link`",
+                "$.subject.uri: must be in the `at-uri` format",
+                "$.text: must be at most 5 graphemes long",
+                "$: missing required property `createdAt`",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_types() {
+        let validator = validator();
+        let record = json!({
+            "$type": "com.example.other",
+            "text": 1,
+            "createdAt": null,
+            "embed": {"$type": "com.example.embed#video"}
+        });
+        let mut found = errors(validator.validate_record("com.example.post", &record));
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                "$.createdAt: must not be null",
+                "$.embed: `com.example.embed#video` is not one of the union types",
+                "$.text: must be a string",
+                "$: `$type` must be `com.example.post`",
+            ]
+        );
+        assert_eq!(
+            errors(validator.validate_record("com.example.unknown", &record)),
+            ["$: unknown record type `com.example.unknown`"]
+        );
+        assert_eq!(
+            errors(validator.validate("com.example.post#missing", &json!({}))),
+            ["$: unresolved reference `com.example.post#missing`"]
+        );
+        assert_eq!(
+            errors(
+                validator
+                    .validate("com.example.post#subject", &json!({"uri": "at://did:plc:alice"}))
+            ),
+            ["$: missing required property `cid`"]
+        );
+    }
+
+    #[test]
+    fn accept_patterns() {
+        let doc = serde_json::from_value::<LexiconDoc>(json!({
+            "lexicon": 1,
+            "id": "com.example.file",
+            "defs": {
+                "any": {
+                    "type": "object",
+                    "properties": {"file": {"type": "blob", "accept": ["*/*"]}}
+                },
+                "text": {
+                    "type": "object",
+                    "properties": {"file": {"type": "blob", "accept": ["text/*"]}}
+                }
+            }
+        }))
+        .expect("invalid lexicon");
+        let validator = Validator::new().with_doc(doc);
+        for mime_type in ["text/plain", "video/mp4", "application/octet-stream"] {
+            let value = json!({"file": blob(mime_type, 1)});
+            assert_eq!(validator.validate("com.example.file#any", &value), Ok(()));
+        }
+        assert_eq!(
+            validator.validate("com.example.file#text", &json!({"file": blob("text/plain", 1)})),
+            Ok(())
+        );
+        assert_eq!(
+            errors(
+                validator.validate("com.example.file#text", &json!({"file": blob("video/mp4", 1)}))
+            ),
+            ["$.file: mime type `video/mp4` is not accepted"]
+        );
+    }
+}
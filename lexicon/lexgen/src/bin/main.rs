@@ -1,4 +1,4 @@
-use atrium_codegen::genapi;
+use atrium_codegen::{genapi, GenOptions};
 use clap::Parser;
 use std::fs;
 use std::path::PathBuf;
@@ -9,6 +9,9 @@ struct Args {
     lexdir: PathBuf,
     #[arg(short, long, default_value = "../atrium-api/src")]
     outdir: PathBuf,
+    /// Mark the enums of open unions as `#[non_exhaustive]`
+    #[arg(long)]
+    non_exhaustive_unions: bool,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -23,6 +26,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             ("chat.bsky", Some("namespace-chatbsky")),
             ("tools.ozone", Some("namespace-toolsozone")),
         ],
+        &GenOptions {
+            non_exhaustive_unions: args.non_exhaustive_unions,
+        },
     )?;
     for path in &results {
         println!(